@@ -3,6 +3,9 @@ use rocket::serde::json::Json;
 
 #[derive(Debug, Responder)]
 pub enum Error {
+    #[response(status = 409, content_type = "json")]
+    Conflict(Json<ApplicationError>),
+
     #[response(status = 400, content_type = "json")]
     InvalidArgument(Json<ApplicationError>),
 
@@ -35,6 +38,7 @@ impl Error {
 impl From<ApplicationError> for Error {
     fn from(value: ApplicationError) -> Self {
         match value {
+            ApplicationError::Conflict => Error::Conflict(Json(value)),
             ApplicationError::InvalidArgument { .. } => Error::bad_request(value),
             ApplicationError::NotFound => Error::NotFound(Json(value)),
             ApplicationError::Internal { .. } => Error::internal(value),
@@ -1,6 +1,147 @@
 use crate::{Error, Result};
+use canopus_definitions::{ApplicationError, PageSize, RemarkKindName, TagTitle};
+use chrono::{DateTime, FixedOffset, SecondsFormat, Utc};
+use rocket::{
+    Request,
+    http::Header,
+    request::{FromRequest, Outcome},
+    serde::{Serialize, json::Json},
+};
+use std::{collections::BTreeSet, convert::Infallible, str::FromStr};
 use uuid::Uuid;
 
+pub struct IdempotencyKey(pub Option<String>);
+
+pub struct IfMatch(Option<String>);
+
+#[derive(Responder)]
+pub struct Versioned<T: Serialize> {
+    inner: Json<T>,
+    etag: Header<'static>,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IdempotencyKey {
+    type Error = Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let key = request
+            .headers()
+            .get_one("Idempotency-Key")
+            .map(ToString::to_string);
+
+        Outcome::Success(IdempotencyKey(key))
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IfMatch {
+    type Error = Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let if_match = request
+            .headers()
+            .get_one("If-Match")
+            .map(ToString::to_string);
+
+        Outcome::Success(IfMatch(if_match))
+    }
+}
+
+impl<T: Serialize> Versioned<T> {
+    pub fn new(inner: T, updated_at: DateTime<Utc>) -> Self {
+        Versioned {
+            inner: Json(inner),
+            etag: Header::new("ETag", format_etag(updated_at)),
+        }
+    }
+}
+
+fn format_etag(updated_at: DateTime<Utc>) -> String {
+    format!(
+        "\"{}\"",
+        updated_at.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+    )
+}
+
+pub fn parse_datetime(
+    datetime: Option<String>,
+    now: DateTime<Utc>,
+    offset: FixedOffset,
+) -> Result<Option<DateTime<Utc>>> {
+    let datetime = datetime
+        .map(|datetime| canopus_definitions::parse_datetime(&datetime, now, &offset))
+        .transpose()?;
+
+    Ok(datetime)
+}
+
 pub fn parse_id(id: &str) -> Result<Uuid> {
     id.parse().map_err(|_err| Error::invalid_id())
 }
+
+pub fn parse_if_match(if_match: IfMatch) -> Result<Option<DateTime<Utc>>> {
+    let IfMatch(Some(if_match)) = if_match else {
+        return Ok(None);
+    };
+
+    let if_match = if_match.trim();
+
+    if if_match == "*" {
+        return Ok(None);
+    }
+
+    let updated_at = if_match
+        .strip_prefix('"')
+        .and_then(|etag| etag.strip_suffix('"'))
+        .and_then(|etag| DateTime::parse_from_rfc3339(etag).ok())
+        .ok_or_else(|| {
+            Error::from(ApplicationError::invalid_argument(
+                "If-Match must be an ETag returned by the service",
+            ))
+        })?;
+
+    Ok(Some(updated_at.to_utc()))
+}
+
+pub fn parse_order_by<T>(order_by: Option<String>) -> Result<T>
+where
+    T: Default + FromStr<Err = ApplicationError>,
+{
+    let order_by = order_by.as_deref().map(str::parse).transpose()?;
+
+    Ok(order_by.unwrap_or_default())
+}
+
+pub fn parse_remark_kind(kind: Option<String>) -> Result<Option<RemarkKindName>> {
+    let kind = kind.as_deref().map(str::parse).transpose()?;
+
+    Ok(kind)
+}
+
+pub fn parse_page_size(page_size: Option<i64>) -> Result<Option<PageSize>> {
+    let page_size = page_size.map(PageSize::new).transpose()?;
+
+    Ok(page_size)
+}
+
+pub fn parse_time_zone(tz: Option<String>) -> Result<FixedOffset> {
+    let Some(tz) = tz else {
+        return Ok(FixedOffset::east_opt(0).expect("UTC offset should be valid"));
+    };
+
+    tz.parse().map_err(|_err| {
+        Error::from(ApplicationError::invalid_argument(
+            "time zone must be a UTC offset like +02:00",
+        ))
+    })
+}
+
+pub fn parse_tag_titles(titles: Vec<String>) -> Result<BTreeSet<TagTitle>> {
+    let titles = titles
+        .into_iter()
+        .map(TagTitle::new)
+        .collect::<canopus_definitions::ApplicationResult<BTreeSet<TagTitle>>>()?;
+
+    Ok(titles)
+}
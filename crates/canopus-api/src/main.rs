@@ -7,7 +7,15 @@ mod tracer;
 use canopus_definitions::ApplicationError;
 use canopus_engine::Engine;
 use error::Error;
-use rocket::{Request, serde::json::Json};
+use rocket::{
+    Config, Request,
+    data::{Limits, ToByteUnit},
+    figment::{
+        Figment,
+        providers::{Env, Format, Serialized, Toml},
+    },
+    serde::json::Json,
+};
 
 type Result<T> = std::result::Result<T, Error>;
 
@@ -19,14 +27,35 @@ async fn main() -> eyre::Result<()> {
     let _guard = tracer::init_subscriber();
     let engine = Engine::start().await?;
 
-    let _rocket = rocket::build()
+    let _rocket = rocket::custom(figment())
+        .mount("/", routes![remarks::export])
+        .mount("/", routes![remarks::import])
+        .mount("/tags", routes![tags::children])
         .mount("/tags", routes![tags::index])
+        .mount("/tags", routes![tags::merge])
         .mount("/tags", routes![tags::show])
+        .mount("/tags", routes![tags::suggest])
         .mount("/tags", routes![tags::update])
+        .mount("/remarks", routes![remarks::add_tags])
+        .mount("/remarks", routes![remarks::attachments])
+        .mount("/remarks", routes![remarks::backlinks])
         .mount("/remarks", routes![remarks::create])
+        .mount("/remarks", routes![remarks::create_attachment])
+        .mount("/remarks", routes![remarks::create_bulk])
         .mount("/remarks", routes![remarks::delete])
+        .mount("/remarks", routes![remarks::delete_attachment])
+        .mount("/remarks", routes![remarks::download_attachment])
         .mount("/remarks", routes![remarks::index])
+        .mount("/remarks", routes![remarks::links])
+        .mount("/remarks", routes![remarks::remove_tag])
+        .mount("/remarks", routes![remarks::remove_tags])
+        .mount("/remarks", routes![remarks::restore])
+        .mount("/remarks", routes![remarks::restore_revision])
+        .mount("/remarks", routes![remarks::revisions])
+        .mount("/remarks", routes![remarks::search])
         .mount("/remarks", routes![remarks::show])
+        .mount("/remarks", routes![remarks::toggle_item])
+        .mount("/remarks", routes![remarks::trash])
         .mount("/remarks", routes![remarks::update])
         .register("/", catchers![not_found, internal_error])
         .manage(engine)
@@ -36,6 +65,17 @@ async fn main() -> eyre::Result<()> {
     Ok(())
 }
 
+fn figment() -> Figment {
+    let limits = Limits::default()
+        .limit("file", 32.mebibytes())
+        .limit("data-form", 32.mebibytes());
+
+    Figment::from(Config::default())
+        .merge(Serialized::default("limits", limits))
+        .merge(Toml::file(Env::var_or("ROCKET_CONFIG", "Rocket.toml")).nested())
+        .merge(Env::prefixed("ROCKET_").ignore(&["PROFILE"]).global())
+}
+
 #[catch(404)]
 #[tracing::instrument(name = "Not found error catcher")]
 fn not_found(req: &Request) -> Error {
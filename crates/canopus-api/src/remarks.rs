@@ -1,47 +1,418 @@
-use crate::{Result, helpers};
-use canopus_definitions::{Page, PageToken, Remark};
+use crate::{
+    Error, Result,
+    helpers::{self, IdempotencyKey, IfMatch, Versioned},
+};
+use canopus_definitions::{
+    ApplicationError, Page, PageSize, PageToken, Remark, RemarkAttachment, RemarkBulkOutcome,
+    RemarkImportFailure, RemarkImportFailureAttributes, RemarkLink, RemarkRevision,
+    RemarkSearchHit, RemarksImportSummary, RemarksImportSummaryAttributes,
+};
 use canopus_engine::{Engine, remarks};
-use canopus_operations::remarks::{NewRemarkAttributes, RemarkChanges, RemarksPageParameters};
+use canopus_operations::remarks::{
+    BulkNewRemarkAttributes, ImportedRemarkAttributes, NewRemarkAttachmentAttributes,
+    NewRemarkAttributes, RemarkChanges, RemarkKindAttributes, RemarksDatesFilter, RemarksOrderBy,
+    RemarksPageParameters, RemarksSearchParameters, RemarksTagsFilter,
+};
+use chrono::{DateTime, Utc};
 use rocket::{
-    State,
+    Data, Request, State,
+    data::{ByteUnit, Limits},
+    form::Form,
+    fs::TempFile,
+    http::{
+        ContentType, Header,
+        uri::{Segments, fmt::Path},
+    },
+    response::{self, Responder, stream::TextStream},
     serde::{Deserialize, json::Json},
+    tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader},
 };
+use uuid::Uuid;
+
+#[derive(Responder)]
+pub struct AttachmentDownload {
+    content: (ContentType, Vec<u8>),
+    disposition: Header<'static>,
+}
+
+#[derive(FromForm)]
+pub struct AttachmentForm<'r> {
+    file: TempFile<'r>,
+}
+
+pub struct RemarksExport<'a>(&'a Engine);
+
+#[derive(Debug, FromForm)]
+pub struct RemarksQuery {
+    page_token: Option<String>,
+    page_size: Option<i64>,
+    order_by: Option<String>,
+    all_tags: Vec<String>,
+    any_tags: Vec<String>,
+    exclude_tags: Vec<String>,
+    created_after: Option<String>,
+    created_before: Option<String>,
+    updated_after: Option<String>,
+    updated_before: Option<String>,
+    kind: Option<String>,
+    tz: Option<String>,
+}
+
+#[post("/<id>/tags", data = "<form>")]
+#[tracing::instrument(skip(engine, if_match), name = "Add remark tags", err(Debug))]
+pub async fn add_tags(
+    engine: &State<Engine>,
+    id: &str,
+    if_match: IfMatch,
+    form: Option<Json<RemarkTagsForm>>,
+) -> Result<Versioned<Remark>> {
+    let id = helpers::parse_id(id)?;
+    let expected_updated_at = helpers::parse_if_match(if_match)?;
+
+    let tags = form
+        .and_then(|form| form.into_inner().tags)
+        .unwrap_or_default();
+
+    let remark = remarks::add_remark_tags(engine, id, tags, expected_updated_at).await?;
+    let updated_at = remark.updated_at();
+
+    Ok(Versioned::new(remark, updated_at))
+}
+
+#[get("/<id>/attachments")]
+#[tracing::instrument(skip(engine), name = "Remark attachments index", err(Debug))]
+pub async fn attachments(engine: &State<Engine>, id: &str) -> Result<Json<Page<RemarkAttachment>>> {
+    let id = helpers::parse_id(id)?;
+
+    let page = remarks::list_remark_attachments(engine, id).await?;
+
+    Ok(Json(page))
+}
+
+#[get("/<id>/backlinks")]
+#[tracing::instrument(skip(engine), name = "Remark backlinks index", err(Debug))]
+pub async fn backlinks(engine: &State<Engine>, id: &str) -> Result<Json<Page<RemarkLink>>> {
+    let id = helpers::parse_id(id)?;
+
+    let page = remarks::list_remark_backlinks(engine, id).await?;
+
+    Ok(Json(page))
+}
 
 #[post("/", data = "<form>")]
-#[tracing::instrument(skip(engine), name = "Create remark", err(Debug))]
+#[tracing::instrument(skip(engine, idempotency_key), name = "Create remark", err(Debug))]
 pub async fn create(
     engine: &State<Engine>,
+    idempotency_key: IdempotencyKey,
     form: Option<Json<NewRemarkForm>>,
-) -> Result<Json<Remark>> {
+) -> Result<Versioned<Remark>> {
     let new_remark_attributes = form
         .map(|form| form.into_inner().into())
         .unwrap_or_else(NewRemarkAttributes::empty);
 
-    let remark = remarks::create_remark(engine, new_remark_attributes).await?;
+    let remark = match idempotency_key {
+        IdempotencyKey(Some(key)) => {
+            remarks::create_idempotent_remark(engine, key, new_remark_attributes).await?
+        }
+        IdempotencyKey(None) => remarks::create_remark(engine, new_remark_attributes).await?,
+    };
+    let updated_at = remark.updated_at();
 
-    Ok(Json(remark))
+    Ok(Versioned::new(remark, updated_at))
+}
+
+#[post("/bulk", data = "<form>")]
+#[tracing::instrument(skip(engine, form), name = "Create remarks in bulk", err(Debug))]
+pub async fn create_bulk(
+    engine: &State<Engine>,
+    form: Option<Json<BulkRemarksForm>>,
+) -> Result<Json<Vec<RemarkBulkOutcome>>> {
+    let BulkRemarksForm { remarks, dry_run } = form
+        .map(Json::into_inner)
+        .ok_or_else(|| ApplicationError::invalid_argument("no remarks provided"))?;
+
+    let outcomes = remarks::create_remarks(
+        engine,
+        remarks.into_iter().map(Into::into).collect(),
+        dry_run,
+    )
+    .await?;
+
+    Ok(Json(outcomes))
+}
+
+#[post("/<id>/attachments", data = "<form>")]
+#[tracing::instrument(skip(engine, form), name = "Create remark attachment", err(Debug))]
+pub async fn create_attachment(
+    engine: &State<Engine>,
+    id: &str,
+    form: Form<AttachmentForm<'_>>,
+) -> Result<Json<RemarkAttachment>> {
+    let id = helpers::parse_id(id)?;
+
+    let attributes = form.into_inner().into_attributes().await?;
+
+    let attachment = remarks::create_remark_attachment(engine, id, attributes).await?;
+
+    Ok(Json(attachment))
 }
 
-#[delete("/<id>")]
-#[tracing::instrument(skip(engine), name = "Delete remark", err(Debug))]
-pub async fn delete(engine: &State<Engine>, id: &str) -> Result<Json<Remark>> {
+#[delete("/<id>?<permanent>")]
+#[tracing::instrument(skip(engine, if_match), name = "Delete remark", err(Debug))]
+pub async fn delete(
+    engine: &State<Engine>,
+    id: &str,
+    permanent: Option<bool>,
+    if_match: IfMatch,
+) -> Result<Json<Remark>> {
     let id = helpers::parse_id(id)?;
+    let expected_updated_at = helpers::parse_if_match(if_match)?;
 
-    let remark = remarks::delete_remark(engine, id).await?;
+    let remark = if permanent.unwrap_or_default() {
+        remarks::delete_remark_permanently(engine, id, expected_updated_at).await?
+    } else {
+        remarks::delete_remark(engine, id, expected_updated_at).await?
+    };
 
     Ok(Json(remark))
 }
 
-#[get("/?<page_token>")]
-#[tracing::instrument(skip(engine), name = "Remarks index", err(Debug))]
-pub async fn index(
+#[delete("/<id>/attachments/<attachment_id>")]
+#[tracing::instrument(skip(engine), name = "Delete remark attachment", err(Debug))]
+pub async fn delete_attachment(
     engine: &State<Engine>,
-    page_token: Option<String>,
-) -> Result<Json<Page<Remark>>> {
+    id: &str,
+    attachment_id: &str,
+) -> Result<Json<RemarkAttachment>> {
+    let id = helpers::parse_id(id)?;
+    let attachment_id = helpers::parse_id(attachment_id)?;
+
+    let attachment = remarks::delete_remark_attachment(engine, id, attachment_id).await?;
+
+    Ok(Json(attachment))
+}
+
+#[get("/<id>/attachments/<attachment_id>")]
+#[tracing::instrument(skip(engine), name = "Download remark attachment", err(Debug))]
+pub async fn download_attachment(
+    engine: &State<Engine>,
+    id: &str,
+    attachment_id: &str,
+) -> Result<AttachmentDownload> {
+    let id = helpers::parse_id(id)?;
+    let attachment_id = helpers::parse_id(attachment_id)?;
+
+    let (attachment, content) =
+        remarks::get_remark_attachment_content(engine, id, attachment_id).await?;
+
+    Ok(attachment_download(attachment, content))
+}
+
+#[get("/export")]
+#[tracing::instrument(skip(engine), name = "Export remarks")]
+pub fn export(engine: &State<Engine>) -> RemarksExport<'_> {
+    RemarksExport(engine)
+}
+
+#[post("/import", data = "<data>")]
+#[tracing::instrument(skip(engine, limits, data), name = "Import remarks", err(Debug))]
+pub async fn import(
+    engine: &State<Engine>,
+    limits: &Limits,
+    data: Data<'_>,
+) -> Result<Json<RemarksImportSummary>> {
+    let limit = limits.get("import").unwrap_or(ByteUnit::max_value());
+    let mut lines = BufReader::new(data.open(limit)).lines();
+
+    let mut line_number = 0;
+    let mut imported = 0;
+    let mut skipped = 0;
+    let mut failures = Vec::new();
+
+    while let Some(line) = lines
+        .next_line()
+        .await
+        .map_err(|err| ApplicationError::internal("failed to read import", err))?
+    {
+        line_number += 1;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let outcome = match rocket::serde::json::from_str::<ImportedRemarkForm>(&line) {
+            Ok(form) => remarks::import_remark(engine, form.into()).await,
+            Err(err) => Err(ApplicationError::invalid_argument(&err.to_string())),
+        };
+
+        // Invalid lines are reported and the rest of the stream is still imported
+        match outcome {
+            Ok(true) => imported += 1,
+            Ok(false) => skipped += 1,
+            Err(ApplicationError::InvalidArgument(message)) => {
+                failures.push(RemarkImportFailure::new(RemarkImportFailureAttributes {
+                    line: line_number,
+                    message,
+                }))
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    Ok(Json(RemarksImportSummary::new(
+        RemarksImportSummaryAttributes {
+            imported,
+            skipped,
+            failures,
+        },
+    )))
+}
+
+#[get("/?<query..>")]
+#[tracing::instrument(skip(engine), name = "Remarks index", err(Debug))]
+pub async fn index(engine: &State<Engine>, query: RemarksQuery) -> Result<Json<Page<Remark>>> {
+    let RemarksQuery {
+        page_token,
+        page_size,
+        order_by,
+        all_tags,
+        any_tags,
+        exclude_tags,
+        created_after,
+        created_before,
+        updated_after,
+        updated_before,
+        kind,
+        tz,
+    } = query;
+
+    let now = Utc::now();
+    let offset = helpers::parse_time_zone(tz)?;
+
     let page = remarks::list_remarks(
         engine,
         RemarksPageParameters {
             page_token: page_token.map(PageToken::from),
+            page_size: helpers::parse_page_size(page_size)?,
+            order_by: helpers::parse_order_by(order_by)?,
+            dates_filter: RemarksDatesFilter {
+                created_after: helpers::parse_datetime(created_after, now, offset)?,
+                created_before: helpers::parse_datetime(created_before, now, offset)?,
+                updated_after: helpers::parse_datetime(updated_after, now, offset)?,
+                updated_before: helpers::parse_datetime(updated_before, now, offset)?,
+            },
+            tags_filter: RemarksTagsFilter {
+                include_all: helpers::parse_tag_titles(all_tags)?,
+                include_any: helpers::parse_tag_titles(any_tags)?,
+                exclude: helpers::parse_tag_titles(exclude_tags)?,
+            },
+            kind: helpers::parse_remark_kind(kind)?,
+            trashed: false,
+        },
+    )
+    .await?;
+
+    Ok(Json(page))
+}
+
+#[get("/<id>/links")]
+#[tracing::instrument(skip(engine), name = "Remark links index", err(Debug))]
+pub async fn links(engine: &State<Engine>, id: &str) -> Result<Json<Page<RemarkLink>>> {
+    let id = helpers::parse_id(id)?;
+
+    let page = remarks::list_remark_links(engine, id).await?;
+
+    Ok(Json(page))
+}
+
+#[delete("/<id>/tags/<title..>")]
+#[tracing::instrument(skip(engine, if_match), name = "Remove remark tag", err(Debug))]
+pub async fn remove_tag(
+    engine: &State<Engine>,
+    id: &str,
+    title: Segments<'_, Path>,
+    if_match: IfMatch,
+) -> Result<Versioned<Remark>> {
+    let id = helpers::parse_id(id)?;
+    let expected_updated_at = helpers::parse_if_match(if_match)?;
+
+    let title = title.collect::<Vec<&str>>().join("/");
+
+    let remark = remarks::remove_remark_tags(engine, id, vec![title], expected_updated_at).await?;
+    let updated_at = remark.updated_at();
+
+    Ok(Versioned::new(remark, updated_at))
+}
+
+#[delete("/<id>/tags?<title>")]
+#[tracing::instrument(skip(engine, if_match), name = "Remove remark tags", err(Debug))]
+pub async fn remove_tags(
+    engine: &State<Engine>,
+    id: &str,
+    title: Vec<String>,
+    if_match: IfMatch,
+) -> Result<Versioned<Remark>> {
+    let id = helpers::parse_id(id)?;
+    let expected_updated_at = helpers::parse_if_match(if_match)?;
+
+    let remark = remarks::remove_remark_tags(engine, id, title, expected_updated_at).await?;
+    let updated_at = remark.updated_at();
+
+    Ok(Versioned::new(remark, updated_at))
+}
+
+#[post("/<id>/restore")]
+#[tracing::instrument(skip(engine, if_match), name = "Restore remark", err(Debug))]
+pub async fn restore(engine: &State<Engine>, id: &str, if_match: IfMatch) -> Result<Json<Remark>> {
+    let id = helpers::parse_id(id)?;
+    let expected_updated_at = helpers::parse_if_match(if_match)?;
+
+    let remark = remarks::restore_remark(engine, id, expected_updated_at).await?;
+
+    Ok(Json(remark))
+}
+
+#[post("/<id>/revisions/<number>/restore")]
+#[tracing::instrument(skip(engine, if_match), name = "Restore remark revision", err(Debug))]
+pub async fn restore_revision(
+    engine: &State<Engine>,
+    id: &str,
+    number: i32,
+    if_match: IfMatch,
+) -> Result<Versioned<Remark>> {
+    let id = helpers::parse_id(id)?;
+    let expected_updated_at = helpers::parse_if_match(if_match)?;
+
+    let remark = remarks::restore_remark_revision(engine, id, number, expected_updated_at).await?;
+    let updated_at = remark.updated_at();
+
+    Ok(Versioned::new(remark, updated_at))
+}
+
+#[get("/<id>/revisions")]
+#[tracing::instrument(skip(engine), name = "Remark revisions index", err(Debug))]
+pub async fn revisions(engine: &State<Engine>, id: &str) -> Result<Json<Page<RemarkRevision>>> {
+    let id = helpers::parse_id(id)?;
+
+    let page = remarks::list_remark_revisions(engine, id).await?;
+
+    Ok(Json(page))
+}
+
+#[get("/search?<q>&<page_token>&<page_size>")]
+#[tracing::instrument(skip(engine), name = "Search remarks", err(Debug))]
+pub async fn search(
+    engine: &State<Engine>,
+    q: Option<String>,
+    page_token: Option<String>,
+    page_size: Option<i64>,
+) -> Result<Json<Page<RemarkSearchHit>>> {
+    let page = remarks::search_remarks(
+        engine,
+        RemarksSearchParameters {
+            query: q.unwrap_or_default(),
+            page_token: page_token.map(PageToken::from),
+            page_size: helpers::parse_page_size(page_size)?,
         },
     )
     .await?;
@@ -51,30 +422,118 @@ pub async fn index(
 
 #[get("/<id>")]
 #[tracing::instrument(skip(engine), name = "Show remark", err(Debug))]
-pub async fn show(engine: &State<Engine>, id: &str) -> Result<Json<Remark>> {
+pub async fn show(engine: &State<Engine>, id: &str) -> Result<Versioned<Remark>> {
     let id = helpers::parse_id(id)?;
 
     let remark = remarks::get_remark(engine, id).await?;
+    let updated_at = remark.updated_at();
 
-    Ok(Json(remark))
+    Ok(Versioned::new(remark, updated_at))
+}
+
+#[get("/trash?<page_token>&<page_size>&<order_by>")]
+#[tracing::instrument(skip(engine), name = "Trashed remarks index", err(Debug))]
+pub async fn trash(
+    engine: &State<Engine>,
+    page_token: Option<String>,
+    page_size: Option<i64>,
+    order_by: Option<String>,
+) -> Result<Json<Page<Remark>>> {
+    let page = remarks::list_remarks(
+        engine,
+        RemarksPageParameters {
+            page_token: page_token.map(PageToken::from),
+            page_size: helpers::parse_page_size(page_size)?,
+            order_by: helpers::parse_order_by(order_by)?,
+            trashed: true,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    Ok(Json(page))
+}
+
+#[post("/<id>/items/<number>/toggle")]
+#[tracing::instrument(skip(engine, if_match), name = "Toggle remark item", err(Debug))]
+pub async fn toggle_item(
+    engine: &State<Engine>,
+    id: &str,
+    number: usize,
+    if_match: IfMatch,
+) -> Result<Versioned<Remark>> {
+    let id = helpers::parse_id(id)?;
+    let expected_updated_at = helpers::parse_if_match(if_match)?;
+
+    let remark = remarks::toggle_remark_item(engine, id, number, expected_updated_at).await?;
+    let updated_at = remark.updated_at();
+
+    Ok(Versioned::new(remark, updated_at))
 }
 
 #[patch("/<id>", data = "<form>")]
-#[tracing::instrument(skip(engine), name = "Update remark", err(Debug))]
+#[tracing::instrument(skip(engine, if_match), name = "Update remark", err(Debug))]
 pub async fn update(
     engine: &State<Engine>,
     id: &str,
+    if_match: IfMatch,
     form: Option<Json<UpdateRemarkForm>>,
-) -> Result<Json<Remark>> {
+) -> Result<Versioned<Remark>> {
     let id = helpers::parse_id(id)?;
 
-    let changes = form
-        .map(|form| form.into_inner().into())
-        .unwrap_or_else(RemarkChanges::empty);
+    let changes = RemarkChanges {
+        expected_updated_at: helpers::parse_if_match(if_match)?,
+        ..form
+            .map(|form| form.into_inner().into())
+            .unwrap_or_else(RemarkChanges::empty)
+    };
 
     let remark = remarks::update_remark(engine, id, changes).await?;
+    let updated_at = remark.updated_at();
 
-    Ok(Json(remark))
+    Ok(Versioned::new(remark, updated_at))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct BulkRemarkForm {
+    essence: Option<String>,
+    tags: Option<Vec<String>>,
+    created_at: Option<DateTime<Utc>>,
+    content_hash: Option<String>,
+    kind: Option<String>,
+    payload: Option<RemarkPayloadForm>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct BulkRemarksForm {
+    remarks: Vec<BulkRemarkForm>,
+
+    #[serde(default)]
+    dry_run: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct ImportedRemarkForm {
+    id: Uuid,
+    essence: String,
+
+    #[serde(default)]
+    tags: Vec<String>,
+
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+
+    #[serde(default)]
+    deleted_at: Option<DateTime<Utc>>,
+
+    #[serde(default)]
+    kind: Option<String>,
+
+    #[serde(default)]
+    payload: Option<RemarkPayloadForm>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -82,6 +541,24 @@ pub async fn update(
 struct NewRemarkForm {
     essence: Option<String>,
     tags: Option<Vec<String>>,
+    kind: Option<String>,
+    payload: Option<RemarkPayloadForm>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct RemarkPayloadForm {
+    url: Option<String>,
+    source: Option<String>,
+    author: Option<String>,
+    done: Option<bool>,
+    due_date: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct RemarkTagsForm {
+    tags: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -89,23 +566,239 @@ struct NewRemarkForm {
 struct UpdateRemarkForm {
     essence: Option<String>,
     tags: Option<Vec<String>>,
+    kind: Option<String>,
+    payload: Option<RemarkPayloadForm>,
+}
+
+impl From<BulkRemarkForm> for BulkNewRemarkAttributes {
+    fn from(value: BulkRemarkForm) -> Self {
+        let BulkRemarkForm {
+            essence,
+            tags,
+            created_at,
+            content_hash,
+            kind,
+            payload,
+        } = value;
+
+        BulkNewRemarkAttributes {
+            essence: essence.unwrap_or_default(),
+            tags: tags.unwrap_or_default(),
+            created_at,
+            content_hash,
+            kind: RemarkPayloadForm::into_attributes(kind, payload),
+        }
+    }
+}
+
+impl From<ImportedRemarkForm> for ImportedRemarkAttributes {
+    fn from(value: ImportedRemarkForm) -> Self {
+        let ImportedRemarkForm {
+            id,
+            essence,
+            tags,
+            created_at,
+            updated_at,
+            deleted_at,
+            kind,
+            payload,
+        } = value;
+
+        ImportedRemarkAttributes {
+            id,
+            essence,
+            tags,
+            created_at,
+            updated_at,
+            deleted_at,
+            kind: RemarkPayloadForm::into_attributes(kind, payload),
+        }
+    }
 }
 
 impl From<NewRemarkForm> for NewRemarkAttributes {
     fn from(value: NewRemarkForm) -> Self {
-        let NewRemarkForm { essence, tags } = value;
+        let NewRemarkForm {
+            essence,
+            tags,
+            kind,
+            payload,
+        } = value;
 
         NewRemarkAttributes {
             essence: essence.unwrap_or_default(),
             tags: tags.unwrap_or_default(),
+            kind: RemarkPayloadForm::into_attributes(kind, payload),
         }
     }
 }
 
 impl From<UpdateRemarkForm> for RemarkChanges {
     fn from(value: UpdateRemarkForm) -> Self {
-        let UpdateRemarkForm { essence, tags } = value;
+        let UpdateRemarkForm {
+            essence,
+            tags,
+            kind,
+            payload,
+        } = value;
+
+        let kind = (kind.is_some() || payload.is_some())
+            .then(|| RemarkPayloadForm::into_attributes(kind, payload));
+
+        RemarkChanges {
+            essence,
+            tags,
+            kind,
+            expected_updated_at: None,
+        }
+    }
+}
+
+impl RemarkPayloadForm {
+    fn into_attributes(kind: Option<String>, payload: Option<Self>) -> RemarkKindAttributes {
+        let Some(RemarkPayloadForm {
+            url,
+            source,
+            author,
+            done,
+            due_date,
+        }) = payload
+        else {
+            return RemarkKindAttributes {
+                kind,
+                ..Default::default()
+            };
+        };
+
+        RemarkKindAttributes {
+            kind,
+            url,
+            source,
+            author,
+            done,
+            due_date,
+        }
+    }
+}
+
+impl<'r> Responder<'r, 'r> for RemarksExport<'r> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'r> {
+        let RemarksExport(engine) = self;
+
+        let mut response = export_lines(engine).respond_to(request)?;
+        response.set_header(ContentType::new("application", "x-ndjson"));
+
+        Ok(response)
+    }
+}
+
+impl AttachmentForm<'_> {
+    async fn into_attributes(self) -> Result<NewRemarkAttachmentAttributes> {
+        let AttachmentForm { file } = self;
+
+        let file_name = file
+            .raw_name()
+            .map(|name| name.dangerous_unsafe_unsanitized_raw().to_string())
+            .unwrap_or_default();
+
+        let media_type = match file.content_type() {
+            Some(content_type) if *content_type != ContentType::Binary => content_type.clone(),
+            _ => file_name
+                .rsplit_once('.')
+                .and_then(|(_, extension)| ContentType::from_extension(extension))
+                .unwrap_or(ContentType::Binary),
+        };
+
+        let mut content = Vec::new();
+
+        Box::pin(file.open().await.map_err(read_attachment_err)?)
+            .read_to_end(&mut content)
+            .await
+            .map_err(read_attachment_err)?;
+
+        Ok(NewRemarkAttachmentAttributes {
+            file_name,
+            media_type: media_type.to_string(),
+            content,
+        })
+    }
+}
+
+fn attachment_download(attachment: RemarkAttachment, content: Vec<u8>) -> AttachmentDownload {
+    let content_type =
+        ContentType::parse_flexible(attachment.media_type()).unwrap_or(ContentType::Binary);
+
+    let file_name: String = attachment
+        .file_name()
+        .chars()
+        .map(|c| match c {
+            '"' | '\\' => '_',
+            c if c.is_ascii() && !c.is_ascii_control() => c,
+            _ => '_',
+        })
+        .collect();
+
+    AttachmentDownload {
+        content: (content_type, content),
+        disposition: Header::new(
+            "Content-Disposition",
+            format!("attachment; filename=\"{}\"", file_name),
+        ),
+    }
+}
+
+fn read_attachment_err(err: std::io::Error) -> Error {
+    Error::internal(ApplicationError::internal(
+        "failed to read uploaded attachment",
+        err,
+    ))
+}
+
+// The client treats an export without the trailing end line as truncated
+fn export_lines(engine: &Engine) -> TextStream![String + '_] {
+    TextStream! {
+        let mut count = 0;
+
+        for trashed in [false, true] {
+            let mut page_token = None;
+
+            loop {
+                let parameters = RemarksPageParameters {
+                    page_token,
+                    page_size: PageSize::new(PageSize::MAX).ok(),
+                    order_by: RemarksOrderBy::CreatedAtAsc,
+                    trashed,
+                    ..Default::default()
+                };
+
+                let page = match remarks::list_remarks(engine, parameters).await {
+                    Ok(page) => page,
+                    Err(err) => {
+                        tracing::error!("Failed to export remarks: {:?}", err);
+                        return;
+                    }
+                };
+
+                for remark in page.items {
+                    match rocket::serde::json::to_string(&remark) {
+                        Ok(line) => {
+                            count += 1;
+                            yield format!("{}\n", line);
+                        }
+                        Err(err) => {
+                            tracing::error!("Failed to serialize remark: {:?}", err);
+                            return;
+                        }
+                    }
+                }
+
+                match page.next_page_token {
+                    Some(next_page_token) => page_token = Some(next_page_token),
+                    None => break,
+                }
+            }
+        }
 
-        RemarkChanges { essence, tags }
+        yield format!("{{\"end\":true,\"count\":{}}}\n", count);
     }
 }
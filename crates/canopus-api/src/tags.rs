@@ -1,19 +1,41 @@
-use crate::{Result, helpers};
+use crate::{
+    Result,
+    helpers::{self, IfMatch, Versioned},
+};
 use canopus_definitions::{Page, PageToken, Tag};
 use canopus_engine::{Engine, tags};
-use canopus_operations::tags::TagsPageParameters;
+use canopus_operations::tags::{TagChanges, TagsPageParameters};
 use rocket::{
     State,
     serde::{Deserialize, json::Json},
 };
 
-#[get("/?<page_token>")]
+#[get("/<id>/children")]
+#[tracing::instrument(skip(engine), name = "Tag children index", err(Debug))]
+pub async fn children(engine: &State<Engine>, id: &str) -> Result<Json<Page<Tag>>> {
+    let id = helpers::parse_id(id)?;
+
+    let page = tags::list_tag_children(engine, id).await?;
+
+    Ok(Json(page))
+}
+
+#[get("/?<page_token>&<page_size>&<order_by>&<with_usage_count>")]
 #[tracing::instrument(skip(engine), name = "Tags index", err(Debug))]
-pub async fn index(engine: &State<Engine>, page_token: Option<String>) -> Result<Json<Page<Tag>>> {
+pub async fn index(
+    engine: &State<Engine>,
+    page_token: Option<String>,
+    page_size: Option<i64>,
+    order_by: Option<String>,
+    with_usage_count: Option<bool>,
+) -> Result<Json<Page<Tag>>> {
     let page = tags::list_tags(
         engine,
         TagsPageParameters {
             page_token: page_token.map(PageToken::from),
+            page_size: helpers::parse_page_size(page_size)?,
+            order_by: helpers::parse_order_by(order_by)?,
+            with_usage_count: with_usage_count.unwrap_or_default(),
         },
     )
     .await?;
@@ -21,23 +43,64 @@ pub async fn index(engine: &State<Engine>, page_token: Option<String>) -> Result
     Ok(Json(page))
 }
 
+#[post("/<id>/merge", data = "<form>")]
+#[tracing::instrument(skip(engine), name = "Merge tags", err(Debug))]
+pub async fn merge(
+    engine: &State<Engine>,
+    id: &str,
+    form: Option<Json<MergeTagsForm>>,
+) -> Result<Json<Tag>> {
+    let id = helpers::parse_id(id)?;
+
+    let target_id = form
+        .map(|form| form.into_inner())
+        .unwrap_or_default()
+        .target_id
+        .unwrap_or_default();
+
+    let target_id = helpers::parse_id(&target_id)?;
+
+    let tag = tags::merge_tags(engine, id, target_id).await?;
+
+    Ok(Json(tag))
+}
+
 #[get("/<id>")]
 #[tracing::instrument(skip(engine), name = "Tags show", err(Debug))]
-pub async fn show(engine: &State<Engine>, id: &str) -> Result<Json<Tag>> {
+pub async fn show(engine: &State<Engine>, id: &str) -> Result<Versioned<Tag>> {
     let id = helpers::parse_id(id)?;
 
     let tag = tags::get_tag(engine, id).await?;
+    let updated_at = tag.updated_at();
 
-    Ok(Json(tag))
+    Ok(Versioned::new(tag, updated_at))
+}
+
+#[get("/suggest?<prefix>&<page_size>")]
+#[tracing::instrument(skip(engine), name = "Suggest tags", err(Debug))]
+pub async fn suggest(
+    engine: &State<Engine>,
+    prefix: Option<String>,
+    page_size: Option<i64>,
+) -> Result<Json<Page<Tag>>> {
+    let page = tags::suggest_tags(
+        engine,
+        prefix.unwrap_or_default(),
+        helpers::parse_page_size(page_size)?,
+    )
+    .await?;
+
+    Ok(Json(page))
 }
 
 #[patch("/<id>", data = "<form>")]
-#[tracing::instrument(skip(engine), name = "Update tag", err(Debug))]
+#[tracing::instrument(skip(engine, if_match), name = "Update tag", err(Debug))]
 pub async fn update(
     engine: &State<Engine>,
     id: &str,
+    if_match: IfMatch,
     form: Option<Json<UpdateTagForm>>,
-) -> Result<Json<Tag>> {
+) -> Result<Versioned<Tag>> {
     let id = helpers::parse_id(id)?;
 
     let title = form
@@ -46,9 +109,21 @@ pub async fn update(
         .title
         .unwrap_or_default();
 
-    let tag = tags::update_tag(engine, id, title).await?;
+    let changes = TagChanges {
+        title,
+        expected_updated_at: helpers::parse_if_match(if_match)?,
+    };
 
-    Ok(Json(tag))
+    let tag = tags::update_tag(engine, id, changes).await?;
+    let updated_at = tag.updated_at();
+
+    Ok(Versioned::new(tag, updated_at))
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct MergeTagsForm {
+    target_id: Option<String>,
 }
 
 #[derive(Default, Debug, Deserialize)]
@@ -0,0 +1,329 @@
+use canopus_definitions::{ApplicationResult, Remark, RemarkKind, TagTitle};
+use chrono::{DateTime, Utc};
+use quick_xml::escape::{resolve_html5_entity, unescape_with};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+const MICROSECONDS_THRESHOLD: i64 = 100_000_000_000;
+const SUPPORTED_SCHEMES: [&str; 2] = ["http://", "https://"];
+
+pub struct Bookmark {
+    pub title: String,
+    pub url: String,
+    pub folders: Vec<String>,
+    pub added_at: Option<DateTime<Utc>>,
+}
+
+pub enum BookmarkImportStatus {
+    Created(Uuid),
+    WouldCreate,
+    AlreadyExists,
+    Unsupported,
+    Failed(String),
+}
+
+pub struct BookmarkImportReport {
+    dry_run: bool,
+    entries: Vec<(String, BookmarkImportStatus)>,
+}
+
+enum Capture {
+    Folder(String),
+    Link {
+        url: String,
+        added_at: Option<String>,
+        title: String,
+    },
+}
+
+pub fn parse(html: &str) -> Vec<Bookmark> {
+    let mut bookmarks = Vec::new();
+    let mut folders: Vec<Option<String>> = Vec::new();
+    let mut pending_folder: Option<String> = None;
+    let mut capture: Option<Capture> = None;
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        if let Some(Capture::Folder(text) | Capture::Link { title: text, .. }) = capture.as_mut() {
+            text.push_str(&rest[..start]);
+        }
+
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let name = tag
+            .split(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match name.as_str() {
+            "h3" => capture = Some(Capture::Folder(String::new())),
+            "/h3" => {
+                if let Some(Capture::Folder(title)) = capture.take() {
+                    pending_folder = Some(clean_text(&title));
+                }
+            }
+            "a" => {
+                capture = attribute(tag, "href").map(|url| Capture::Link {
+                    url,
+                    added_at: attribute(tag, "add_date"),
+                    title: String::new(),
+                });
+            }
+            "/a" => {
+                if let Some(Capture::Link {
+                    url,
+                    added_at,
+                    title,
+                }) = capture.take()
+                {
+                    bookmarks.push(Bookmark {
+                        title: clean_text(&title),
+                        url: url.trim().to_string(),
+                        folders: folders.iter().flatten().cloned().collect(),
+                        added_at: added_at.as_deref().and_then(parse_timestamp),
+                    });
+                }
+            }
+            "dl" => folders.push(pending_folder.take()),
+            "/dl" => {
+                folders.pop();
+            }
+            _ => {}
+        }
+    }
+
+    bookmarks
+}
+
+pub fn remark_urls(remarks: &[Remark]) -> HashSet<String> {
+    let mut urls = HashSet::new();
+
+    for remark in remarks {
+        if let RemarkKind::Link { url, .. } = remark.kind() {
+            urls.insert(normalize_url(url));
+        }
+
+        for word in remark
+            .essence()
+            .as_str()
+            .split(|c: char| c.is_whitespace() || matches!(c, '(' | ')' | '<' | '>' | '"' | '['))
+        {
+            if let Some(start) = SUPPORTED_SCHEMES
+                .iter()
+                .filter_map(|scheme| word.find(scheme))
+                .min()
+            {
+                urls.insert(normalize_url(&word[start..]));
+            }
+        }
+    }
+
+    urls
+}
+
+pub fn normalize_url(url: &str) -> String {
+    url.trim()
+        .trim_end_matches(['.', ',', ';', ']'])
+        .trim_end_matches('/')
+        .to_string()
+}
+
+impl Bookmark {
+    pub fn content_hash(&self) -> String {
+        format!("{:x}", Sha256::digest(normalize_url(&self.url)))
+    }
+
+    pub fn essence(&self) -> String {
+        if self.title.is_empty() || self.title == self.url {
+            self.url.clone()
+        } else {
+            format!("{}\n\n{}", self.title, self.url)
+        }
+    }
+
+    pub fn is_supported(&self) -> bool {
+        SUPPORTED_SCHEMES
+            .iter()
+            .any(|scheme| self.url.to_ascii_lowercase().starts_with(scheme))
+    }
+
+    pub fn label(&self) -> &str {
+        if self.title.is_empty() {
+            &self.url
+        } else {
+            &self.title
+        }
+    }
+
+    pub fn tag(&self) -> ApplicationResult<Option<TagTitle>> {
+        if self.folders.is_empty() {
+            return Ok(None);
+        }
+
+        let path = self
+            .folders
+            .iter()
+            .map(|folder| folder.replace('/', "-"))
+            .collect::<Vec<String>>()
+            .join("/");
+
+        TagTitle::new(path).map(Some)
+    }
+}
+
+impl BookmarkImportReport {
+    pub fn new(dry_run: bool) -> Self {
+        Self {
+            dry_run,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, label: String, status: BookmarkImportStatus) {
+        self.entries.push((label, status));
+    }
+}
+
+impl std::fmt::Display for BookmarkImportReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut created = 0;
+        let mut skipped = 0;
+        let mut failed = 0;
+
+        for (label, status) in &self.entries {
+            match status {
+                BookmarkImportStatus::Created(id) => {
+                    created += 1;
+                    writeln!(f, "created {} ({})", label, id)?;
+                }
+                BookmarkImportStatus::WouldCreate => {
+                    created += 1;
+                    writeln!(f, "would create {}", label)?;
+                }
+                BookmarkImportStatus::AlreadyExists => {
+                    skipped += 1;
+                    writeln!(f, "skipped {} (already in remarks)", label)?;
+                }
+                BookmarkImportStatus::Unsupported => {
+                    skipped += 1;
+                    writeln!(f, "skipped {} (unsupported URL)", label)?;
+                }
+                BookmarkImportStatus::Failed(message) => {
+                    failed += 1;
+                    writeln!(f, "failed {}: {}", label, message)?;
+                }
+            }
+        }
+
+        let created_label = if self.dry_run {
+            "would be created"
+        } else {
+            "created"
+        };
+
+        write!(
+            f,
+            "{} {}, {} skipped, {} failed",
+            created, created_label, skipped, failed
+        )
+    }
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let lowercase = tag.to_ascii_lowercase();
+    let pattern = format!("{}=", name);
+
+    let start = lowercase
+        .match_indices(&pattern)
+        .map(|(index, _)| index)
+        .find(|index| tag[..*index].ends_with(|c: char| c.is_whitespace()))?
+        + pattern.len();
+
+    let value = &tag[start..];
+
+    let value = match value.chars().next() {
+        Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+        _ => value
+            .split(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or_default(),
+    };
+
+    Some(unescape(value))
+}
+
+fn clean_text(text: &str) -> String {
+    unescape(text)
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    let value: i64 = value.trim().parse().ok()?;
+
+    if value >= MICROSECONDS_THRESHOLD {
+        DateTime::from_timestamp_micros(value)
+    } else {
+        DateTime::from_timestamp(value, 0)
+    }
+}
+
+fn unescape(value: &str) -> String {
+    unescape_with(value, resolve_html5_entity)
+        .map(|value| value.to_string())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let html = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<META HTTP-EQUIV="Content-Type" CONTENT="text/html; charset=UTF-8">
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks</H1>
+<DL><p>
+    <DT><H3 ADD_DATE="1700000000">Bookmarks bar</H3>
+    <DL><p>
+        <DT><H3>Rust / Lang</H3>
+        <DL><p>
+            <DT><A HREF="https://doc.rust-lang.org/book/" ADD_DATE="1700000100" ICON="data:image/png;base64,AA==">The Rust &amp; Book</A>
+        </DL><p>
+        <DT><A HREF="javascript:void(0)">Bookmarklet</A>
+    </DL><p>
+    <DT><A href='https://example.com'></A>
+</DL><p>"#;
+
+        let bookmarks = parse(html);
+
+        assert_eq!(bookmarks.len(), 3);
+        assert_eq!(bookmarks[0].title, "The Rust & Book");
+        assert_eq!(bookmarks[0].url, "https://doc.rust-lang.org/book/");
+        assert_eq!(
+            bookmarks[0].tag().unwrap().unwrap().as_str(),
+            "bookmarks bar/rust - lang"
+        );
+        assert_eq!(
+            bookmarks[0].added_at,
+            DateTime::from_timestamp(1_700_000_100, 0)
+        );
+        assert_eq!(
+            bookmarks[0].essence(),
+            "The Rust & Book\n\nhttps://doc.rust-lang.org/book/"
+        );
+        assert!(!bookmarks[1].is_supported());
+        assert_eq!(bookmarks[1].folders, ["Bookmarks bar"]);
+        assert_eq!(bookmarks[2].essence(), "https://example.com");
+        assert!(bookmarks[2].tag().unwrap().is_none());
+    }
+}
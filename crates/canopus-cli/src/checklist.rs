@@ -0,0 +1,58 @@
+use canopus_definitions::Remark;
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct OpenItemsReport {
+    remarks: Vec<OpenItemsRemark>,
+}
+
+struct OpenItemsRemark {
+    id: Uuid,
+    title: String,
+    items: Vec<(usize, String)>,
+}
+
+impl OpenItemsReport {
+    pub fn push(&mut self, remarks: &[Remark]) {
+        let remarks = remarks.iter().filter_map(|remark| {
+            let items: Vec<(usize, String)> = remark
+                .items()
+                .iter()
+                .filter(|item| !item.is_checked())
+                .map(|item| (item.number(), item.text().to_string()))
+                .collect();
+
+            if items.is_empty() {
+                return None;
+            }
+
+            let title = remark.essence().lines().next().unwrap_or_default().trim();
+
+            Some(OpenItemsRemark {
+                id: remark.id(),
+                title: title.to_string(),
+                items,
+            })
+        });
+
+        self.remarks.extend(remarks);
+    }
+}
+
+impl std::fmt::Display for OpenItemsReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut count = 0;
+
+        for OpenItemsRemark { id, title, items } in &self.remarks {
+            writeln!(f, "{} {}", id, title)?;
+
+            for (number, text) in items {
+                writeln!(f, "  {}. [ ] {}", number, text)?;
+            }
+
+            count += items.len();
+        }
+
+        write!(f, "{} open items", count)
+    }
+}
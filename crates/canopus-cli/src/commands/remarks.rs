@@ -1,18 +1,80 @@
-use crate::{CliApp, editor};
-use canopus_client::remarks::{self, NewRemark, RemarkUpdates};
-use canopus_definitions::ApplicationResult;
-use clap::Subcommand;
+use crate::{
+    CliApp,
+    bookmarks::{self, BookmarkImportReport, BookmarkImportStatus},
+    checklist::OpenItemsReport,
+    completion::complete_tags,
+    diff, editor,
+    enex::{EnexImportReport, EnexImportStatus, EnexNote, EnexReader, EnexResource},
+    markdown::{self, MarkdownExport, MarkdownImportReport, MarkdownImportStatus},
+    remarks_csv::{self, CsvImportReport, RemarksCsvWriter},
+};
+use canopus_client::{
+    Client,
+    remarks::{
+        self, BulkNewRemark, NewAttachment, NewRemark, RemarkPayload, RemarkUpdates,
+        RemarksPageParameters,
+    },
+};
+use canopus_definitions::{ApplicationError, ApplicationResult, PageSize, Remark};
+use chrono::{Local, Utc};
+use clap::{Args, Subcommand};
+use clap_complete::ArgValueCompleter;
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
 use uuid::Uuid;
 
+const BULK_MAX_BYTES: usize = 512 * 1024;
+const BULK_MAX_REMARKS: usize = 100;
+
+struct RemarkPages<'a> {
+    client: &'a Client,
+    page_token: Option<String>,
+    done: bool,
+}
+
+#[derive(Args)]
+pub struct RemarkKindArgs {
+    #[arg(long, value_parser = ["note", "link", "quote", "task"])]
+    kind: Option<String>,
+
+    #[arg(long)]
+    url: Option<String>,
+
+    #[arg(long)]
+    source: Option<String>,
+
+    #[arg(long)]
+    author: Option<String>,
+
+    #[arg(long)]
+    done: Option<bool>,
+
+    #[arg(long)]
+    due_date: Option<String>,
+}
+
 #[derive(Subcommand)]
 pub enum RemarksCommands {
     AddRemarkTags {
         id: Uuid,
 
-        #[arg(required = true, value_delimiter = ',')]
+        #[arg(required = true, value_delimiter = ',', add = ArgValueCompleter::new(complete_tags))]
         tags: Vec<String>,
     },
 
+    Attach {
+        id: Uuid,
+
+        path: PathBuf,
+
+        #[arg(short, long)]
+        media_type: Option<String>,
+    },
+
     ClearRemarkTags {
         id: Uuid,
     },
@@ -21,30 +83,183 @@ pub enum RemarksCommands {
         #[arg(short, long)]
         essence: String,
 
-        #[arg(short, long)]
+        #[arg(short, long, add = ArgValueCompleter::new(complete_tags))]
         tags: Vec<String>,
+
+        #[command(flatten)]
+        kind: RemarkKindArgs,
     },
 
     DeleteRemark {
         id: Uuid,
+
+        #[arg(long)]
+        permanent: bool,
     },
 
     DeleteRemarkTags {
         id: Uuid,
 
-        #[arg(required = true, value_delimiter = ',')]
+        #[arg(required = true, value_delimiter = ',', add = ArgValueCompleter::new(complete_tags))]
         tags: Vec<String>,
     },
 
+    DeleteRemarkAttachment {
+        id: Uuid,
+
+        attachment_id: Uuid,
+    },
+
+    DiffRemarkRevisions {
+        id: Uuid,
+
+        from: i32,
+
+        to: i32,
+    },
+
+    Download {
+        id: Uuid,
+
+        attachment_id: Uuid,
+
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
     EditRemark {
         id: Uuid,
     },
 
+    Export {
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    ExportCsv {
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        #[arg(short, long)]
+        page_token: Option<String>,
+
+        #[arg(long)]
+        page_size: Option<i64>,
+    },
+
+    ExportMarkdown {
+        dir: PathBuf,
+    },
+
+    Import {
+        input: PathBuf,
+    },
+
+    ImportBookmarks {
+        input: PathBuf,
+
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    ImportCsv {
+        input: PathBuf,
+    },
+
+    ImportEnex {
+        input: PathBuf,
+    },
+
+    ImportMarkdown {
+        dir: PathBuf,
+
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     NewRemark,
 
+    ListTrash {
+        #[arg(short, long)]
+        page_token: Option<String>,
+
+        #[arg(long)]
+        page_size: Option<i64>,
+    },
+
+    ListOpenItems,
+
+    ListRemarkAttachments {
+        id: Uuid,
+    },
+
+    ListRemarkBacklinks {
+        id: Uuid,
+    },
+
+    ListRemarkLinks {
+        id: Uuid,
+    },
+
+    ListRemarkRevisions {
+        id: Uuid,
+    },
+
     ListRemarks {
         #[arg(short, long)]
         page_token: Option<String>,
+
+        #[arg(long)]
+        page_size: Option<i64>,
+
+        #[arg(long, value_parser = ["created_at_asc", "created_at_desc", "updated_at_asc", "updated_at_desc"])]
+        order_by: Option<String>,
+
+        #[arg(long, value_delimiter = ',', add = ArgValueCompleter::new(complete_tags))]
+        all_tags: Vec<String>,
+
+        #[arg(long, value_delimiter = ',', add = ArgValueCompleter::new(complete_tags))]
+        any_tags: Vec<String>,
+
+        #[arg(long, value_delimiter = ',', add = ArgValueCompleter::new(complete_tags))]
+        exclude_tags: Vec<String>,
+
+        #[arg(long, allow_hyphen_values = true)]
+        created_after: Option<String>,
+
+        #[arg(long, allow_hyphen_values = true)]
+        created_before: Option<String>,
+
+        #[arg(long, allow_hyphen_values = true)]
+        updated_after: Option<String>,
+
+        #[arg(long, allow_hyphen_values = true)]
+        updated_before: Option<String>,
+
+        #[arg(long, value_parser = ["note", "link", "quote", "task"])]
+        kind: Option<String>,
+    },
+
+    PurgeTrash,
+
+    RestoreRemark {
+        id: Uuid,
+    },
+
+    RestoreRemarkRevision {
+        id: Uuid,
+
+        number: i32,
+    },
+
+    SearchRemarks {
+        query: String,
+
+        #[arg(short, long)]
+        page_token: Option<String>,
+
+        #[arg(long)]
+        page_size: Option<i64>,
     },
 
     ShowRemark {
@@ -53,14 +268,23 @@ pub enum RemarksCommands {
 
     ShowLastRemark,
 
+    ToggleRemarkItem {
+        id: Uuid,
+
+        number: usize,
+    },
+
     UpdateRemark {
         id: Uuid,
 
         #[arg(short, long)]
         essence: Option<String>,
 
-        #[arg(short, long)]
+        #[arg(short, long, add = ArgValueCompleter::new(complete_tags))]
         tags: Option<Vec<String>>,
+
+        #[command(flatten)]
+        kind: RemarkKindArgs,
     },
 }
 
@@ -70,66 +294,128 @@ impl RemarksCommands {
 
         match self {
             Self::AddRemarkTags { id, tags } => {
-                let remark = remarks::show(client, id).await?;
+                let remark = remarks::add_tags(client, id, tags, None).await?;
 
-                let current_tags = remark.tags().iter().map(ToString::to_string).collect();
+                renderer.render(remark);
+            }
+            Self::ClearRemarkTags { id } => {
+                let remark = remarks::show(client, id).await?;
 
                 let remark = remarks::update(
                     client,
                     id,
                     RemarkUpdates {
-                        essence: None,
-                        tags: Some([current_tags, tags].concat()),
+                        tags: Some(vec![]),
+                        ..Default::default()
                     },
+                    Some(remark.updated_at()),
                 )
                 .await?;
 
                 renderer.render(remark);
             }
-            Self::ClearRemarkTags { id } => {
-                let remark = remarks::update(
+            Self::CreateRemark {
+                essence,
+                tags,
+                kind,
+            } => {
+                let (kind, payload) = kind.into_parts();
+
+                let remark = remarks::create(
                     client,
-                    id,
-                    RemarkUpdates {
-                        essence: None,
-                        tags: Some(vec![]),
+                    NewRemark {
+                        essence,
+                        tags,
+                        kind,
+                        payload,
                     },
                 )
                 .await?;
 
                 renderer.render(remark);
             }
-            Self::CreateRemark { essence, tags } => {
-                let remark = remarks::create(client, NewRemark { essence, tags }).await?;
+            Self::DeleteRemark { id, permanent } => {
+                let remark = if permanent {
+                    remarks::purge(client, id, None).await?
+                } else {
+                    remarks::delete(client, id, None).await?
+                };
 
                 renderer.render(remark);
             }
-            Self::DeleteRemark { id } => {
-                let remark = remarks::delete(client, id).await?;
+            Self::DeleteRemarkTags { id, tags } => {
+                let remark = remarks::remove_tags(client, id, &tags, None).await?;
 
                 renderer.render(remark);
             }
-            Self::DeleteRemarkTags {
+            Self::Attach {
                 id,
-                tags: tags_to_remove,
+                path,
+                media_type,
             } => {
-                let remark = remarks::show(client, id).await?;
-
-                let mut tags: Vec<String> = remark.tags().iter().map(ToString::to_string).collect();
+                let file_name = path
+                    .file_name()
+                    .map(|file_name| file_name.to_string_lossy().to_string())
+                    .ok_or_else(|| ApplicationError::invalid_argument("path must name a file"))?;
 
-                tags.retain(|tag| !tags_to_remove.iter().any(|t| t == tag));
+                let content = std::fs::read(&path).map_err(|err| {
+                    ApplicationError::internal("failed to read attachment file", err)
+                })?;
 
-                let remark = remarks::update(
+                let attachment = remarks::attach(
                     client,
                     id,
-                    RemarkUpdates {
-                        essence: None,
-                        tags: Some(tags),
+                    NewAttachment {
+                        file_name,
+                        media_type,
+                        content,
                     },
                 )
                 .await?;
 
-                renderer.render(remark);
+                renderer.render(attachment);
+            }
+            Self::DeleteRemarkAttachment { id, attachment_id } => {
+                let attachment = remarks::delete_attachment(client, id, attachment_id).await?;
+
+                renderer.render(attachment);
+            }
+            Self::DiffRemarkRevisions { id, from, to } => {
+                let page = remarks::revisions(client, id).await?;
+
+                let find_revision = |number: i32| {
+                    page.items
+                        .iter()
+                        .find(|revision| revision.number() == number)
+                        .ok_or(ApplicationError::NotFound)
+                };
+
+                let diff = diff::unified(find_revision(from)?, find_revision(to)?);
+
+                renderer.render(diff);
+            }
+            Self::Download {
+                id,
+                attachment_id,
+                output,
+            } => {
+                let page = remarks::attachments(client, id).await?;
+
+                let attachment = page
+                    .items
+                    .into_iter()
+                    .find(|attachment| attachment.id() == attachment_id)
+                    .ok_or(ApplicationError::NotFound)?;
+
+                let content = remarks::download(client, id, attachment_id).await?;
+
+                let output = output.unwrap_or_else(|| PathBuf::from(attachment.file_name()));
+
+                std::fs::write(&output, content).map_err(|err| {
+                    ApplicationError::internal("failed to write attachment file", err)
+                })?;
+
+                renderer.render(attachment);
             }
             Self::EditRemark { id } => {
                 let remark = remarks::show(client, id).await?;
@@ -142,11 +428,135 @@ impl RemarksCommands {
                         essence: Some(essence),
                         ..Default::default()
                     },
+                    Some(remark.updated_at()),
                 )
                 .await?;
 
                 renderer.render(remark);
             }
+            Self::Export { output } => {
+                let mut output: Box<dyn Write> = match output {
+                    Some(path) => Box::new(BufWriter::new(File::create(path).map_err(|err| {
+                        ApplicationError::internal("failed to create export file", err)
+                    })?)),
+                    None => Box::new(BufWriter::new(std::io::stdout().lock())),
+                };
+
+                remarks::export(client, &mut output).await?;
+            }
+            Self::ExportCsv {
+                output,
+                page_token,
+                page_size,
+            } => {
+                let output: Box<dyn Write> = match output {
+                    Some(path) => Box::new(BufWriter::new(File::create(path).map_err(|err| {
+                        ApplicationError::internal("failed to create export file", err)
+                    })?)),
+                    None => Box::new(BufWriter::new(std::io::stdout().lock())),
+                };
+
+                let mut writer = RemarksCsvWriter::new(output)?;
+
+                if page_token.is_some() || page_size.is_some() {
+                    let page = remarks::index(
+                        client,
+                        RemarksPageParameters {
+                            page_token,
+                            page_size,
+                            order_by: Some("created_at_asc".to_string()),
+                            ..Default::default()
+                        },
+                    )
+                    .await?;
+
+                    writer.write(&page.items)?;
+                    writer.finish()?;
+
+                    // Keeps stdout plain CSV so a single page can be piped to ConvertFrom-Csv
+                    if let Some(token) = page.next_page_token {
+                        eprintln!("{}", token.as_str());
+                    }
+                } else {
+                    let mut pages = RemarkPages::new(client);
+
+                    while let Some(remarks) = pages.next().await? {
+                        writer.write(&remarks)?;
+                    }
+
+                    writer.finish()?;
+                }
+            }
+            Self::ExportMarkdown { dir } => {
+                let mut export = MarkdownExport::open(&dir)?;
+                let mut pages = RemarkPages::new(client);
+
+                while let Some(remarks) = pages.next().await? {
+                    export.write(&remarks)?;
+                }
+
+                renderer.render(export.finish()?);
+            }
+            Self::Import { input } => {
+                let input = tokio::fs::File::open(input)
+                    .await
+                    .map_err(|err| ApplicationError::internal("failed to open import file", err))?;
+
+                let summary = remarks::import(client, input).await?;
+
+                renderer.render(summary);
+            }
+            Self::ImportBookmarks { input, dry_run } => {
+                let html = std::fs::read_to_string(input).map_err(|err| {
+                    ApplicationError::internal("failed to read bookmarks file", err)
+                })?;
+
+                let report = import_bookmarks(client, &html, dry_run).await?;
+
+                renderer.render(report);
+            }
+            Self::ImportCsv { input } => {
+                let input = File::open(input)
+                    .map_err(|err| ApplicationError::internal("failed to open import file", err))?;
+
+                let (remarks, errors) = remarks_csv::read(input, Utc::now(), &Local)?;
+
+                let (lines, remarks): (Vec<u64>, Vec<Remark>) = remarks
+                    .into_iter()
+                    .map(|row| (row.line, row.remark))
+                    .unzip();
+
+                let summary = if remarks.is_empty() {
+                    None
+                } else {
+                    Some(remarks::import_remarks(client, &remarks).await?)
+                };
+
+                renderer.render(CsvImportReport::new(summary, &lines, errors));
+            }
+            Self::ImportEnex { input } => {
+                let input = File::open(input)
+                    .map_err(|err| ApplicationError::internal("failed to open import file", err))?;
+
+                let mut reader = EnexReader::new(BufReader::new(input));
+                let mut report = EnexImportReport::default();
+
+                while let Some((title, note)) = reader.next_note()? {
+                    let status = match note {
+                        Ok(note) => import_enex_note(client, note).await,
+                        Err(err) => EnexImportStatus::Failed(err.to_string()),
+                    };
+
+                    report.push(title, status);
+                }
+
+                renderer.render(report);
+            }
+            Self::ImportMarkdown { dir, dry_run } => {
+                let report = import_markdown(client, &dir, dry_run).await?;
+
+                renderer.render(report);
+            }
             Self::NewRemark => {
                 let essence = editor::open()?;
 
@@ -155,31 +565,157 @@ impl RemarksCommands {
                     NewRemark {
                         essence,
                         tags: vec![],
+                        kind: None,
+                        payload: None,
                     },
                 )
                 .await?;
 
                 renderer.render(remark);
             }
+            Self::ListTrash {
+                page_token,
+                page_size,
+            } => {
+                let page = remarks::trash(client, page_token, page_size).await?;
+
+                renderer.render(page);
+            }
+            Self::ListOpenItems => {
+                let mut report = OpenItemsReport::default();
+                let mut pages = RemarkPages::new(client);
+
+                while let Some(remarks) = pages.next().await? {
+                    report.push(&remarks);
+                }
+
+                renderer.render(report);
+            }
+            Self::ListRemarkAttachments { id } => {
+                let page = remarks::attachments(client, id).await?;
+
+                renderer.render(page);
+            }
+            Self::ListRemarkBacklinks { id } => {
+                let page = remarks::backlinks(client, id).await?;
+
+                renderer.render(page);
+            }
+            Self::ListRemarkLinks { id } => {
+                let page = remarks::links(client, id).await?;
+
+                renderer.render(page);
+            }
+            Self::ListRemarkRevisions { id } => {
+                let page = remarks::revisions(client, id).await?;
+
+                renderer.render(page);
+            }
+            Self::PurgeTrash => loop {
+                let page = remarks::trash(client, None, Some(PageSize::MAX)).await?;
+
+                if page.items.is_empty() {
+                    break;
+                }
+
+                for remark in page.items {
+                    let remark =
+                        remarks::purge(client, remark.id(), Some(remark.updated_at())).await?;
+
+                    renderer.render(remark);
+                }
+            },
+            Self::RestoreRemark { id } => {
+                let remark = remarks::restore(client, id, None).await?;
+
+                renderer.render(remark);
+            }
+            Self::RestoreRemarkRevision { id, number } => {
+                let remark = remarks::restore_revision(client, id, number, None).await?;
+
+                renderer.render(remark);
+            }
+            Self::SearchRemarks {
+                query,
+                page_token,
+                page_size,
+            } => {
+                let page = remarks::search(client, &query, page_token, page_size).await?;
+
+                renderer.render(page);
+            }
             Self::ShowRemark { id } => {
                 let remark = remarks::show(client, id).await?;
 
                 renderer.render(remark);
             }
             Self::ShowLastRemark => {
-                let page = remarks::index(client, None).await?;
+                let page = remarks::index(client, Default::default()).await?;
 
                 if let Some(remark) = page.items.first() {
                     renderer.render(remark);
                 }
             }
-            Self::ListRemarks { page_token } => {
-                let page = remarks::index(client, page_token).await?;
+            Self::ListRemarks {
+                page_token,
+                page_size,
+                order_by,
+                all_tags,
+                any_tags,
+                exclude_tags,
+                created_after,
+                created_before,
+                updated_after,
+                updated_before,
+                kind,
+            } => {
+                let page = remarks::index(
+                    client,
+                    RemarksPageParameters {
+                        page_token,
+                        page_size,
+                        order_by,
+                        all_tags,
+                        any_tags,
+                        exclude_tags,
+                        created_after: local_datetime(created_after)?,
+                        created_before: local_datetime(created_before)?,
+                        updated_after: local_datetime(updated_after)?,
+                        updated_before: local_datetime(updated_before)?,
+                        kind,
+                        tz: None,
+                    },
+                )
+                .await?;
 
                 renderer.render(page);
             }
-            Self::UpdateRemark { id, essence, tags } => {
-                let remark = remarks::update(client, id, RemarkUpdates { essence, tags }).await?;
+            Self::ToggleRemarkItem { id, number } => {
+                let remark = remarks::toggle_item(client, id, number, None).await?;
+
+                renderer.render(remark);
+            }
+            Self::UpdateRemark {
+                id,
+                essence,
+                tags,
+                kind,
+            } => {
+                let (kind, payload) = kind.into_parts();
+                let remark = remarks::show(client, id).await?;
+
+                let remark = remarks::update(
+                    client,
+                    id,
+                    RemarkUpdates {
+                        essence,
+                        tags,
+                        kind,
+                        payload,
+                    },
+                    Some(remark.updated_at()),
+                )
+                .await?;
 
                 renderer.render(remark);
             }
@@ -188,3 +724,285 @@ impl RemarksCommands {
         Ok(())
     }
 }
+
+impl RemarkKindArgs {
+    fn into_parts(self) -> (Option<String>, Option<RemarkPayload>) {
+        let RemarkKindArgs {
+            kind,
+            url,
+            source,
+            author,
+            done,
+            due_date,
+        } = self;
+
+        let payload = RemarkPayload {
+            url,
+            source,
+            author,
+            done,
+            due_date,
+        };
+
+        let has_payload = payload.url.is_some()
+            || payload.source.is_some()
+            || payload.author.is_some()
+            || payload.done.is_some()
+            || payload.due_date.is_some();
+
+        (kind, has_payload.then_some(payload))
+    }
+}
+
+impl<'a> RemarkPages<'a> {
+    fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            page_token: None,
+            done: false,
+        }
+    }
+
+    async fn next(&mut self) -> ApplicationResult<Option<Vec<Remark>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let page = remarks::index(
+            self.client,
+            RemarksPageParameters {
+                page_token: self.page_token.take(),
+                page_size: Some(PageSize::MAX),
+                order_by: Some("created_at_asc".to_string()),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        match page.next_page_token {
+            Some(token) => self.page_token = Some(token.as_str().to_string()),
+            None => self.done = true,
+        }
+
+        Ok(Some(page.items))
+    }
+}
+
+fn local_datetime(input: Option<String>) -> ApplicationResult<Option<String>> {
+    input
+        .map(|input| canopus_definitions::parse_datetime(&input, Utc::now(), &Local))
+        .transpose()
+        .map(|datetime| datetime.map(|datetime| datetime.to_rfc3339()))
+}
+
+async fn import_bookmarks(
+    client: &Client,
+    html: &str,
+    dry_run: bool,
+) -> ApplicationResult<BookmarkImportReport> {
+    let mut known_urls = HashSet::new();
+    let mut pages = RemarkPages::new(client);
+
+    while let Some(remarks) = pages.next().await? {
+        known_urls.extend(bookmarks::remark_urls(&remarks));
+    }
+
+    let mut report = BookmarkImportReport::new(dry_run);
+    let mut pending: Vec<(String, BulkNewRemark)> = Vec::new();
+
+    for bookmark in bookmarks::parse(html) {
+        let label = bookmark.label().to_string();
+
+        if !bookmark.is_supported() {
+            report.push(label, BookmarkImportStatus::Unsupported);
+            continue;
+        }
+
+        if !known_urls.insert(bookmarks::normalize_url(&bookmark.url)) {
+            report.push(label, BookmarkImportStatus::AlreadyExists);
+            continue;
+        }
+
+        let tag = match bookmark.tag() {
+            Ok(tag) => tag,
+            Err(err) => {
+                report.push(label, BookmarkImportStatus::Failed(err.to_string()));
+                continue;
+            }
+        };
+
+        pending.push((
+            label,
+            BulkNewRemark {
+                essence: bookmark.essence(),
+                tags: tag.iter().map(|tag| tag.to_string()).collect(),
+                created_at: bookmark.added_at,
+                content_hash: Some(bookmark.content_hash()),
+                kind: Some("link".to_string()),
+                payload: Some(RemarkPayload {
+                    url: Some(bookmark.url.clone()),
+                    ..Default::default()
+                }),
+            },
+        ));
+    }
+
+    while !pending.is_empty() {
+        let (labels, new_remarks): (Vec<String>, Vec<BulkNewRemark>) =
+            pending.drain(..pending.len().min(BULK_MAX_REMARKS)).unzip();
+
+        match remarks::create_bulk(client, new_remarks, dry_run).await {
+            Ok(outcomes) => {
+                for (label, outcome) in labels.into_iter().zip(outcomes) {
+                    let status = match (outcome.is_skipped(), outcome.remark_id()) {
+                        (true, _) => BookmarkImportStatus::AlreadyExists,
+                        (false, Some(id)) => BookmarkImportStatus::Created(id),
+                        (false, None) => BookmarkImportStatus::WouldCreate,
+                    };
+
+                    report.push(label, status);
+                }
+            }
+            Err(err) => {
+                for label in labels {
+                    report.push(label, BookmarkImportStatus::Failed(err.to_string()));
+                }
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+async fn import_enex_note(client: &Client, note: EnexNote) -> EnexImportStatus {
+    let EnexNote { remark, resources } = note;
+    let id = remark.id();
+
+    match remarks::import_remarks(client, &[remark]).await {
+        Ok(summary) => match summary.failures().first() {
+            Some(failure) => return EnexImportStatus::Failed(failure.message().to_string()),
+            None if summary.imported() == 0 => return EnexImportStatus::AlreadyImported(id),
+            None => {}
+        },
+        Err(err) => return EnexImportStatus::Failed(err.to_string()),
+    }
+
+    let mut attached = 0;
+    let mut skipped_resources = Vec::new();
+
+    for EnexResource {
+        file_name,
+        media_type,
+        content,
+    } in resources
+    {
+        if content.is_empty() {
+            skipped_resources.push((
+                file_name,
+                "resource data is missing or not valid base64".to_string(),
+            ));
+            continue;
+        }
+
+        let new_attachment = NewAttachment {
+            file_name: file_name.clone(),
+            media_type,
+            content,
+        };
+
+        match remarks::attach(client, id, new_attachment).await {
+            Ok(_) => attached += 1,
+            Err(err) => skipped_resources.push((file_name, err.to_string())),
+        }
+    }
+
+    EnexImportStatus::Imported {
+        id,
+        attached,
+        skipped_resources,
+    }
+}
+
+async fn import_markdown(
+    client: &Client,
+    dir: &Path,
+    dry_run: bool,
+) -> ApplicationResult<MarkdownImportReport> {
+    let mut report = MarkdownImportReport::new(dry_run);
+    let mut hashes = HashSet::new();
+    let mut batch: Vec<(PathBuf, BulkNewRemark)> = Vec::new();
+    let mut batch_bytes = 0;
+
+    for path in markdown::note_paths(dir)? {
+        let note = match markdown::read_note(&path, &Local) {
+            Ok(note) => note,
+            Err(err) => {
+                report.push(path, MarkdownImportStatus::Failed(err.to_string()));
+                continue;
+            }
+        };
+
+        if !hashes.insert(note.content_hash.clone()) {
+            report.push(path, MarkdownImportStatus::Duplicate);
+            continue;
+        }
+
+        for message in note.skipped_tags {
+            report.warn(path.clone(), message);
+        }
+
+        if batch.len() == BULK_MAX_REMARKS
+            || (!batch.is_empty() && batch_bytes + note.essence.len() > BULK_MAX_BYTES)
+        {
+            create_markdown_batch(client, std::mem::take(&mut batch), dry_run, &mut report).await;
+            batch_bytes = 0;
+        }
+
+        batch_bytes += note.essence.len();
+        batch.push((
+            path,
+            BulkNewRemark {
+                essence: note.essence,
+                tags: note.tags.iter().map(|tag| tag.to_string()).collect(),
+                created_at: Some(note.created_at),
+                content_hash: Some(note.content_hash),
+                kind: None,
+                payload: None,
+            },
+        ));
+    }
+
+    if !batch.is_empty() {
+        create_markdown_batch(client, batch, dry_run, &mut report).await;
+    }
+
+    Ok(report)
+}
+
+async fn create_markdown_batch(
+    client: &Client,
+    batch: Vec<(PathBuf, BulkNewRemark)>,
+    dry_run: bool,
+    report: &mut MarkdownImportReport,
+) {
+    let (paths, new_remarks): (Vec<PathBuf>, Vec<BulkNewRemark>) = batch.into_iter().unzip();
+
+    match remarks::create_bulk(client, new_remarks, dry_run).await {
+        Ok(outcomes) => {
+            for (path, outcome) in paths.into_iter().zip(outcomes) {
+                let status = match (outcome.is_skipped(), outcome.remark_id()) {
+                    (true, id) => MarkdownImportStatus::AlreadyImported(id),
+                    (false, Some(id)) => MarkdownImportStatus::Created(id),
+                    (false, None) => MarkdownImportStatus::WouldCreate,
+                };
+
+                report.push(path, status);
+            }
+        }
+        Err(err) => {
+            for path in paths {
+                report.push(path, MarkdownImportStatus::Failed(err.to_string()));
+            }
+        }
+    }
+}
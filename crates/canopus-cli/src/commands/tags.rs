@@ -1,20 +1,53 @@
 use crate::CliApp;
-use canopus_client::tags;
-use canopus_definitions::ApplicationResult;
-use clap::{Subcommand, arg};
+use canopus_client::{
+    Client,
+    tags::{self, TagUpdates, TagsPageParameters},
+};
+use canopus_definitions::{ApplicationResult, PageSize, Tag, TagTitle};
+use clap::Subcommand;
 use uuid::Uuid;
 
 #[derive(Subcommand)]
 pub enum TagsCommands {
+    ListTagChildren {
+        id: Uuid,
+    },
+
     ListTags {
         #[arg(short, long)]
         page_token: Option<String>,
+
+        #[arg(long, conflicts_with = "tree")]
+        page_size: Option<i64>,
+
+        #[arg(long, conflicts_with = "tree", value_parser = ["created_at_desc", "title", "usage_count"])]
+        order_by: Option<String>,
+
+        #[arg(long, conflicts_with = "page_token")]
+        tree: bool,
+
+        #[arg(long, conflicts_with = "tree")]
+        with_usage_count: bool,
+    },
+
+    MergeTags {
+        source_id: Uuid,
+
+        target_id: Uuid,
     },
 
     ShowTag {
         id: Uuid,
     },
 
+    SuggestTags {
+        #[arg(default_value = "")]
+        prefix: String,
+
+        #[arg(long)]
+        page_size: Option<i64>,
+    },
+
     UpdateTag {
         id: Uuid,
 
@@ -23,23 +56,68 @@ pub enum TagsCommands {
     },
 }
 
+struct TagsTree {
+    titles: Vec<TagTitle>,
+}
+
 impl TagsCommands {
     pub async fn execute(self, app: &CliApp) -> ApplicationResult<()> {
         let CliApp { client, renderer } = app;
 
         match self {
-            Self::ListTags { page_token } => {
-                let page = tags::index(client, page_token).await?;
+            Self::ListTagChildren { id } => {
+                let page = tags::children(client, id).await?;
 
                 renderer.render(page);
             }
+            Self::ListTags { tree: true, .. } => {
+                let tags = list_all_tags(client).await?;
+
+                renderer.render(TagsTree::new(tags));
+            }
+            Self::ListTags {
+                page_token,
+                page_size,
+                order_by,
+                tree: false,
+                with_usage_count,
+            } => {
+                let page = tags::index(
+                    client,
+                    TagsPageParameters {
+                        page_token,
+                        page_size,
+                        order_by,
+                        with_usage_count,
+                    },
+                )
+                .await?;
+
+                renderer.render(page);
+            }
+            Self::MergeTags {
+                source_id,
+                target_id,
+            } => {
+                let tag = tags::merge(client, source_id, target_id).await?;
+
+                renderer.render(tag);
+            }
             Self::ShowTag { id } => {
                 let tag = tags::show(client, id).await?;
 
                 renderer.render(tag);
             }
+            Self::SuggestTags { prefix, page_size } => {
+                let page = tags::suggest(client, &prefix, page_size).await?;
+
+                renderer.render(page);
+            }
             Self::UpdateTag { id, title } => {
-                let tag = tags::update(client, id, title).await?;
+                let tag = tags::show(client, id).await?;
+
+                let tag =
+                    tags::update(client, id, TagUpdates { title }, Some(tag.updated_at())).await?;
 
                 renderer.render(tag);
             }
@@ -48,3 +126,51 @@ impl TagsCommands {
         Ok(())
     }
 }
+
+impl TagsTree {
+    fn new(tags: Vec<Tag>) -> Self {
+        let mut titles: Vec<TagTitle> = tags.iter().map(|tag| tag.title().clone()).collect();
+
+        titles.sort_by(|a, b| a.split('/').cmp(b.split('/')));
+
+        Self { titles }
+    }
+}
+
+impl std::fmt::Display for TagsTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines = self
+            .titles
+            .iter()
+            .map(|title| format!("{}{}", "  ".repeat(title.depth()), title.name()))
+            .collect::<Vec<String>>();
+
+        f.write_str(&lines.join("\n"))
+    }
+}
+
+async fn list_all_tags(client: &Client) -> ApplicationResult<Vec<Tag>> {
+    let mut tags = Vec::new();
+    let mut page_token = None;
+
+    loop {
+        let page = tags::index(
+            client,
+            TagsPageParameters {
+                page_token,
+                page_size: Some(PageSize::MAX),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        tags.extend(page.items);
+
+        match page.next_page_token {
+            Some(token) => page_token = Some(token.as_str().to_string()),
+            None => break,
+        }
+    }
+
+    Ok(tags)
+}
@@ -0,0 +1,38 @@
+use canopus_client::{Client, tags};
+use clap_complete::engine::CompletionCandidate;
+use std::ffi::OsStr;
+
+pub fn complete_tags(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Some(current) = current.to_str() else {
+        return Vec::new();
+    };
+
+    let (head, prefix) = match current.rsplit_once(',') {
+        Some((head, prefix)) => (format!("{head},"), prefix),
+        None => (String::new(), current),
+    };
+
+    let Ok(client) = Client::from_env() else {
+        return Vec::new();
+    };
+
+    let page = tokio::task::block_in_place(|| {
+        tokio::runtime::Handle::current().block_on(tags::suggest(&client, prefix, None))
+    });
+
+    let Ok(page) = page else {
+        return Vec::new();
+    };
+
+    page.items
+        .iter()
+        .map(|tag| {
+            let help = tag.usage_count().map(|count| match count {
+                1 => "1 remark".into(),
+                count => format!("{count} remarks").into(),
+            });
+
+            CompletionCandidate::new(format!("{head}{}", tag.title())).help(help)
+        })
+        .collect()
+}
@@ -0,0 +1,26 @@
+use canopus_definitions::RemarkRevision;
+use similar::TextDiff;
+
+pub fn unified(from: &RemarkRevision, to: &RemarkRevision) -> String {
+    let old = revision_text(from);
+    let new = revision_text(to);
+
+    TextDiff::from_lines(&old, &new)
+        .unified_diff()
+        .header(
+            &format!("revision {}", from.number()),
+            &format!("revision {}", to.number()),
+        )
+        .to_string()
+}
+
+fn revision_text(revision: &RemarkRevision) -> String {
+    let tags = revision
+        .tags()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    format!("tags: {}\n\n{}\n", tags, revision.essence().as_str())
+}
@@ -0,0 +1,572 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+use canopus_definitions::{
+    ApplicationError, ApplicationResult, Remark, RemarkAttributes, RemarkEssence, RemarkKind,
+    TagTitle,
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use quick_xml::{
+    Reader,
+    escape::resolve_html5_entity,
+    events::{BytesStart, BytesText, Event},
+};
+use sha2::{Digest, Sha256};
+use std::io::BufRead;
+use uuid::Uuid;
+
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+pub struct EnexReader<R> {
+    reader: Reader<R>,
+    buf: Vec<u8>,
+}
+
+pub struct EnexNote {
+    pub remark: Remark,
+    pub resources: Vec<EnexResource>,
+}
+
+pub struct EnexResource {
+    pub file_name: String,
+    pub media_type: Option<String>,
+    pub content: Vec<u8>,
+}
+
+pub enum EnexImportStatus {
+    Imported {
+        id: Uuid,
+        attached: usize,
+        skipped_resources: Vec<(String, String)>,
+    },
+    AlreadyImported(Uuid),
+    Failed(String),
+}
+
+#[derive(Default)]
+pub struct EnexImportReport {
+    entries: Vec<(String, EnexImportStatus)>,
+}
+
+#[derive(Default)]
+struct RawNote {
+    title: String,
+    content: String,
+    created: String,
+    updated: String,
+    tags: Vec<String>,
+    resources: Vec<RawResource>,
+}
+
+#[derive(Default)]
+struct RawResource {
+    data: String,
+    mime: String,
+    file_name: String,
+}
+
+#[derive(Default)]
+struct MarkdownWriter {
+    output: String,
+    lists: Vec<Option<usize>>,
+    links: Vec<Option<String>>,
+    quote_depth: usize,
+    in_pre: bool,
+    skip_depth: usize,
+}
+
+impl<R: BufRead> EnexReader<R> {
+    pub fn new(input: R) -> Self {
+        Self {
+            reader: Reader::from_reader(input),
+            buf: Vec::new(),
+        }
+    }
+
+    pub fn next_note(
+        &mut self,
+    ) -> ApplicationResult<Option<(String, ApplicationResult<EnexNote>)>> {
+        let mut note: Option<RawNote> = None;
+        let mut resource: Option<RawResource> = None;
+        let mut text = String::new();
+
+        loop {
+            self.buf.clear();
+
+            match self
+                .reader
+                .read_event_into(&mut self.buf)
+                .map_err(read_err)?
+            {
+                Event::Start(element) => {
+                    text.clear();
+
+                    match element.local_name().as_ref() {
+                        b"note" => note = Some(RawNote::default()),
+                        b"resource" => resource = Some(RawResource::default()),
+                        _ => {}
+                    }
+                }
+                Event::Text(content) => text.push_str(&unescape(&content)?),
+                Event::CData(content) => text.push_str(&String::from_utf8_lossy(&content)),
+                Event::End(element) => {
+                    let Some(current) = note.as_mut() else {
+                        continue;
+                    };
+
+                    let value = std::mem::take(&mut text);
+
+                    match (element.local_name().as_ref(), resource.as_mut()) {
+                        (b"data", Some(resource)) => resource.data = value,
+                        (b"mime", Some(resource)) => resource.mime = value,
+                        (b"file-name", Some(resource)) => resource.file_name = value,
+                        (b"resource", _) => current.resources.extend(resource.take()),
+                        (b"title", None) => current.title = value,
+                        (b"content", None) => current.content = value,
+                        (b"created", None) => current.created = value,
+                        (b"updated", None) => current.updated = value,
+                        (b"tag", None) => current.tags.push(value),
+                        (b"note", _) => {
+                            let note = note.take().unwrap_or_default();
+                            let title = note.title.trim().to_string();
+
+                            return Ok(Some((title, note.convert())));
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Eof => return Ok(None),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl RawNote {
+    fn convert(self) -> ApplicationResult<EnexNote> {
+        let RawNote {
+            title,
+            content,
+            created,
+            updated,
+            tags,
+            resources,
+        } = self;
+
+        let title = title.trim();
+        let body = enml_to_markdown(&content)?;
+
+        let essence = match body.lines().next() {
+            None if title.is_empty() => {
+                return Err(ApplicationError::invalid_argument("note is empty"));
+            }
+            None => format!("# {}", title),
+            Some(first_line)
+                if title.is_empty() || first_line.trim_start_matches(['#', ' ']) == title =>
+            {
+                body
+            }
+            Some(_) => format!("# {}\n\n{}", title, body),
+        };
+
+        let created_at = parse_timestamp(&created)?.unwrap_or_else(Utc::now);
+        let updated_at = parse_timestamp(&updated)?
+            .unwrap_or(created_at)
+            .max(created_at);
+
+        let mut tag_titles = Vec::new();
+
+        for tag in tags {
+            let tag = TagTitle::new(tag)?;
+
+            if !tag_titles.contains(&tag) {
+                tag_titles.push(tag);
+            }
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(title);
+        hasher.update([0]);
+        hasher.update(created.trim());
+        hasher.update([0]);
+        hasher.update(&content);
+
+        let digest = hasher.finalize();
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(&digest[..16]);
+
+        let remark = Remark::new(RemarkAttributes {
+            id: Uuid::new_v8(bytes),
+            essence: RemarkEssence::new(essence)?,
+            tags: tag_titles,
+            created_at,
+            updated_at,
+            deleted_at: None,
+            kind: RemarkKind::Note,
+        });
+
+        let resources = resources
+            .into_iter()
+            .enumerate()
+            .map(|(index, resource)| resource.convert(index))
+            .collect();
+
+        Ok(EnexNote { remark, resources })
+    }
+}
+
+impl RawResource {
+    fn convert(self, index: usize) -> EnexResource {
+        let RawResource {
+            data,
+            mime,
+            file_name,
+        } = self;
+
+        let mime = mime.trim();
+
+        let file_name = match file_name.trim() {
+            "" => {
+                let extension = mime.rsplit_once('/').map_or("bin", |(_, subtype)| subtype);
+
+                format!("resource-{}.{}", index + 1, extension)
+            }
+            file_name => file_name.to_string(),
+        };
+
+        let data = data
+            .bytes()
+            .filter(|byte| !byte.is_ascii_whitespace())
+            .collect::<Vec<u8>>();
+
+        EnexResource {
+            file_name,
+            media_type: Some(mime.to_string()).filter(|mime| !mime.is_empty()),
+            content: STANDARD.decode(data).unwrap_or_default(),
+        }
+    }
+}
+
+impl EnexImportReport {
+    pub fn push(&mut self, title: String, status: EnexImportStatus) {
+        self.entries.push((title, status));
+    }
+}
+
+impl std::fmt::Display for EnexImportReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut imported = 0;
+        let mut skipped = 0;
+        let mut failed = 0;
+
+        for (title, status) in &self.entries {
+            let title = if title.is_empty() {
+                "(untitled)"
+            } else {
+                title
+            };
+
+            match status {
+                EnexImportStatus::Imported {
+                    id,
+                    attached,
+                    skipped_resources,
+                } => {
+                    imported += 1;
+                    writeln!(f, "imported {} ({}, {} attached)", title, id, attached)?;
+
+                    for (file_name, message) in skipped_resources {
+                        writeln!(f, "  skipped resource {}: {}", file_name, message)?;
+                    }
+                }
+                EnexImportStatus::AlreadyImported(id) => {
+                    skipped += 1;
+                    writeln!(f, "skipped {} (already imported as {})", title, id)?;
+                }
+                EnexImportStatus::Failed(message) => {
+                    failed += 1;
+                    writeln!(f, "failed {}: {}", title, message)?;
+                }
+            }
+        }
+
+        write!(
+            f,
+            "{} imported, {} skipped, {} failed",
+            imported, skipped, failed
+        )
+    }
+}
+
+pub fn enml_to_markdown(content: &str) -> ApplicationResult<String> {
+    let mut reader = Reader::from_str(content);
+    let mut writer = MarkdownWriter::default();
+
+    reader.config_mut().expand_empty_elements = true;
+
+    loop {
+        match reader.read_event().map_err(read_err)? {
+            Event::Start(element) => writer.start(&element),
+            Event::End(element) => writer.end(element.local_name().as_ref()),
+            Event::Text(text) => writer.text(&unescape(&text)?),
+            Event::CData(text) => writer.text(&String::from_utf8_lossy(&text)),
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(writer.finish())
+}
+
+impl MarkdownWriter {
+    fn start(&mut self, element: &BytesStart) {
+        let name = element.local_name();
+
+        if self.skip_depth > 0 || name.as_ref() == b"en-crypt" {
+            self.skip_depth += 1;
+            return;
+        }
+
+        match name.as_ref() {
+            b"div" | b"p" | b"tr" => self.line_break(),
+            b"br" => self.newline(),
+            b"hr" => {
+                self.paragraph_break();
+                self.output.push_str("---");
+                self.newline();
+            }
+            b"h1" | b"h2" | b"h3" | b"h4" | b"h5" | b"h6" => {
+                let level = usize::from(name.as_ref()[1] - b'0');
+
+                self.paragraph_break();
+                self.output.push_str(&"#".repeat(level));
+                self.output.push(' ');
+            }
+            b"ul" => self.lists.push(None),
+            b"ol" => self.lists.push(Some(0)),
+            b"li" => {
+                self.line_break();
+                self.output
+                    .push_str(&"  ".repeat(self.lists.len().saturating_sub(1)));
+
+                match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        self.output.push_str(&format!("{}. ", number));
+                    }
+                    _ => self.output.push_str("- "),
+                }
+            }
+            b"en-todo" => {
+                let checked = element
+                    .try_get_attribute("checked")
+                    .ok()
+                    .flatten()
+                    .is_some_and(|checked| checked.value.as_ref() == b"true");
+
+                if !self.output.ends_with("- ") {
+                    self.output.push_str("- ");
+                }
+
+                self.output.push_str(if checked { "[x] " } else { "[ ] " });
+            }
+            b"a" => {
+                let href = element
+                    .try_get_attribute("href")
+                    .ok()
+                    .flatten()
+                    .and_then(|href| href.unescape_value().ok())
+                    .map(|href| href.to_string());
+
+                self.output.push('[');
+                self.links.push(href);
+            }
+            b"b" | b"strong" => self.output.push_str("**"),
+            b"i" | b"em" => self.output.push('*'),
+            b"s" | b"strike" | b"del" => self.output.push_str("~~"),
+            b"code" if !self.in_pre => self.output.push('`'),
+            b"pre" => {
+                self.paragraph_break();
+                self.output.push_str("```");
+                self.newline();
+                self.in_pre = true;
+            }
+            b"blockquote" => {
+                self.paragraph_break();
+                self.quote_depth += 1;
+                self.output.push_str("> ");
+            }
+            b"td" | b"th" if !self.at_line_start() => self.output.push_str(" | "),
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, name: &[u8]) {
+        if self.skip_depth > 0 {
+            self.skip_depth -= 1;
+            return;
+        }
+
+        match name {
+            b"div" | b"p" | b"li" | b"tr" => self.line_break(),
+            b"h1" | b"h2" | b"h3" | b"h4" | b"h5" | b"h6" => self.paragraph_break(),
+            b"ul" | b"ol" => {
+                self.lists.pop();
+                self.line_break();
+            }
+            b"a" => match self.links.pop().flatten() {
+                Some(href) => self.output.push_str(&format!("]({})", href)),
+                None => self.output.push(']'),
+            },
+            b"b" | b"strong" => self.output.push_str("**"),
+            b"i" | b"em" => self.output.push('*'),
+            b"s" | b"strike" | b"del" => self.output.push_str("~~"),
+            b"code" if !self.in_pre => self.output.push('`'),
+            b"pre" => {
+                self.in_pre = false;
+                self.line_break();
+                self.output.push_str("```");
+                self.paragraph_break();
+            }
+            b"blockquote" => {
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+                self.paragraph_break();
+            }
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.skip_depth > 0 {
+            return;
+        }
+
+        if self.in_pre {
+            self.output.push_str(text);
+            return;
+        }
+
+        for (index, word) in text.split(|c: char| c.is_whitespace()).enumerate() {
+            let at_start = self.at_line_start() || self.output.ends_with(' ');
+
+            if index > 0 && !at_start {
+                self.output.push(' ');
+            }
+
+            self.output.push_str(word);
+        }
+    }
+
+    fn finish(self) -> String {
+        let mut lines: Vec<&str> = Vec::new();
+
+        for line in self.output.lines().map(str::trim_end) {
+            let blank = line.is_empty() || line.chars().all(|c| c == '>' || c == ' ');
+
+            if blank && lines.last().is_none_or(|last| last.is_empty()) {
+                continue;
+            }
+
+            lines.push(if blank { "" } else { line });
+        }
+
+        lines.join("\n").trim().to_string()
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.output.is_empty()
+            || self.output.ends_with('\n')
+            || self.output.ends_with("> ")
+            || self.output.ends_with("- ")
+            || self.output.ends_with("] ")
+            || self.output.ends_with("# ")
+    }
+
+    fn line_break(&mut self) {
+        let line = self.output.rsplit('\n').next().unwrap_or_default();
+
+        if !line.chars().all(|c| c == '>' || c == ' ') {
+            self.newline();
+        }
+    }
+
+    fn newline(&mut self) {
+        self.output.push('\n');
+        self.output.push_str(&"> ".repeat(self.quote_depth));
+    }
+
+    fn paragraph_break(&mut self) {
+        self.line_break();
+        self.newline();
+    }
+}
+
+fn parse_timestamp(value: &str) -> ApplicationResult<Option<DateTime<Utc>>> {
+    let value = value.trim();
+
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    NaiveDateTime::parse_from_str(value, TIMESTAMP_FORMAT)
+        .map(|timestamp| Some(timestamp.and_utc()))
+        .map_err(|_| ApplicationError::invalid_argument(&format!("invalid timestamp {}", value)))
+}
+
+fn read_err(err: quick_xml::Error) -> ApplicationError {
+    ApplicationError::invalid_argument(&format!("invalid ENEX file: {}", err))
+}
+
+fn unescape(text: &BytesText) -> ApplicationResult<String> {
+    text.unescape_with(resolve_html5_entity)
+        .map(|text| text.to_string())
+        .map_err(read_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enml_to_markdown() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE en-note SYSTEM "http://xml.evernote.com/pub/enml2.dtd">
+<en-note><div>Plain&nbsp;<b>bold</b> <a href="https://example.com">link</a></div>
+<div><en-todo checked="true"/>Done</div><ol><li>One</li><li>Two</li></ol>
+<h1>Title</h1><en-crypt>c2VjcmV0</en-crypt><div>End</div></en-note>"#;
+
+        assert_eq!(
+            enml_to_markdown(content).unwrap(),
+            "Plain **bold** [link](https://example.com)\n- [x] Done\n1. One\n2. Two\n\n# Title\n\nEnd"
+        );
+    }
+
+    #[test]
+    fn test_read_notes() {
+        let enex = r#"<en-export><note><title>Note</title><created>20230105T083000Z</created>
+<tag>Work</tag><content><![CDATA[<en-note><div>Body</div></en-note>]]></content>
+<resource><data encoding="base64">aGk=</data><mime>text/plain</mime></resource></note>
+<note><title>Broken</title><created>yesterday</created><content><![CDATA[<en-note>x</en-note>]]></content></note>
+</en-export>"#;
+
+        let mut reader = EnexReader::new(enex.as_bytes());
+
+        let (title, note) = reader.next_note().unwrap().unwrap();
+        let note = note.unwrap();
+
+        assert_eq!(title, "Note");
+        assert_eq!(note.remark.essence().as_str(), "# Note\n\nBody");
+        assert_eq!(note.remark.tags()[0].as_str(), "work");
+        assert_eq!(
+            note.remark.created_at(),
+            "2023-01-05T08:30:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(note.remark.updated_at(), note.remark.created_at());
+        assert_eq!(note.resources[0].file_name, "resource-1.plain");
+        assert_eq!(note.resources[0].content, b"hi");
+
+        let (title, note) = reader.next_note().unwrap().unwrap();
+
+        assert_eq!(title, "Broken");
+        assert!(note.is_err());
+        assert!(reader.next_note().unwrap().is_none());
+    }
+}
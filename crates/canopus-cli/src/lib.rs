@@ -1,6 +1,13 @@
+mod bookmarks;
+mod checklist;
 mod commands;
+mod completion;
+mod diff;
 mod display;
 mod editor;
+mod enex;
+mod markdown;
+mod remarks_csv;
 
 use canopus_client::Client;
 use canopus_definitions::{ApplicationError, ApplicationResult};
@@ -1,11 +1,14 @@
 use canopus_cli::{CliApp, Cli};
-use clap::Parser;
+use clap::{CommandFactory, Parser};
+use clap_complete::CompleteEnv;
 use eyre::WrapErr;
 
 #[tokio::main]
 async fn main() -> eyre::Result<()> {
     dotenvy::dotenv().wrap_err_with(|| "Failed to load .env file")?;
 
+    CompleteEnv::with_factory(Cli::command).complete();
+
     let cli = match Cli::try_parse() {
         Ok(cli) => cli,
         Err(err) => err.exit(),
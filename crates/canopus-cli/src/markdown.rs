@@ -0,0 +1,750 @@
+use canopus_definitions::{ApplicationError, ApplicationResult, Remark, TagTitle};
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+use uuid::Uuid;
+
+const CODE_FENCE: &str = "```";
+const CREATED_AT_KEYS: [&str; 3] = ["created_at", "created", "date"];
+const FRONTMATTER_DELIMITER: &str = "---";
+const MANIFEST_FILE_NAME: &str = ".canopus-export.json";
+const SLUG_MAX_LENGTH: usize = 60;
+
+pub struct Frontmatter {
+    values: HashMap<String, String>,
+    lists: HashMap<String, Vec<String>>,
+}
+
+pub struct MarkdownExport {
+    dir: PathBuf,
+    manifest: ExportManifest,
+    occupied: HashMap<String, Option<Uuid>>,
+    live_ids: HashSet<Uuid>,
+    summary: MarkdownExportSummary,
+}
+
+#[derive(Default)]
+pub struct MarkdownExportSummary {
+    written: usize,
+    unchanged: usize,
+    removed: usize,
+}
+
+pub struct MarkdownNote {
+    pub essence: String,
+    pub tags: Vec<TagTitle>,
+    pub skipped_tags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub content_hash: String,
+}
+
+pub enum MarkdownImportStatus {
+    Created(Uuid),
+    WouldCreate,
+    AlreadyImported(Option<Uuid>),
+    Duplicate,
+    Failed(String),
+}
+
+pub struct MarkdownImportReport {
+    dry_run: bool,
+    entries: Vec<(PathBuf, MarkdownImportStatus)>,
+    warnings: Vec<(PathBuf, String)>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct ExportManifest {
+    files: HashMap<Uuid, ExportedFile>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ExportedFile {
+    name: String,
+    updated_at: DateTime<Utc>,
+}
+
+impl MarkdownExport {
+    pub fn open(dir: &Path) -> ApplicationResult<Self> {
+        fs::create_dir_all(dir).map_err(export_err)?;
+
+        let mut manifest: ExportManifest = match fs::read(dir.join(MANIFEST_FILE_NAME)) {
+            Ok(json) => serde_json::from_slice(&json).map_err(|_| {
+                ApplicationError::invalid_argument("markdown export manifest is malformed")
+            })?,
+            Err(err) if err.kind() == ErrorKind::NotFound => ExportManifest::default(),
+            Err(err) => return Err(export_err(err)),
+        };
+
+        manifest
+            .files
+            .retain(|_, file| dir.join(&file.name).is_file());
+
+        let mut occupied: HashMap<String, Option<Uuid>> = HashMap::new();
+
+        for entry in fs::read_dir(dir).map_err(export_err)? {
+            let path = entry.map_err(export_err)?.path();
+
+            if path.extension().is_some_and(|extension| extension == "md")
+                && let Some(name) = path.file_name().and_then(|name| name.to_str())
+            {
+                occupied.insert(name.to_string(), None);
+            }
+        }
+
+        for (id, file) in &manifest.files {
+            occupied.insert(file.name.clone(), Some(*id));
+        }
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            manifest,
+            occupied,
+            live_ids: HashSet::new(),
+            summary: MarkdownExportSummary::default(),
+        })
+    }
+
+    // The manifest is saved even when a write fails, so files already written stay tracked
+    pub fn write(&mut self, remarks: &[Remark]) -> ApplicationResult<()> {
+        let result = self.write_remarks(remarks);
+
+        if result.is_err() {
+            self.save_manifest()?;
+        }
+
+        result
+    }
+
+    pub fn finish(mut self) -> ApplicationResult<MarkdownExportSummary> {
+        let result = self.remove_stale_files();
+
+        self.save_manifest()?;
+
+        result.map(|()| self.summary)
+    }
+
+    // Only files listed in the manifest were written by the export, any other file is left alone
+    fn remove_stale_files(&mut self) -> ApplicationResult<()> {
+        let removed_ids: Vec<Uuid> = self
+            .manifest
+            .files
+            .keys()
+            .filter(|id| !self.live_ids.contains(id))
+            .copied()
+            .collect();
+
+        for id in removed_ids {
+            if let Some(file) = self.manifest.files.remove(&id) {
+                fs::remove_file(self.dir.join(&file.name)).map_err(export_err)?;
+                self.summary.removed += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_remarks(&mut self, remarks: &[Remark]) -> ApplicationResult<()> {
+        for remark in remarks {
+            self.live_ids.insert(remark.id());
+
+            let previous = self.manifest.files.get(&remark.id());
+
+            if previous.is_some_and(|file| file.updated_at == remark.updated_at()) {
+                self.summary.unchanged += 1;
+                continue;
+            }
+
+            let name = file_name(remark, &self.occupied);
+
+            if let Some(previous) = previous.filter(|file| file.name != name) {
+                fs::remove_file(self.dir.join(&previous.name)).map_err(export_err)?;
+                self.occupied.remove(&previous.name);
+            }
+
+            fs::write(self.dir.join(&name), render(remark)).map_err(export_err)?;
+            self.occupied.insert(name.clone(), Some(remark.id()));
+
+            self.manifest.files.insert(
+                remark.id(),
+                ExportedFile {
+                    name,
+                    updated_at: remark.updated_at(),
+                },
+            );
+            self.summary.written += 1;
+        }
+
+        Ok(())
+    }
+
+    fn save_manifest(&self) -> ApplicationResult<()> {
+        let json = serde_json::to_vec_pretty(&self.manifest).map_err(|err| {
+            ApplicationError::internal("failed to serialize markdown export manifest", err)
+        })?;
+
+        fs::write(self.dir.join(MANIFEST_FILE_NAME), json).map_err(export_err)
+    }
+}
+
+pub fn note_paths(dir: &Path) -> ApplicationResult<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    let mut entries = fs::read_dir(dir)
+        .map_err(import_err)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<PathBuf>, _>>()
+        .map_err(import_err)?;
+
+    entries.sort();
+
+    for path in entries {
+        let hidden = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_none_or(|name| name.starts_with('.'));
+
+        if hidden {
+            continue;
+        }
+
+        if path.is_dir() {
+            paths.extend(note_paths(&path)?);
+        } else if path.extension().is_some_and(|extension| extension == "md") {
+            paths.push(path);
+        }
+    }
+
+    Ok(paths)
+}
+
+pub fn read_note<Tz: TimeZone>(path: &Path, time_zone: &Tz) -> ApplicationResult<MarkdownNote> {
+    let bytes = fs::read(path).map_err(import_err)?;
+    let content_hash = format!("{:x}", Sha256::digest(&bytes));
+
+    let content = String::from_utf8(bytes)
+        .map_err(|_| ApplicationError::invalid_argument("note is not valid UTF-8"))?;
+
+    let (frontmatter, body) = parse(&content);
+    let body = body.trim();
+
+    if body.is_empty() {
+        return Err(ApplicationError::invalid_argument("note is empty"));
+    }
+
+    let mut tags = Vec::new();
+    let mut skipped_tags = Vec::new();
+
+    let frontmatter_tags = frontmatter
+        .as_ref()
+        .map(|frontmatter| frontmatter.list("tags"))
+        .unwrap_or_default();
+
+    for tag in frontmatter_tags.into_iter().chain(hashtags(body)) {
+        match TagTitle::new(tag.trim_start_matches('#').to_string()) {
+            Ok(title) if !tags.contains(&title) => tags.push(title),
+            Ok(_) => {}
+            Err(err) => skipped_tags.push(format!("skipped tag \"{}\": {}", tag, err)),
+        }
+    }
+
+    let created_at = match frontmatter
+        .as_ref()
+        .and_then(|frontmatter| CREATED_AT_KEYS.iter().find_map(|key| frontmatter.get(key)))
+    {
+        Some(created_at) => canopus_definitions::parse_absolute_datetime(created_at, time_zone)?,
+        None => fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .map(DateTime::<Utc>::from)
+            .map_err(import_err)?,
+    };
+
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default();
+
+    let first_line = body.lines().next().unwrap_or_default();
+
+    let essence = if is_titled_by(first_line, stem) {
+        body.to_string()
+    } else {
+        format!("# {}\n\n{}", stem, body)
+    };
+
+    Ok(MarkdownNote {
+        essence,
+        tags,
+        skipped_tags,
+        created_at,
+        content_hash,
+    })
+}
+
+pub fn hashtags(body: &str) -> Vec<&str> {
+    let mut tags = Vec::new();
+    let mut in_code_block = false;
+
+    for line in body.lines() {
+        if line.trim_start().starts_with(CODE_FENCE) {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            continue;
+        }
+
+        let mut in_code_span = false;
+        let mut previous = ' ';
+
+        for (index, c) in line.char_indices() {
+            if c == '`' {
+                in_code_span = !in_code_span;
+            } else if c == '#' && !in_code_span && previous.is_whitespace() {
+                let tag = &line[index + 1..];
+                let end = tag
+                    .find(|c: char| !(c.is_alphanumeric() || matches!(c, '/' | '-' | '_')))
+                    .unwrap_or(tag.len());
+                let tag = tag[..end].trim_end_matches('/');
+
+                if tag.chars().any(|c| !c.is_ascii_digit()) {
+                    tags.push(tag);
+                }
+            }
+
+            previous = c;
+        }
+    }
+
+    tags
+}
+
+pub fn parse(content: &str) -> (Option<Frontmatter>, &str) {
+    let Some(rest) = content
+        .strip_prefix(FRONTMATTER_DELIMITER)
+        .and_then(|rest| {
+            rest.strip_prefix('\n')
+                .or_else(|| rest.strip_prefix("\r\n"))
+        })
+    else {
+        return (None, content);
+    };
+
+    let mut values = HashMap::new();
+    let mut lists: HashMap<String, Vec<String>> = HashMap::new();
+    let mut list_key: Option<String> = None;
+    let mut offset = 0;
+
+    for line in rest.split_inclusive('\n') {
+        offset += line.len();
+
+        let line = line.trim_end();
+
+        if line == FRONTMATTER_DELIMITER {
+            let body = rest[offset..].trim_start_matches(['\r', '\n']);
+
+            return (Some(Frontmatter { values, lists }), body);
+        }
+
+        if let Some(item) = line.trim_start().strip_prefix("- ") {
+            if let Some(key) = &list_key {
+                lists
+                    .entry(key.clone())
+                    .or_default()
+                    .push(unquote(item.trim()));
+            }
+
+            continue;
+        }
+
+        list_key = None;
+
+        if line.starts_with([' ', '#']) {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+
+        let key = key.trim().to_string();
+        let value = value.trim();
+
+        if value.is_empty() {
+            list_key = Some(key);
+        } else if let Some(items) = value
+            .strip_prefix('[')
+            .and_then(|value| value.strip_suffix(']'))
+        {
+            lists.insert(
+                key,
+                items
+                    .split(',')
+                    .map(|item| unquote(item.trim()))
+                    .filter(|item| !item.is_empty())
+                    .collect(),
+            );
+        } else {
+            values.insert(key, unquote(value));
+        }
+    }
+
+    (None, content)
+}
+
+pub fn render(remark: &Remark) -> String {
+    let tags = if remark.tags().is_empty() {
+        " []".to_string()
+    } else {
+        remark
+            .tags()
+            .iter()
+            .map(|tag| format!("\n  - {}", quote(tag.as_str())))
+            .collect()
+    };
+
+    format!(
+        "{delimiter}\nid: {}\ntags:{}\ncreated_at: {}\nupdated_at: {}\n{delimiter}\n\n{}\n",
+        remark.id(),
+        tags,
+        remark
+            .created_at()
+            .to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        remark
+            .updated_at()
+            .to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        remark.essence().as_str(),
+        delimiter = FRONTMATTER_DELIMITER,
+    )
+}
+
+pub fn slug(text: &str) -> String {
+    let mut slug = String::new();
+
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if slug.chars().count() >= SLUG_MAX_LENGTH {
+            break;
+        }
+
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    slug.trim_end_matches('-').to_string()
+}
+
+impl Frontmatter {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values
+            .get(key)
+            .map(String::as_str)
+            .filter(|value| !value.is_empty())
+    }
+
+    pub fn list(&self, key: &str) -> Vec<&str> {
+        match (self.lists.get(key), self.values.get(key)) {
+            (Some(items), _) => items.iter().map(String::as_str).collect(),
+            (None, Some(value)) => value
+                .split([',', ' '])
+                .filter(|item| !item.is_empty())
+                .collect(),
+            (None, None) => Vec::new(),
+        }
+    }
+}
+
+impl MarkdownImportReport {
+    pub fn new(dry_run: bool) -> Self {
+        Self {
+            dry_run,
+            entries: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, path: PathBuf, status: MarkdownImportStatus) {
+        self.entries.push((path, status));
+    }
+
+    pub fn warn(&mut self, path: PathBuf, message: String) {
+        self.warnings.push((path, message));
+    }
+}
+
+impl std::fmt::Display for MarkdownImportReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut created = 0;
+        let mut skipped = 0;
+        let mut failed = 0;
+
+        let mut entries: Vec<&(PathBuf, MarkdownImportStatus)> = self.entries.iter().collect();
+
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        for (path, status) in entries {
+            let path = path.display();
+
+            match status {
+                MarkdownImportStatus::Created(id) => {
+                    created += 1;
+                    writeln!(f, "created {} ({})", path, id)?;
+                }
+                MarkdownImportStatus::WouldCreate => {
+                    created += 1;
+                    writeln!(f, "would create {}", path)?;
+                }
+                MarkdownImportStatus::AlreadyImported(id) => {
+                    skipped += 1;
+
+                    match id {
+                        Some(id) => writeln!(f, "skipped {} (already imported as {})", path, id)?,
+                        None => writeln!(f, "skipped {} (already imported)", path)?,
+                    }
+                }
+                MarkdownImportStatus::Duplicate => {
+                    skipped += 1;
+                    writeln!(f, "skipped {} (duplicate of another file)", path)?;
+                }
+                MarkdownImportStatus::Failed(message) => {
+                    failed += 1;
+                    writeln!(f, "failed {}: {}", path, message)?;
+                }
+            }
+        }
+
+        for (path, message) in &self.warnings {
+            writeln!(f, "warning {}: {}", path.display(), message)?;
+        }
+
+        let created_label = if self.dry_run {
+            "would be created"
+        } else {
+            "created"
+        };
+
+        write!(
+            f,
+            "{} {}, {} skipped, {} failed",
+            created, created_label, skipped, failed
+        )
+    }
+}
+
+impl std::fmt::Display for MarkdownExportSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} written, {} unchanged, {} removed",
+            self.written, self.unchanged, self.removed
+        )
+    }
+}
+
+fn export_err(err: std::io::Error) -> ApplicationError {
+    ApplicationError::internal("failed to write markdown export", err)
+}
+
+fn import_err(err: std::io::Error) -> ApplicationError {
+    ApplicationError::internal("failed to read markdown import", err)
+}
+
+fn file_name(remark: &Remark, occupied: &HashMap<String, Option<Uuid>>) -> String {
+    let first_line = remark.essence().as_str().lines().next().unwrap_or_default();
+
+    let slug = match slug(first_line) {
+        slug if slug.is_empty() => remark.id().to_string(),
+        slug => slug,
+    };
+
+    let name = format!("{}.md", slug);
+
+    match occupied.get(&name) {
+        None => name,
+        Some(Some(id)) if *id == remark.id() => name,
+        Some(_) => format!("{}-{}.md", slug, &remark.id().simple().to_string()[..8]),
+    }
+}
+
+// Exported files are named after their first line, with an id suffix when the name was taken
+fn is_titled_by(line: &str, stem: &str) -> bool {
+    if Uuid::try_parse(stem).is_ok() {
+        return true;
+    }
+
+    let title = match stem.rsplit_once('-') {
+        Some((title, suffix))
+            if suffix.len() == 8 && suffix.bytes().all(|byte| byte.is_ascii_hexdigit()) =>
+        {
+            title
+        }
+        _ => stem,
+    };
+
+    slug(title).is_empty() || slug(line) == slug(title) || slug(line) == slug(stem)
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn unquote(value: &str) -> String {
+    if let Some(value) = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        return value.replace("\\\"", "\"").replace("\\\\", "\\");
+    }
+
+    if let Some(value) = value
+        .strip_prefix('\'')
+        .and_then(|value| value.strip_suffix('\''))
+    {
+        return value.replace("''", "'");
+    }
+
+    value.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canopus_definitions::{RemarkAttributes, RemarkEssence, RemarkKind, TagTitle};
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(
+            slug("# Rust ownership, explained!"),
+            "rust-ownership-explained"
+        );
+        assert_eq!(slug("Überblick: Größe"), "überblick-größe");
+        assert_eq!(slug("?!"), "");
+        assert_eq!(slug(&"a".repeat(100)).len(), SLUG_MAX_LENGTH);
+    }
+
+    #[test]
+    fn test_render_and_parse() {
+        let created_at = "2025-01-01T08:30:00.5Z".parse().unwrap();
+
+        let remark = Remark::new(RemarkAttributes {
+            id: Uuid::from_u128(42),
+            essence: RemarkEssence::new("Title\n\nBody".to_string()).unwrap(),
+            tags: vec![TagTitle::new("say \"hi\"".to_string()).unwrap()],
+            created_at,
+            updated_at: created_at,
+            deleted_at: None,
+            kind: RemarkKind::Note,
+        });
+
+        let content = render(&remark);
+        let (frontmatter, body) = parse(&content);
+        let frontmatter = frontmatter.unwrap();
+
+        assert_eq!(
+            frontmatter.get("id"),
+            Some(remark.id().to_string().as_str())
+        );
+        assert_eq!(
+            frontmatter.get("updated_at"),
+            Some("2025-01-01T08:30:00.500Z")
+        );
+        assert_eq!(body, "Title\n\nBody\n");
+        assert_eq!(parse("no frontmatter").1, "no frontmatter");
+    }
+
+    #[test]
+    fn test_export_removes_only_exported_files() {
+        let dir = std::env::temp_dir().join(format!("canopus-export-{}", Uuid::new_v4()));
+        let created_at = "2025-01-01T08:30:00Z".parse().unwrap();
+
+        let remark = |id: u128, essence: &str| {
+            Remark::new(RemarkAttributes {
+                id: Uuid::from_u128(id),
+                essence: RemarkEssence::new(essence.to_string()).unwrap(),
+                tags: vec![],
+                created_at,
+                updated_at: created_at,
+                deleted_at: None,
+                kind: RemarkKind::Note,
+            })
+        };
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("notes.md"),
+            format!("---\nid: {}\n---\n\nMine", Uuid::from_u128(3)),
+        )
+        .unwrap();
+
+        let mut export = MarkdownExport::open(&dir).unwrap();
+        export.write(&[remark(1, "First")]).unwrap();
+        export.write(&[remark(2, "Second")]).unwrap();
+
+        assert_eq!(export.finish().unwrap().written, 2);
+
+        let mut export = MarkdownExport::open(&dir).unwrap();
+        export.write(&[remark(1, "First")]).unwrap();
+
+        let summary = export.finish().unwrap();
+
+        assert_eq!((summary.unchanged, summary.removed), (1, 1));
+        assert!(dir.join("first.md").exists());
+        assert!(!dir.join("second.md").exists());
+        assert!(dir.join("notes.md").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_frontmatter_lists_and_hashtags() {
+        let content = "---\ntags:\n  - rust\n  - \"#lang/systems\"\naliases: [a, 'b']\nkeywords: one, two\n---\n\nBody";
+        let frontmatter = parse(content).0.unwrap();
+
+        assert_eq!(frontmatter.list("tags"), ["rust", "#lang/systems"]);
+        assert_eq!(frontmatter.list("aliases"), ["a", "b"]);
+        assert_eq!(frontmatter.list("keywords"), ["one", "two"]);
+
+        let body =
+            "# Heading #title\n#todo and #2024 or a#b, `#code` #dev/ops.\n```\n#skipped\n```";
+
+        assert_eq!(hashtags(body), ["title", "todo", "dev/ops"]);
+    }
+
+    #[test]
+    fn test_read_note() {
+        let dir = std::env::temp_dir().join(format!("canopus-import-{}", Uuid::new_v4()));
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("first-0000002a.md"),
+            "---\ntags: [rust, lang//systems]\ncreated_at: 2025-01-01\n---\n\nFirst\n",
+        )
+        .unwrap();
+        fs::write(dir.join("loose.md"), "Body").unwrap();
+        fs::write(dir.join("relative.md"), "---\ncreated_at: -7d\n---\n\nBody").unwrap();
+
+        let note = read_note(&dir.join("first-0000002a.md"), &Utc).unwrap();
+
+        assert_eq!(note.essence, "First");
+        assert_eq!(note.tags, [TagTitle::new("rust".to_string()).unwrap()]);
+        assert_eq!(note.skipped_tags.len(), 1);
+        assert_eq!(
+            note.created_at,
+            "2025-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+
+        let note = read_note(&dir.join("loose.md"), &Utc).unwrap();
+
+        assert_eq!(note.essence, "# loose\n\nBody");
+        assert!(read_note(&dir.join("relative.md"), &Utc).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,427 @@
+use canopus_definitions::{
+    ApplicationError, ApplicationResult, Remark, RemarkAttributes, RemarkEssence, RemarkKind,
+    RemarkKindName, RemarksImportSummary, TagTitle,
+};
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+use uuid::Uuid;
+
+const HEADERS: [&str; 7] = [
+    "id",
+    "essence",
+    "tags",
+    "created_at",
+    "updated_at",
+    "kind",
+    "payload",
+];
+const TAG_DELIMITER: char = ';';
+const TAG_ESCAPE: char = '\\';
+
+pub struct RemarksCsvWriter<W: Write> {
+    writer: csv::Writer<W>,
+}
+
+pub struct CsvRow {
+    pub line: u64,
+    pub remark: Remark,
+}
+
+pub struct CsvRowError {
+    line: u64,
+    message: String,
+}
+
+pub struct CsvImportReport {
+    summary: Option<RemarksImportSummary>,
+    errors: Vec<CsvRowError>,
+}
+
+struct Columns {
+    id: Option<usize>,
+    essence: usize,
+    tags: Option<usize>,
+    created_at: Option<usize>,
+    updated_at: Option<usize>,
+    kind: Option<usize>,
+    payload: Option<usize>,
+}
+
+impl<W: Write> RemarksCsvWriter<W> {
+    pub fn new(output: W) -> ApplicationResult<Self> {
+        let mut writer = csv::Writer::from_writer(output);
+
+        writer.write_record(HEADERS).map_err(write_err)?;
+
+        Ok(Self { writer })
+    }
+
+    pub fn write(&mut self, remarks: &[Remark]) -> ApplicationResult<()> {
+        for remark in remarks {
+            let tags = join_tags(&remark.tags());
+
+            self.writer
+                .write_record([
+                    remark.id().to_string().as_str(),
+                    remark.essence().as_str(),
+                    tags.as_str(),
+                    remark
+                        .created_at()
+                        .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+                        .as_str(),
+                    remark
+                        .updated_at()
+                        .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+                        .as_str(),
+                    remark.kind().name().as_str(),
+                    remark.kind().payload().unwrap_or_default().as_str(),
+                ])
+                .map_err(write_err)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> ApplicationResult<()> {
+        self.writer.flush().map_err(write_err)
+    }
+}
+
+pub fn read<Tz: TimeZone>(
+    input: impl Read,
+    now: DateTime<Utc>,
+    time_zone: &Tz,
+) -> ApplicationResult<(Vec<CsvRow>, Vec<CsvRowError>)> {
+    let mut reader = csv::Reader::from_reader(input);
+    let columns = Columns::new(reader.headers().map_err(read_err)?)?;
+
+    let mut remarks = Vec::new();
+    let mut errors = Vec::new();
+
+    for record in reader.records() {
+        let line = match &record {
+            Ok(record) => record.position(),
+            Err(err) => err.position(),
+        }
+        .map_or(0, csv::Position::line);
+
+        match record
+            .map_err(read_err)
+            .and_then(|record| columns.remark(&record, now, time_zone))
+        {
+            Ok(remark) => remarks.push(CsvRow { line, remark }),
+            Err(err) => errors.push(CsvRowError {
+                line,
+                message: err.to_string(),
+            }),
+        }
+    }
+
+    Ok((remarks, errors))
+}
+
+impl Columns {
+    fn new(headers: &csv::StringRecord) -> ApplicationResult<Self> {
+        let position = |name: &str| headers.iter().position(|header| header.trim() == name);
+
+        Ok(Self {
+            id: position("id"),
+            essence: position("essence").ok_or_else(|| {
+                ApplicationError::invalid_argument("CSV header has no essence column")
+            })?,
+            tags: position("tags"),
+            created_at: position("created_at"),
+            updated_at: position("updated_at"),
+            kind: position("kind"),
+            payload: position("payload"),
+        })
+    }
+
+    fn remark<Tz: TimeZone>(
+        &self,
+        record: &csv::StringRecord,
+        now: DateTime<Utc>,
+        time_zone: &Tz,
+    ) -> ApplicationResult<Remark> {
+        let field = |index: Option<usize>| {
+            index
+                .and_then(|index| record.get(index))
+                .filter(|value| !value.trim().is_empty())
+        };
+
+        let trimmed_field = |index: Option<usize>| field(index).map(str::trim);
+
+        let id = match trimmed_field(self.id) {
+            Some(id) => id
+                .parse()
+                .map_err(|_| ApplicationError::invalid_argument("invalid remark id"))?,
+            None => content_id(record),
+        };
+
+        let essence =
+            RemarkEssence::new(field(Some(self.essence)).unwrap_or_default().to_string())?;
+
+        let tags = split_tags(field(self.tags).unwrap_or_default())
+            .into_iter()
+            .filter(|tag| !tag.trim().is_empty())
+            .map(TagTitle::new)
+            .collect::<ApplicationResult<Vec<TagTitle>>>()?;
+
+        let created_at = match trimmed_field(self.created_at) {
+            Some(created_at) => canopus_definitions::parse_datetime(created_at, now, time_zone)?,
+            None => now,
+        };
+
+        let updated_at = match trimmed_field(self.updated_at) {
+            Some(updated_at) => canopus_definitions::parse_datetime(updated_at, now, time_zone)?,
+            None => created_at,
+        };
+
+        if updated_at < created_at {
+            return Err(ApplicationError::invalid_argument(
+                "remark updated_at can't be earlier than created_at",
+            ));
+        }
+
+        let kind = match trimmed_field(self.kind) {
+            Some(kind) => {
+                let kind: RemarkKindName = kind.parse()?;
+
+                RemarkKind::from_parts(kind.as_str(), field(self.payload))
+                    .map_err(|_| ApplicationError::invalid_argument("invalid remark payload"))?
+            }
+            None => RemarkKind::Note,
+        };
+
+        Ok(Remark::new(RemarkAttributes {
+            id,
+            essence,
+            tags,
+            created_at,
+            updated_at,
+            deleted_at: None,
+            kind,
+        }))
+    }
+}
+
+impl CsvImportReport {
+    pub fn new(
+        summary: Option<RemarksImportSummary>,
+        lines: &[u64],
+        mut errors: Vec<CsvRowError>,
+    ) -> Self {
+        // The server numbers the imported remarks, map them back to their CSV lines
+        if let Some(summary) = &summary {
+            for failure in summary.failures() {
+                errors.push(CsvRowError {
+                    line: failure
+                        .line()
+                        .checked_sub(1)
+                        .and_then(|index| lines.get(index as usize))
+                        .copied()
+                        .unwrap_or_default(),
+                    message: failure.message().to_string(),
+                });
+            }
+        }
+
+        errors.sort_by_key(|error| error.line);
+
+        Self { summary, errors }
+    }
+}
+
+impl std::fmt::Display for CsvImportReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for CsvRowError { line, message } in &self.errors {
+            writeln!(f, "line {}: {}", line, message)?;
+        }
+
+        let (imported, skipped) = self
+            .summary
+            .as_ref()
+            .map_or((0, 0), |summary| (summary.imported(), summary.skipped()));
+
+        write!(
+            f,
+            "{} imported, {} skipped, {} failed",
+            imported,
+            skipped,
+            self.errors.len()
+        )
+    }
+}
+
+// Rows without an id get one derived from their content, so importing the file again skips them
+fn content_id(record: &csv::StringRecord) -> Uuid {
+    let mut hasher = Sha256::new();
+
+    for field in record {
+        hasher.update(field);
+        hasher.update([0]);
+    }
+
+    let digest = hasher.finalize();
+    let mut bytes = [0; 16];
+    bytes.copy_from_slice(&digest[..16]);
+
+    Uuid::new_v8(bytes)
+}
+
+fn join_tags(tags: &[&TagTitle]) -> String {
+    let mut joined = String::new();
+
+    for (index, tag) in tags.iter().enumerate() {
+        if index > 0 {
+            joined.push(TAG_DELIMITER);
+        }
+
+        for c in tag.as_str().chars() {
+            if c == TAG_DELIMITER || c == TAG_ESCAPE {
+                joined.push(TAG_ESCAPE);
+            }
+
+            joined.push(c);
+        }
+    }
+
+    joined
+}
+
+fn split_tags(value: &str) -> Vec<String> {
+    let mut tags = vec![String::new()];
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            TAG_ESCAPE if matches!(chars.peek(), Some(&TAG_DELIMITER | &TAG_ESCAPE)) => {
+                tags.last_mut().unwrap().extend(chars.next());
+            }
+            TAG_DELIMITER => tags.push(String::new()),
+            c => tags.last_mut().unwrap().push(c),
+        }
+    }
+
+    tags
+}
+
+fn read_err(err: csv::Error) -> ApplicationError {
+    ApplicationError::invalid_argument(&err.to_string())
+}
+
+fn write_err(err: impl std::error::Error) -> ApplicationError {
+    ApplicationError::internal("failed to write CSV export", err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canopus_definitions::{
+        RemarkImportFailure, RemarkImportFailureAttributes, RemarksImportSummaryAttributes,
+    };
+
+    #[test]
+    fn test_write_and_read() {
+        let created_at = "2025-01-01T08:30:00Z".parse().unwrap();
+
+        let remark = Remark::new(RemarkAttributes {
+            id: Uuid::from_u128(42),
+            essence: RemarkEssence::new("Title, \"quoted\"\n\nBody".to_string()).unwrap(),
+            tags: vec![
+                TagTitle::new("a/b".to_string()).unwrap(),
+                TagTitle::new("c".to_string()).unwrap(),
+            ],
+            created_at,
+            updated_at: created_at,
+            deleted_at: None,
+            kind: RemarkKind::Quote {
+                source: Some("Notebook".to_string()),
+                author: None,
+            },
+        });
+
+        let mut output = Vec::new();
+        let mut writer = RemarksCsvWriter::new(&mut output).unwrap();
+        writer.write(std::slice::from_ref(&remark)).unwrap();
+        writer.finish().unwrap();
+
+        let input = format!(
+            "{}{}\n,,bad,,,,\nnot-an-id,Remark,,,,,\n",
+            String::from_utf8(output).unwrap(),
+            ",Minimal,,2025-02-01,,,"
+        );
+
+        let (remarks, errors) = read(input.as_bytes(), Utc::now(), &Utc).unwrap();
+
+        let (again, _) = read(input.as_bytes(), Utc::now(), &Utc).unwrap();
+        let remarks = remarks
+            .into_iter()
+            .map(|row| row.remark)
+            .collect::<Vec<Remark>>();
+
+        assert_eq!(remarks.len(), 2);
+        assert_eq!(remarks[0].id(), remark.id());
+        assert_eq!(remarks[0].essence().as_str(), remark.essence().as_str());
+        assert_eq!(remarks[0].tags(), remark.tags());
+        assert_eq!(remarks[0].created_at(), created_at);
+        assert_eq!(remarks[0].kind(), remark.kind());
+        assert_eq!(remarks[1].essence().as_str(), "Minimal");
+        assert_eq!(remarks[1].id(), again[1].remark.id());
+        assert_eq!(
+            remarks[1].created_at(),
+            "2025-02-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(
+            errors.iter().map(|error| error.line).collect::<Vec<u64>>(),
+            [6, 7]
+        );
+
+        let delimited = Remark::new(RemarkAttributes {
+            id: Uuid::from_u128(43),
+            essence: RemarkEssence::new("Delimited".to_string()).unwrap(),
+            tags: vec![
+                TagTitle::new("a;b".to_string()).unwrap(),
+                TagTitle::new("c\\;".to_string()).unwrap(),
+            ],
+            created_at,
+            updated_at: created_at,
+            deleted_at: None,
+            kind: RemarkKind::Note,
+        });
+
+        let mut output = Vec::new();
+        let mut writer = RemarksCsvWriter::new(&mut output).unwrap();
+        writer.write(std::slice::from_ref(&delimited)).unwrap();
+        writer.finish().unwrap();
+
+        let (remarks, _) = read(output.as_slice(), Utc::now(), &Utc).unwrap();
+
+        assert_eq!(remarks[0].remark.tags(), delimited.tags());
+    }
+
+    #[test]
+    fn test_import_report_maps_failures_to_lines() {
+        let summary = RemarksImportSummary::new(RemarksImportSummaryAttributes {
+            imported: 1,
+            skipped: 0,
+            failures: vec![RemarkImportFailure::new(RemarkImportFailureAttributes {
+                line: 2,
+                message: "remark essence is too long".to_string(),
+            })],
+        });
+
+        let errors = vec![CsvRowError {
+            line: 4,
+            message: "invalid remark id".to_string(),
+        }];
+
+        let report = CsvImportReport::new(Some(summary), &[2, 6], errors);
+
+        assert_eq!(
+            report.to_string(),
+            "line 4: invalid remark id\nline 6: remark essence is too long\n1 imported, 0 skipped, 2 failed"
+        );
+    }
+}
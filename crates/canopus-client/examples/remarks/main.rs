@@ -1,6 +1,9 @@
-use canopus_client::{Client, remarks};
+use canopus_client::{
+    Client,
+    remarks::{self, RemarksPageParameters},
+};
 use canopus_definitions::ApplicationResult;
-use clap::{Parser, Subcommand, command};
+use clap::{Parser, Subcommand};
 use eyre::WrapErr;
 
 #[tokio::main]
@@ -47,7 +50,14 @@ impl App {
 
         match command {
             Commands::List { page_token } => {
-                let page = remarks::index(&client, page_token).await?;
+                let page = remarks::index(
+                    &client,
+                    RemarksPageParameters {
+                        page_token,
+                        ..Default::default()
+                    },
+                )
+                .await?;
 
                 println!("{}", page);
             }
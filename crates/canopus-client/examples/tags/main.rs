@@ -1,6 +1,9 @@
-use canopus_client::{Client, tags};
+use canopus_client::{
+    Client,
+    tags::{self, TagsPageParameters},
+};
 use canopus_definitions::ApplicationResult;
-use clap::{Parser, Subcommand, command};
+use clap::{Parser, Subcommand};
 use eyre::WrapErr;
 use uuid::Uuid;
 
@@ -57,7 +60,14 @@ impl App {
                 println!("{}", tag);
             }
             Commands::List { page_token } => {
-                let page = tags::index(&client, page_token).await?;
+                let page = tags::index(
+                    &client,
+                    TagsPageParameters {
+                        page_token,
+                        ..Default::default()
+                    },
+                )
+                .await?;
 
                 println!("{}", page);
             }
@@ -2,39 +2,236 @@ use crate::{
     Client, from_reqwest_err,
     rest::{self, Path, Resource},
 };
-use canopus_definitions::{ApplicationResult, Page, Remark};
-use serde::Serialize;
+use canopus_definitions::{
+    ApplicationError, ApplicationResult, Page, Remark, RemarkAttachment, RemarkBulkOutcome,
+    RemarkLink, RemarkRevision, RemarkSearchHit, RemarksImportSummary,
+};
+use chrono::{DateTime, Utc};
+use reqwest::multipart::{Form, Part};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
 use uuid::Uuid;
 
+const CREATE_ATTEMPTS: usize = 3;
+
+#[derive(Serialize)]
+pub struct BulkNewRemark {
+    pub essence: String,
+    pub tags: Vec<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub content_hash: Option<String>,
+    pub kind: Option<String>,
+    pub payload: Option<RemarkPayload>,
+}
+
+pub struct NewAttachment {
+    pub file_name: String,
+    pub media_type: Option<String>,
+    pub content: Vec<u8>,
+}
+
 #[derive(Serialize)]
 pub struct NewRemark {
     pub essence: String,
     pub tags: Vec<String>,
+    pub kind: Option<String>,
+    pub payload: Option<RemarkPayload>,
+}
+
+#[derive(Default, Serialize)]
+pub struct RemarkPayload {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub done: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+}
+
+#[derive(Default)]
+pub struct RemarksPageParameters {
+    pub page_token: Option<String>,
+    pub page_size: Option<i64>,
+    pub order_by: Option<String>,
+    pub all_tags: Vec<String>,
+    pub any_tags: Vec<String>,
+    pub exclude_tags: Vec<String>,
+    pub created_after: Option<String>,
+    pub created_before: Option<String>,
+    pub updated_after: Option<String>,
+    pub updated_before: Option<String>,
+    pub kind: Option<String>,
+    pub tz: Option<String>,
+}
+
+#[derive(Serialize)]
+struct BulkRemarks {
+    remarks: Vec<BulkNewRemark>,
+    dry_run: bool,
+}
+
+#[derive(Serialize)]
+struct RemarkTags {
+    tags: Vec<String>,
 }
 
 #[derive(Default, Serialize)]
 pub struct RemarkUpdates {
     pub essence: Option<String>,
     pub tags: Option<Vec<String>>,
+    pub kind: Option<String>,
+    pub payload: Option<RemarkPayload>,
+}
+
+pub async fn add_tags(
+    client: &Client,
+    id: Uuid,
+    tags: Vec<String>,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<Remark> {
+    let Client { inner, base_url } = client;
+
+    rest::create(
+        inner,
+        Resource {
+            base_url,
+            path: Path::RemarkTags(id),
+        },
+        RemarkTags { tags },
+        None,
+        expected_updated_at,
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
+}
+
+pub async fn attach(
+    client: &Client,
+    id: Uuid,
+    new_attachment: NewAttachment,
+) -> ApplicationResult<RemarkAttachment> {
+    let Client { inner, base_url } = client;
+
+    let NewAttachment {
+        file_name,
+        media_type,
+        content,
+    } = new_attachment;
+
+    let part = Part::bytes(content)
+        .file_name(file_name)
+        .mime_str(media_type.as_deref().unwrap_or("application/octet-stream"))
+        .map_err(from_reqwest_err)?;
+
+    rest::upload(
+        inner,
+        Resource {
+            base_url,
+            path: Path::RemarkAttachments(id),
+        },
+        Form::new().part("file", part),
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
+}
+
+pub async fn attachments(client: &Client, id: Uuid) -> ApplicationResult<Page<RemarkAttachment>> {
+    let Client { base_url, inner } = client;
+
+    rest::get(
+        inner,
+        Resource {
+            base_url,
+            path: Path::RemarkAttachments(id),
+        },
+        None,
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
+}
+
+pub async fn backlinks(client: &Client, id: Uuid) -> ApplicationResult<Page<RemarkLink>> {
+    let Client { base_url, inner } = client;
+
+    rest::get(
+        inner,
+        Resource {
+            base_url,
+            path: Path::RemarkBacklinks(id),
+        },
+        None,
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
 }
 
 pub async fn create(client: &Client, new_remark: NewRemark) -> ApplicationResult<Remark> {
     let Client { inner, base_url } = client;
 
+    let idempotency_key = Uuid::new_v4().to_string();
+    let mut attempt = 1;
+
+    loop {
+        let result = rest::create(
+            inner,
+            Resource {
+                base_url,
+                path: Path::Remarks,
+            },
+            &new_remark,
+            Some(&idempotency_key),
+            None,
+        )
+        .await;
+
+        match result {
+            Err(err) if attempt < CREATE_ATTEMPTS && (err.is_timeout() || err.is_connect()) => {
+                attempt += 1;
+            }
+            result => return result.map_err(from_reqwest_err)?.into(),
+        }
+    }
+}
+
+pub async fn create_bulk(
+    client: &Client,
+    remarks: Vec<BulkNewRemark>,
+    dry_run: bool,
+) -> ApplicationResult<Vec<RemarkBulkOutcome>> {
+    let Client { inner, base_url } = client;
+
     rest::create(
         inner,
         Resource {
             base_url,
-            path: Path::Remarks,
+            path: Path::RemarksBulk,
         },
-        new_remark,
+        BulkRemarks { remarks, dry_run },
+        None,
+        None,
     )
     .await
     .map_err(from_reqwest_err)?
     .into()
 }
 
-pub async fn delete(client: &Client, id: Uuid) -> ApplicationResult<Remark> {
+pub async fn delete(
+    client: &Client,
+    id: Uuid,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<Remark> {
     let Client { inner, base_url } = client;
 
     rest::delete(
@@ -43,18 +240,171 @@ pub async fn delete(client: &Client, id: Uuid) -> ApplicationResult<Remark> {
             base_url,
             path: Path::Remark(id),
         },
+        None,
+        expected_updated_at,
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
+}
+
+pub async fn delete_attachment(
+    client: &Client,
+    id: Uuid,
+    attachment_id: Uuid,
+) -> ApplicationResult<RemarkAttachment> {
+    let Client { inner, base_url } = client;
+
+    rest::delete(
+        inner,
+        Resource {
+            base_url,
+            path: Path::RemarkAttachment(id, attachment_id),
+        },
+        None,
+        None,
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
+}
+
+pub async fn download(
+    client: &Client,
+    id: Uuid,
+    attachment_id: Uuid,
+) -> ApplicationResult<Vec<u8>> {
+    let Client { inner, base_url } = client;
+
+    rest::download(
+        inner,
+        Resource {
+            base_url,
+            path: Path::RemarkAttachment(id, attachment_id),
+        },
     )
     .await
     .map_err(from_reqwest_err)?
     .into()
 }
 
-pub async fn index(client: &Client, page_token: Option<String>) -> ApplicationResult<Page<Remark>> {
+pub async fn export(client: &Client, output: &mut impl Write) -> ApplicationResult<()> {
+    let Client { inner, base_url } = client;
+
+    let mut response: reqwest::Response = ApplicationResult::from(
+        rest::open(
+            inner,
+            Resource {
+                base_url,
+                path: Path::Export,
+            },
+        )
+        .await
+        .map_err(from_reqwest_err)?,
+    )?;
+
+    let mut writer = ExportWriter::new(output);
+
+    while let Some(chunk) = response.chunk().await.map_err(from_reqwest_err)? {
+        writer.write(&chunk)?;
+    }
+
+    writer.finish()
+}
+
+pub async fn import(
+    client: &Client,
+    input: impl Into<reqwest::Body>,
+) -> ApplicationResult<RemarksImportSummary> {
+    let Client { inner, base_url } = client;
+
+    rest::stream(
+        inner,
+        Resource {
+            base_url,
+            path: Path::Import,
+        },
+        input,
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
+}
+
+pub async fn import_remarks(
+    client: &Client,
+    remarks: &[Remark],
+) -> ApplicationResult<RemarksImportSummary> {
+    let mut body = Vec::new();
+
+    for remark in remarks {
+        serde_json::to_writer(&mut body, remark)
+            .map_err(|err| ApplicationError::internal("failed to serialize remark", err))?;
+        body.push(b'\n');
+    }
+
+    import(client, body).await
+}
+
+pub async fn index(
+    client: &Client,
+    parameters: RemarksPageParameters,
+) -> ApplicationResult<Page<Remark>> {
     let Client { base_url, inner } = client;
 
-    let query = page_token
-        .as_deref()
-        .map(|token| vec![("page_token", token)]);
+    let RemarksPageParameters {
+        page_token,
+        page_size,
+        order_by,
+        all_tags,
+        any_tags,
+        exclude_tags,
+        created_after,
+        created_before,
+        updated_after,
+        updated_before,
+        kind,
+        tz,
+    } = &parameters;
+
+    let page_size = page_size.map(|size| size.to_string());
+
+    let mut query = Vec::new();
+
+    if let Some(token) = page_token.as_deref() {
+        query.push(("page_token", token));
+    }
+
+    if let Some(size) = page_size.as_deref() {
+        query.push(("page_size", size));
+    }
+
+    if let Some(order_by) = order_by.as_deref() {
+        query.push(("order_by", order_by));
+    }
+
+    query.extend(all_tags.iter().map(|tag| ("all_tags", tag.as_str())));
+    query.extend(any_tags.iter().map(|tag| ("any_tags", tag.as_str())));
+    query.extend(
+        exclude_tags
+            .iter()
+            .map(|tag| ("exclude_tags", tag.as_str())),
+    );
+
+    let filters = [
+        ("created_after", created_after),
+        ("created_before", created_before),
+        ("updated_after", updated_after),
+        ("updated_before", updated_before),
+        ("kind", kind),
+        ("tz", tz),
+    ];
+
+    query.extend(
+        filters
+            .iter()
+            .filter_map(|(name, value)| value.as_deref().map(|value| (*name, value))),
+    );
 
     rest::get(
         inner,
@@ -62,7 +412,183 @@ pub async fn index(client: &Client, page_token: Option<String>) -> ApplicationRe
             base_url,
             path: Path::Remarks,
         },
-        query.as_deref(),
+        Some(&query),
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
+}
+
+pub async fn links(client: &Client, id: Uuid) -> ApplicationResult<Page<RemarkLink>> {
+    let Client { base_url, inner } = client;
+
+    rest::get(
+        inner,
+        Resource {
+            base_url,
+            path: Path::RemarkLinks(id),
+        },
+        None,
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
+}
+
+pub async fn purge(
+    client: &Client,
+    id: Uuid,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<Remark> {
+    let Client { inner, base_url } = client;
+
+    rest::delete(
+        inner,
+        Resource {
+            base_url,
+            path: Path::Remark(id),
+        },
+        Some(&[("permanent", "true")]),
+        expected_updated_at,
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
+}
+
+pub async fn remove_tag(
+    client: &Client,
+    id: Uuid,
+    title: &str,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<Remark> {
+    let Client { inner, base_url } = client;
+
+    rest::delete(
+        inner,
+        Resource {
+            base_url,
+            path: Path::RemarkTag(id, title.to_string()),
+        },
+        None,
+        expected_updated_at,
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
+}
+
+pub async fn remove_tags(
+    client: &Client,
+    id: Uuid,
+    titles: &[String],
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<Remark> {
+    let Client { inner, base_url } = client;
+
+    let query: Vec<(&str, &str)> = titles
+        .iter()
+        .map(|title| ("title", title.as_str()))
+        .collect();
+
+    rest::delete(
+        inner,
+        Resource {
+            base_url,
+            path: Path::RemarkTags(id),
+        },
+        Some(&query),
+        expected_updated_at,
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
+}
+
+pub async fn restore(
+    client: &Client,
+    id: Uuid,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<Remark> {
+    let Client { base_url, inner } = client;
+
+    rest::post(
+        inner,
+        Resource {
+            base_url,
+            path: Path::RemarkRestore(id),
+        },
+        expected_updated_at,
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
+}
+
+pub async fn restore_revision(
+    client: &Client,
+    id: Uuid,
+    number: i32,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<Remark> {
+    let Client { base_url, inner } = client;
+
+    rest::post(
+        inner,
+        Resource {
+            base_url,
+            path: Path::RemarkRevisionRestore(id, number),
+        },
+        expected_updated_at,
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
+}
+
+pub async fn revisions(client: &Client, id: Uuid) -> ApplicationResult<Page<RemarkRevision>> {
+    let Client { base_url, inner } = client;
+
+    rest::get(
+        inner,
+        Resource {
+            base_url,
+            path: Path::RemarkRevisions(id),
+        },
+        None,
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
+}
+
+pub async fn search(
+    client: &Client,
+    query: &str,
+    page_token: Option<String>,
+    page_size: Option<i64>,
+) -> ApplicationResult<Page<RemarkSearchHit>> {
+    let Client { base_url, inner } = client;
+
+    let page_size = page_size.map(|size| size.to_string());
+
+    let mut query = vec![("q", query)];
+
+    if let Some(token) = page_token.as_deref() {
+        query.push(("page_token", token));
+    }
+
+    if let Some(size) = page_size.as_deref() {
+        query.push(("page_size", size));
+    }
+
+    rest::get(
+        inner,
+        Resource {
+            base_url,
+            path: Path::RemarksSearch,
+        },
+        Some(&query),
     )
     .await
     .map_err(from_reqwest_err)?
@@ -85,10 +611,64 @@ pub async fn show(client: &Client, id: Uuid) -> ApplicationResult<Remark> {
     .into()
 }
 
+pub async fn toggle_item(
+    client: &Client,
+    id: Uuid,
+    number: usize,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<Remark> {
+    let Client { base_url, inner } = client;
+
+    rest::post(
+        inner,
+        Resource {
+            base_url,
+            path: Path::RemarkItemToggle(id, number),
+        },
+        expected_updated_at,
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
+}
+
+pub async fn trash(
+    client: &Client,
+    page_token: Option<String>,
+    page_size: Option<i64>,
+) -> ApplicationResult<Page<Remark>> {
+    let Client { base_url, inner } = client;
+
+    let page_size = page_size.map(|size| size.to_string());
+
+    let mut query = Vec::new();
+
+    if let Some(token) = page_token.as_deref() {
+        query.push(("page_token", token));
+    }
+
+    if let Some(size) = page_size.as_deref() {
+        query.push(("page_size", size));
+    }
+
+    rest::get(
+        inner,
+        Resource {
+            base_url,
+            path: Path::RemarksTrash,
+        },
+        Some(&query),
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
+}
+
 pub async fn update(
     client: &Client,
     id: Uuid,
     updates: RemarkUpdates,
+    expected_updated_at: Option<DateTime<Utc>>,
 ) -> ApplicationResult<Remark> {
     let Client { base_url, inner } = client;
 
@@ -99,8 +679,115 @@ pub async fn update(
             path: Path::Remark(id),
         },
         updates,
+        expected_updated_at,
     )
     .await
     .map_err(from_reqwest_err)?
     .into()
 }
+
+#[derive(Deserialize)]
+struct ExportEnd {
+    end: bool,
+    count: usize,
+}
+
+struct ExportWriter<'a, W> {
+    output: &'a mut W,
+    pending: Vec<u8>,
+    last_line: Option<Vec<u8>>,
+    count: usize,
+}
+
+impl<'a, W: Write> ExportWriter<'a, W> {
+    fn new(output: &'a mut W) -> Self {
+        Self {
+            output,
+            pending: Vec::new(),
+            last_line: None,
+            count: 0,
+        }
+    }
+
+    // The last line is held back until another one arrives, it is the end line of a complete export
+    fn write(&mut self, chunk: &[u8]) -> ApplicationResult<()> {
+        self.pending.extend_from_slice(chunk);
+
+        while let Some(position) = self.pending.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=position).collect();
+
+            if let Some(previous) = self.last_line.replace(line) {
+                self.output.write_all(&previous).map_err(write_export_err)?;
+                self.count += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> ApplicationResult<()> {
+        let end = self
+            .last_line
+            .filter(|_| self.pending.is_empty())
+            .and_then(|line| serde_json::from_slice::<ExportEnd>(&line).ok());
+
+        match end {
+            Some(ExportEnd { end: true, count }) if count == self.count => {
+                self.output.flush().map_err(write_export_err)
+            }
+            _ => Err(ApplicationError::msg(
+                "export ended before all remarks were received, the output is incomplete",
+            )),
+        }
+    }
+}
+
+fn write_export_err(err: std::io::Error) -> ApplicationError {
+    ApplicationError::internal("failed to write export", err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn export(chunks: &[&str]) -> (ApplicationResult<()>, String) {
+        let mut output = Vec::new();
+        let mut writer = ExportWriter::new(&mut output);
+
+        let result = chunks
+            .iter()
+            .try_for_each(|chunk| writer.write(chunk.as_bytes()))
+            .and_then(|()| writer.finish());
+
+        (result, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_export_writer() {
+        let (result, output) = export(&[
+            "{\"id\":1}\n{\"i",
+            "d\":2}\n{\"end\":true,",
+            "\"count\":2}\n",
+        ]);
+
+        assert!(result.is_ok());
+        assert_eq!(output, "{\"id\":1}\n{\"id\":2}\n");
+
+        let (result, output) = export(&["{\"id\":1}\n{\"id\":2}\n"]);
+
+        assert!(result.is_err());
+        assert_eq!(output, "{\"id\":1}\n");
+
+        assert!(
+            export(&["{\"id\":1}\n{\"end\":true,\"count\":2}\n"])
+                .0
+                .is_err()
+        );
+        assert!(
+            export(&["{\"id\":1}\n{\"end\":true,\"count\":1}"])
+                .0
+                .is_err()
+        );
+        assert!(export(&[]).0.is_err());
+    }
+}
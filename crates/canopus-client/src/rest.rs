@@ -1,7 +1,11 @@
 use core::panic;
 
 use canopus_definitions::{ApplicationError, ApplicationResult};
-use reqwest::Url;
+use chrono::{DateTime, SecondsFormat, Utc};
+use reqwest::{
+    Url,
+    header::{CONTENT_TYPE, IF_MATCH},
+};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -18,43 +22,79 @@ pub struct Resource<'a> {
 }
 
 pub enum Path {
+    Export,
+    Import,
     Remarks,
     Remark(Uuid),
+    RemarkAttachment(Uuid, Uuid),
+    RemarkAttachments(Uuid),
+    RemarkBacklinks(Uuid),
+    RemarkItemToggle(Uuid, usize),
+    RemarkLinks(Uuid),
+    RemarkRestore(Uuid),
+    RemarkRevisions(Uuid),
+    RemarkRevisionRestore(Uuid, i32),
+    RemarkTag(Uuid, String),
+    RemarkTags(Uuid),
+    RemarksBulk,
+    RemarksSearch,
+    RemarksTrash,
     Tag(Uuid),
+    TagChildren(Uuid),
+    TagMerge(Uuid),
     Tags,
+    TagsSuggest,
 }
 
 pub async fn create<T, D>(
     client: &reqwest::Client,
     url: impl Into<Url>,
     data: D,
+    idempotency_key: Option<&str>,
+    expected_updated_at: Option<DateTime<Utc>>,
 ) -> reqwest::Result<ApiResponse<T>>
 where
     T: for<'de> Deserialize<'de>,
     D: Serialize,
 {
-    client
-        .post(url.into())
-        .json(&data)
-        .send()
-        .await?
-        .json::<ApiResponse<T>>()
-        .await
+    let mut request = if_match(client.post(url.into()).json(&data), expected_updated_at);
+
+    if let Some(key) = idempotency_key {
+        request = request.header("Idempotency-Key", key);
+    }
+
+    request.send().await?.json::<ApiResponse<T>>().await
 }
 
 pub async fn delete<T>(
     client: &reqwest::Client,
     url: impl Into<Url>,
+    query: Option<&[(&str, &str)]>,
+    expected_updated_at: Option<DateTime<Utc>>,
 ) -> reqwest::Result<ApiResponse<T>>
 where
     T: for<'de> Deserialize<'de>,
 {
-    client
-        .delete(url.into())
-        .send()
-        .await?
-        .json::<ApiResponse<T>>()
-        .await
+    let mut request = if_match(client.delete(url.into()), expected_updated_at);
+
+    if let Some(query) = query {
+        request = request.query(query);
+    }
+
+    request.send().await?.json::<ApiResponse<T>>().await
+}
+
+pub async fn download(
+    client: &reqwest::Client,
+    url: impl Into<Url>,
+) -> reqwest::Result<ApiResponse<Vec<u8>>> {
+    let response = client.get(url.into()).send().await?;
+
+    if !response.status().is_success() {
+        return response.json::<ApiResponse<Vec<u8>>>().await;
+    }
+
+    Ok(ApiResponse::Ok(response.bytes().await?.to_vec()))
 }
 
 pub async fn get<T>(
@@ -74,24 +114,115 @@ where
     request.send().await?.json::<ApiResponse<T>>().await
 }
 
+pub async fn open(
+    client: &reqwest::Client,
+    url: impl Into<Url>,
+) -> reqwest::Result<ApiResponse<reqwest::Response>> {
+    let response = client.get(url.into()).send().await?;
+
+    if !response.status().is_success() {
+        return Ok(ApiResponse::Err(response.json::<ApplicationError>().await?));
+    }
+
+    Ok(ApiResponse::Ok(response))
+}
+
 pub async fn patch<T, D>(
     client: &reqwest::Client,
     url: impl Into<Url>,
     data: D,
+    expected_updated_at: Option<DateTime<Utc>>,
 ) -> reqwest::Result<ApiResponse<T>>
 where
     T: for<'de> Deserialize<'de>,
     D: Serialize,
+{
+    if_match(client.patch(url.into()).json(&data), expected_updated_at)
+        .send()
+        .await?
+        .json::<ApiResponse<T>>()
+        .await
+}
+
+pub async fn post<T>(
+    client: &reqwest::Client,
+    url: impl Into<Url>,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> reqwest::Result<ApiResponse<T>>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    if_match(client.post(url.into()), expected_updated_at)
+        .send()
+        .await?
+        .json::<ApiResponse<T>>()
+        .await
+}
+
+pub async fn stream<T>(
+    client: &reqwest::Client,
+    url: impl Into<Url>,
+    body: impl Into<reqwest::Body>,
+) -> reqwest::Result<ApiResponse<T>>
+where
+    T: for<'de> Deserialize<'de>,
 {
     client
-        .patch(url.into())
-        .json(&data)
+        .post(url.into())
+        .header(CONTENT_TYPE, "application/x-ndjson")
+        .body(body)
         .send()
         .await?
         .json::<ApiResponse<T>>()
         .await
 }
 
+pub async fn upload<T>(
+    client: &reqwest::Client,
+    url: impl Into<Url>,
+    form: reqwest::multipart::Form,
+) -> reqwest::Result<ApiResponse<T>>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    client
+        .post(url.into())
+        .multipart(form)
+        .send()
+        .await?
+        .json::<ApiResponse<T>>()
+        .await
+}
+
+fn if_match(
+    request: reqwest::RequestBuilder,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> reqwest::RequestBuilder {
+    let Some(updated_at) = expected_updated_at else {
+        return request;
+    };
+
+    request.header(
+        IF_MATCH,
+        format!(
+            "\"{}\"",
+            updated_at.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+        ),
+    )
+}
+
+fn encode_path_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                char::from(byte).to_string()
+            }
+            byte => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
 impl From<Resource<'_>> for Url {
     fn from(value: Resource) -> Url {
         let Resource { base_url, path } = value;
@@ -108,10 +239,38 @@ impl From<Resource<'_>> for Url {
 impl std::fmt::Display for Path {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Path::Export => f.write_str("/export"),
+            Path::Import => f.write_str("/import"),
             Path::Remarks => f.write_str("/remarks"),
             Path::Remark(id) => write!(f, "{}/{}", Path::Remarks, id),
+            Path::RemarkAttachment(id, attachment_id) => {
+                write!(f, "{}/{}", Path::RemarkAttachments(*id), attachment_id)
+            }
+            Path::RemarkAttachments(id) => write!(f, "{}/attachments", Path::Remark(*id)),
+            Path::RemarkBacklinks(id) => write!(f, "{}/backlinks", Path::Remark(*id)),
+            Path::RemarkItemToggle(id, number) => {
+                write!(f, "{}/items/{}/toggle", Path::Remark(*id), number)
+            }
+            Path::RemarkLinks(id) => write!(f, "{}/links", Path::Remark(*id)),
+            Path::RemarkRestore(id) => write!(f, "{}/restore", Path::Remark(*id)),
+            Path::RemarkRevisions(id) => write!(f, "{}/revisions", Path::Remark(*id)),
+            Path::RemarkRevisionRestore(id, number) => {
+                write!(f, "{}/{}/restore", Path::RemarkRevisions(*id), number)
+            }
+            Path::RemarkTag(id, title) => {
+                let segments: Vec<String> = title.split('/').map(encode_path_segment).collect();
+
+                write!(f, "{}/{}", Path::RemarkTags(*id), segments.join("/"))
+            }
+            Path::RemarkTags(id) => write!(f, "{}/tags", Path::Remark(*id)),
+            Path::RemarksBulk => write!(f, "{}/bulk", Path::Remarks),
+            Path::RemarksSearch => write!(f, "{}/search", Path::Remarks),
+            Path::RemarksTrash => write!(f, "{}/trash", Path::Remarks),
             Path::Tags => f.write_str("/tags"),
             Path::Tag(id) => write!(f, "{}/{}", Path::Tags, id),
+            Path::TagChildren(id) => write!(f, "{}/children", Path::Tag(*id)),
+            Path::TagMerge(id) => write!(f, "{}/merge", Path::Tag(*id)),
+            Path::TagsSuggest => write!(f, "{}/suggest", Path::Tags),
         }
     }
 }
@@ -3,15 +3,71 @@ use crate::{
     rest::{self, Path, Resource},
 };
 use canopus_definitions::{ApplicationResult, Page, Tag};
+use chrono::{DateTime, Utc};
 use serde::Serialize;
 use uuid::Uuid;
 
-pub async fn index(client: &Client, page_token: Option<String>) -> ApplicationResult<Page<Tag>> {
+#[derive(Default, Serialize)]
+pub struct TagUpdates {
+    pub title: String,
+}
+
+#[derive(Default)]
+pub struct TagsPageParameters {
+    pub page_token: Option<String>,
+    pub page_size: Option<i64>,
+    pub order_by: Option<String>,
+    pub with_usage_count: bool,
+}
+
+pub async fn children(client: &Client, id: Uuid) -> ApplicationResult<Page<Tag>> {
     let Client { base_url, inner } = client;
 
-    let query = page_token
-        .as_deref()
-        .map(|token| vec![("page_token", token)]);
+    rest::get(
+        inner,
+        Resource {
+            base_url,
+            path: Path::TagChildren(id),
+        },
+        None,
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
+}
+
+pub async fn index(
+    client: &Client,
+    parameters: TagsPageParameters,
+) -> ApplicationResult<Page<Tag>> {
+    let Client { base_url, inner } = client;
+
+    let TagsPageParameters {
+        page_token,
+        page_size,
+        order_by,
+        with_usage_count,
+    } = &parameters;
+
+    let page_size = page_size.map(|size| size.to_string());
+
+    let mut query = Vec::new();
+
+    if let Some(token) = page_token.as_deref() {
+        query.push(("page_token", token));
+    }
+
+    if let Some(size) = page_size.as_deref() {
+        query.push(("page_size", size));
+    }
+
+    if let Some(order_by) = order_by.as_deref() {
+        query.push(("order_by", order_by));
+    }
+
+    if *with_usage_count {
+        query.push(("with_usage_count", "true"));
+    }
 
     rest::get(
         inner,
@@ -19,7 +75,25 @@ pub async fn index(client: &Client, page_token: Option<String>) -> ApplicationRe
             base_url,
             path: Path::Tags,
         },
-        query.as_deref(),
+        Some(&query),
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
+}
+
+pub async fn merge(client: &Client, id: Uuid, target_id: Uuid) -> ApplicationResult<Tag> {
+    let Client { inner, base_url } = client;
+
+    rest::create(
+        inner,
+        Resource {
+            base_url,
+            path: Path::TagMerge(id),
+        },
+        TagsMerge { target_id },
+        None,
+        None,
     )
     .await
     .map_err(from_reqwest_err)?
@@ -42,7 +116,40 @@ pub async fn show(client: &Client, id: Uuid) -> ApplicationResult<Tag> {
     .into()
 }
 
-pub async fn update(client: &Client, id: Uuid, title: String) -> ApplicationResult<Tag> {
+pub async fn suggest(
+    client: &Client,
+    prefix: &str,
+    page_size: Option<i64>,
+) -> ApplicationResult<Page<Tag>> {
+    let Client { base_url, inner } = client;
+
+    let page_size = page_size.map(|size| size.to_string());
+
+    let mut query = vec![("prefix", prefix)];
+
+    if let Some(size) = page_size.as_deref() {
+        query.push(("page_size", size));
+    }
+
+    rest::get(
+        inner,
+        Resource {
+            base_url,
+            path: Path::TagsSuggest,
+        },
+        Some(&query),
+    )
+    .await
+    .map_err(from_reqwest_err)?
+    .into()
+}
+
+pub async fn update(
+    client: &Client,
+    id: Uuid,
+    updates: TagUpdates,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<Tag> {
     let Client { inner, base_url } = client;
 
     rest::patch(
@@ -51,7 +158,8 @@ pub async fn update(client: &Client, id: Uuid, title: String) -> ApplicationResu
             base_url,
             path: Path::Tag(id),
         },
-        TagChanges { title },
+        updates,
+        expected_updated_at,
     )
     .await
     .map_err(from_reqwest_err)?
@@ -59,6 +167,6 @@ pub async fn update(client: &Client, id: Uuid, title: String) -> ApplicationResu
 }
 
 #[derive(Serialize)]
-struct TagChanges {
-    title: String,
+struct TagsMerge {
+    target_id: Uuid,
 }
@@ -0,0 +1,119 @@
+use crate::{ApplicationError, ApplicationResult};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+pub fn parse_datetime<Tz: TimeZone>(
+    input: &str,
+    now: DateTime<Utc>,
+    time_zone: &Tz,
+) -> ApplicationResult<DateTime<Utc>> {
+    let input = input.trim();
+
+    if let Some(relative) = input.strip_prefix('-') {
+        return parse_relative(relative).map(|duration| now - duration);
+    }
+
+    parse_absolute_datetime(input, time_zone).map_err(|_| invalid_datetime())
+}
+
+pub fn parse_absolute_datetime<Tz: TimeZone>(
+    input: &str,
+    time_zone: &Tz,
+) -> ApplicationResult<DateTime<Utc>> {
+    let input = input.trim();
+
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(input) {
+        return Ok(datetime.with_timezone(&Utc));
+    }
+
+    let naive = NaiveDateTime::parse_from_str(input, "%Y-%m-%dT%H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M"))
+        .or_else(|_| {
+            NaiveDate::parse_from_str(input, "%Y-%m-%d")
+                .map(|date| date.and_hms_opt(0, 0, 0).unwrap_or_default())
+        })
+        .map_err(|_| invalid_absolute_datetime())?;
+
+    time_zone
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|datetime| datetime.with_timezone(&Utc))
+        .ok_or_else(invalid_absolute_datetime)
+}
+
+fn parse_relative(relative: &str) -> ApplicationResult<Duration> {
+    let unit_index = relative
+        .char_indices()
+        .last()
+        .map(|(index, _)| index)
+        .ok_or_else(invalid_datetime)?;
+
+    let (amount, unit) = relative.split_at(unit_index);
+
+    if amount.is_empty() || !amount.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(invalid_datetime());
+    }
+
+    let amount: i64 = amount.parse().map_err(|_| invalid_datetime())?;
+
+    let duration = match unit {
+        "m" => Duration::try_minutes(amount),
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        _ => None,
+    };
+
+    duration.ok_or_else(invalid_datetime)
+}
+
+fn invalid_absolute_datetime() -> ApplicationError {
+    ApplicationError::invalid_argument("date must be RFC 3339, YYYY-MM-DD or YYYY-MM-DD HH:MM")
+}
+
+fn invalid_datetime() -> ApplicationError {
+    ApplicationError::invalid_argument(
+        "date must be RFC 3339, YYYY-MM-DD, YYYY-MM-DD HH:MM or relative like -7d, -12h, -2w",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    fn parse(input: &str, offset: &str) -> ApplicationResult<String> {
+        let now = DateTime::parse_from_rfc3339("2025-03-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let offset: FixedOffset = offset.parse().unwrap();
+
+        parse_datetime(input, now, &offset).map(|datetime| datetime.to_rfc3339())
+    }
+
+    #[test]
+    fn test_parse_datetime() {
+        assert_eq!(
+            parse("2025-03-01", "+02:00").unwrap(),
+            "2025-02-28T22:00:00+00:00"
+        );
+        assert_eq!(
+            parse("2025-03-01 08:30", "-05:00").unwrap(),
+            "2025-03-01T13:30:00+00:00"
+        );
+        assert_eq!(
+            parse("2025-03-01T08:30:00+01:00", "-05:00").unwrap(),
+            "2025-03-01T07:30:00+00:00"
+        );
+        assert_eq!(parse("-7d", "+02:00").unwrap(), "2025-03-03T12:00:00+00:00");
+        assert_eq!(
+            parse("-12h", "+00:00").unwrap(),
+            "2025-03-10T00:00:00+00:00"
+        );
+        assert!(parse("-7x", "+00:00").is_err());
+        assert!(parse("--7d", "+00:00").is_err());
+        assert!(parse("-+7d", "+00:00").is_err());
+        assert!(parse("last week", "+00:00").is_err());
+        assert!(parse_absolute_datetime("-7d", &Utc).is_err());
+    }
+}
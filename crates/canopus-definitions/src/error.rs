@@ -3,6 +3,9 @@ use serde::{Deserialize, Serialize};
 #[derive(thiserror::Error, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ApplicationError {
+    #[error("The entity was modified since it was last read, reload it and try again")]
+    Conflict,
+
     #[error("{description}")]
     Internal {
         description: String,
@@ -1,11 +1,19 @@
+mod datetime;
 mod error;
 mod page;
 mod remarks;
 mod tags;
 
+pub use datetime::{parse_absolute_datetime, parse_datetime};
 pub use error::ApplicationError;
-pub use page::{Page, PageToken};
-pub use remarks::{Remark, RemarkAttributes, RemarkEssence};
+pub use page::{Page, PageSize, PageToken};
+pub use remarks::{
+    Remark, RemarkAttachment, RemarkAttachmentAttributes, RemarkAttributes, RemarkBulkOutcome,
+    RemarkBulkOutcomeAttributes, RemarkChecklistItem, RemarkChecklistItemAttributes, RemarkEssence,
+    RemarkImportFailure, RemarkImportFailureAttributes, RemarkKind, RemarkKindName, RemarkLink,
+    RemarkLinkAttributes, RemarkRevision, RemarkRevisionAttributes, RemarkSearchHit,
+    RemarkSearchHitAttributes, RemarksImportSummary, RemarksImportSummaryAttributes,
+};
 pub use tags::{Tag, TagAttributes, TagTitle};
 
 pub type ApplicationResult<T> = std::result::Result<T, ApplicationError>;
@@ -1,5 +1,7 @@
+mod page_size;
 mod page_token;
 
+pub use page_size::PageSize;
 pub use page_token::PageToken;
 
 use serde::{Deserialize, Serialize};
@@ -13,6 +15,9 @@ where
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next_page_token: Option<PageToken>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_size: Option<i64>,
 }
 
 impl<T> std::fmt::Display for Page<T>
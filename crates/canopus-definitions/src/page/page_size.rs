@@ -0,0 +1,50 @@
+use crate::{ApplicationError, ApplicationResult};
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct PageSize(i64);
+
+impl PageSize {
+    pub const DEFAULT: i64 = 20;
+    pub const MAX: i64 = 100;
+    pub const MIN: i64 = 1;
+
+    pub fn new(size: i64) -> ApplicationResult<Self> {
+        if !(Self::MIN..=Self::MAX).contains(&size) {
+            return Err(ApplicationError::InvalidArgument(format!(
+                "page size must be between {} and {}",
+                Self::MIN,
+                Self::MAX
+            )));
+        }
+
+        Ok(Self(size))
+    }
+}
+
+impl Default for PageSize {
+    fn default() -> Self {
+        Self(Self::DEFAULT)
+    }
+}
+
+impl std::ops::Deref for PageSize {
+    type Target = i64;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(PageSize::new(0).is_err());
+        assert!(PageSize::new(PageSize::MAX + 1).is_err());
+        assert_eq!(*PageSize::new(PageSize::MAX).unwrap(), PageSize::MAX);
+        assert_eq!(*PageSize::default(), PageSize::DEFAULT);
+    }
+}
@@ -1,6 +1,25 @@
+mod remark_attachment;
+mod remark_bulk_outcome;
+mod remark_checklist_item;
 mod remark_essence;
-
+mod remark_kind;
+mod remark_link;
+mod remark_revision;
+mod remark_search_hit;
+mod remarks_import_summary;
+
+pub use remark_attachment::{RemarkAttachment, RemarkAttachmentAttributes};
+pub use remark_bulk_outcome::{RemarkBulkOutcome, RemarkBulkOutcomeAttributes};
+pub use remark_checklist_item::{RemarkChecklistItem, RemarkChecklistItemAttributes};
 pub use remark_essence::RemarkEssence;
+pub use remark_kind::{RemarkKind, RemarkKindName};
+pub use remark_link::{RemarkLink, RemarkLinkAttributes};
+pub use remark_revision::{RemarkRevision, RemarkRevisionAttributes};
+pub use remark_search_hit::{RemarkSearchHit, RemarkSearchHitAttributes};
+pub use remarks_import_summary::{
+    RemarkImportFailure, RemarkImportFailureAttributes, RemarksImportSummary,
+    RemarksImportSummaryAttributes,
+};
 
 use crate::{ApplicationError, ApplicationResult, TagTitle};
 use chrono::{DateTime, Utc};
@@ -15,6 +34,15 @@ pub struct Remark {
     tags: BTreeSet<TagTitle>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deleted_at: Option<DateTime<Utc>>,
+
+    #[serde(flatten)]
+    kind: RemarkKind,
+
+    #[serde(default)]
+    items: Vec<RemarkChecklistItem>,
 }
 
 pub struct RemarkAttributes {
@@ -23,6 +51,8 @@ pub struct RemarkAttributes {
     pub tags: Vec<TagTitle>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub kind: RemarkKind,
 }
 
 impl Remark {
@@ -30,6 +60,10 @@ impl Remark {
         self.created_at
     }
 
+    pub fn deleted_at(&self) -> Option<DateTime<Utc>> {
+        self.deleted_at
+    }
+
     pub fn essence(&self) -> &RemarkEssence {
         &self.essence
     }
@@ -38,6 +72,14 @@ impl Remark {
         self.id
     }
 
+    pub fn items(&self) -> &[RemarkChecklistItem] {
+        &self.items
+    }
+
+    pub fn kind(&self) -> &RemarkKind {
+        &self.kind
+    }
+
     pub fn new(attributes: RemarkAttributes) -> Self {
         let RemarkAttributes {
             id,
@@ -45,21 +87,39 @@ impl Remark {
             tags,
             created_at,
             updated_at,
+            deleted_at,
+            kind,
         } = attributes;
 
         Remark {
             id,
+            items: essence.checklist_items(),
             essence,
             tags: BTreeSet::from_iter(tags),
             created_at,
             updated_at,
+            deleted_at,
+            kind,
         }
     }
 
+    pub fn is_trashed(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    pub fn set_deleted_at(&mut self, deleted_at: Option<DateTime<Utc>>) {
+        self.deleted_at = deleted_at;
+    }
+
     pub fn set_essence(&mut self, essence: RemarkEssence) {
+        self.items = essence.checklist_items();
         self.essence = essence;
     }
 
+    pub fn set_kind(&mut self, kind: RemarkKind) {
+        self.kind = kind;
+    }
+
     pub fn set_tags(&mut self, tags: Vec<TagTitle>) {
         self.tags = BTreeSet::from_iter(tags);
     }
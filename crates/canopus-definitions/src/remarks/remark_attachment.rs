@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RemarkAttachment {
+    id: Uuid,
+    remark_id: Uuid,
+    file_name: String,
+    media_type: String,
+    size: i64,
+    checksum: String,
+    created_at: DateTime<Utc>,
+}
+
+pub struct RemarkAttachmentAttributes {
+    pub id: Uuid,
+    pub remark_id: Uuid,
+    pub file_name: String,
+    pub media_type: String,
+    pub size: i64,
+    pub checksum: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RemarkAttachment {
+    pub fn checksum(&self) -> &str {
+        &self.checksum
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn media_type(&self) -> &str {
+        &self.media_type
+    }
+
+    pub fn new(attributes: RemarkAttachmentAttributes) -> Self {
+        let RemarkAttachmentAttributes {
+            id,
+            remark_id,
+            file_name,
+            media_type,
+            size,
+            checksum,
+            created_at,
+        } = attributes;
+
+        Self {
+            id,
+            remark_id,
+            file_name,
+            media_type,
+            size,
+            checksum,
+            created_at,
+        }
+    }
+
+    pub fn remark_id(&self) -> Uuid {
+        self.remark_id
+    }
+
+    pub fn size(&self) -> i64 {
+        self.size
+    }
+}
+
+impl std::fmt::Display for RemarkAttachment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string_pretty(&self).map_err(|_| std::fmt::Error)?;
+
+        f.write_str(&json)
+    }
+}
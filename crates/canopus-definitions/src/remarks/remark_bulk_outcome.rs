@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RemarkBulkOutcome {
+    remark_id: Option<Uuid>,
+    skipped: bool,
+}
+
+pub struct RemarkBulkOutcomeAttributes {
+    pub remark_id: Option<Uuid>,
+    pub skipped: bool,
+}
+
+impl RemarkBulkOutcome {
+    pub fn is_skipped(&self) -> bool {
+        self.skipped
+    }
+
+    pub fn new(attributes: RemarkBulkOutcomeAttributes) -> Self {
+        let RemarkBulkOutcomeAttributes { remark_id, skipped } = attributes;
+
+        Self { remark_id, skipped }
+    }
+
+    pub fn remark_id(&self) -> Option<Uuid> {
+        self.remark_id
+    }
+}
+
+impl std::fmt::Display for RemarkBulkOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string_pretty(&self).map_err(|_| std::fmt::Error)?;
+
+        f.write_str(&json)
+    }
+}
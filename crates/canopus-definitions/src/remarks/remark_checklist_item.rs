@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct RemarkChecklistItem {
+    number: usize,
+    text: String,
+    checked: bool,
+}
+
+pub struct RemarkChecklistItemAttributes {
+    pub number: usize,
+    pub text: String,
+    pub checked: bool,
+}
+
+impl RemarkChecklistItem {
+    pub fn is_checked(&self) -> bool {
+        self.checked
+    }
+
+    pub fn new(attributes: RemarkChecklistItemAttributes) -> Self {
+        let RemarkChecklistItemAttributes {
+            number,
+            text,
+            checked,
+        } = attributes;
+
+        Self {
+            number,
+            text,
+            checked,
+        }
+    }
+
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
@@ -1,5 +1,9 @@
-use crate::{ApplicationError, ApplicationResult};
+use crate::{
+    ApplicationError, ApplicationResult, RemarkChecklistItem, RemarkChecklistItemAttributes,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use uuid::Uuid;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct RemarkEssence(String);
@@ -14,6 +18,132 @@ impl RemarkEssence {
 
         Ok(Self(essence.trim().to_string()))
     }
+
+    pub fn checklist_items(&self) -> Vec<RemarkChecklistItem> {
+        checklist_markers(&self.0)
+            .into_iter()
+            .enumerate()
+            .map(|(index, marker)| {
+                RemarkChecklistItem::new(RemarkChecklistItemAttributes {
+                    number: index + 1,
+                    text: marker.text.to_string(),
+                    checked: marker.checked,
+                })
+            })
+            .collect()
+    }
+
+    pub fn toggle_checklist_item(&self, number: usize) -> ApplicationResult<Self> {
+        let marker = checklist_markers(&self.0)
+            .into_iter()
+            .nth(number.saturating_sub(1))
+            .filter(|_| number > 0)
+            .ok_or(ApplicationError::NotFound)?;
+
+        let mut essence = self.0.clone();
+        let mark = if marker.checked { " " } else { "x" };
+
+        essence.replace_range(marker.offset..marker.offset + 1, mark);
+
+        Ok(Self(essence))
+    }
+
+    pub fn linked_remark_ids(&self) -> BTreeSet<Uuid> {
+        let mut ids = BTreeSet::new();
+        let mut rest = self.0.as_str();
+
+        while let Some(start) = rest.find("[[") {
+            rest = &rest[start + 2..];
+
+            let Some(end) = rest.find("]]") else {
+                break;
+            };
+
+            if let Ok(id) = rest[..end].trim_start_matches('[').trim().parse() {
+                ids.insert(id);
+                rest = &rest[end + 2..];
+            }
+        }
+
+        ids
+    }
+}
+
+struct ChecklistMarker<'a> {
+    offset: usize,
+    checked: bool,
+    text: &'a str,
+}
+
+fn checklist_markers(essence: &str) -> Vec<ChecklistMarker<'_>> {
+    let mut markers = Vec::new();
+    let mut line_start = 0;
+    let mut fence: Option<&str> = None;
+
+    for line in essence.split_inclusive('\n') {
+        let start = line_start;
+        line_start += line.len();
+
+        let trimmed = line.trim_start();
+
+        if let Some(delimiter) = ["```", "~~~"]
+            .into_iter()
+            .find(|delimiter| trimmed.starts_with(delimiter))
+        {
+            match fence {
+                Some(open) if open == delimiter => fence = None,
+                None => fence = Some(delimiter),
+                _ => {}
+            }
+
+            continue;
+        }
+
+        if fence.is_some() {
+            continue;
+        }
+
+        let Some(item) = list_item_content(trimmed) else {
+            continue;
+        };
+
+        let checked = match item.as_bytes() {
+            [b'[', b' ', b']', ..] => false,
+            [b'[', b'x' | b'X', b']', ..] => true,
+            _ => continue,
+        };
+
+        let text = &item[3..];
+
+        if !text.is_empty() && !text.starts_with(char::is_whitespace) {
+            continue;
+        }
+
+        markers.push(ChecklistMarker {
+            offset: start + (line.len() - item.len()) + 1,
+            checked,
+            text: text.trim(),
+        });
+    }
+
+    markers
+}
+
+fn list_item_content(line: &str) -> Option<&str> {
+    let rest = match line.strip_prefix(['-', '*', '+']) {
+        Some(rest) => rest,
+        None => {
+            let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+
+            if digits == 0 {
+                return None;
+            }
+
+            line[digits..].strip_prefix(['.', ')'])?
+        }
+    };
+
+    rest.strip_prefix([' ', '\t']).map(str::trim_start)
 }
 
 impl std::ops::Deref for RemarkEssence {
@@ -23,3 +153,75 @@ impl std::ops::Deref for RemarkEssence {
         &self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checklist_items() {
+        let essence = RemarkEssence::new(
+            "Trip\n\n- [ ] Book flights\n  * [x] Renew passport\n1. [X]\n- [] not an item\n\
+             ```\n- [ ] in code\n```\n+ [ ]no space\n- [ ] Pack"
+                .to_string(),
+        )
+        .unwrap();
+
+        let items = essence.checklist_items();
+
+        assert_eq!(
+            items
+                .iter()
+                .map(|item| (item.number(), item.text(), item.is_checked()))
+                .collect::<Vec<(usize, &str, bool)>>(),
+            [
+                (1, "Book flights", false),
+                (2, "Renew passport", true),
+                (3, "", true),
+                (4, "Pack", false)
+            ]
+        );
+
+        let toggled = essence.toggle_checklist_item(2).unwrap();
+
+        assert!(toggled.contains("  * [ ] Renew passport"));
+        assert!(
+            toggled
+                .toggle_checklist_item(4)
+                .unwrap()
+                .ends_with("- [x] Pack")
+        );
+        assert!(matches!(
+            essence.toggle_checklist_item(0),
+            Err(ApplicationError::NotFound)
+        ));
+        assert!(matches!(
+            essence.toggle_checklist_item(5),
+            Err(ApplicationError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn test_linked_remark_ids() {
+        let essence = RemarkEssence::new(
+            "See [[0195a2b8-7f4e-7c31-9d2a-3b5e8f1c6a70]] and [[ not-an-id ]], \
+             [[[0195a2b8-7f4e-7c31-9d2a-3b5e8f1c6a71]]] or [[0195a2b8-7f4e-7c31-9d2a-3b5e8f1c6a70]]"
+                .to_string(),
+        )
+        .unwrap();
+
+        let ids: Vec<String> = essence
+            .linked_remark_ids()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(
+            ids,
+            vec![
+                "0195a2b8-7f4e-7c31-9d2a-3b5e8f1c6a70",
+                "0195a2b8-7f4e-7c31-9d2a-3b5e8f1c6a71"
+            ]
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{ApplicationError, ApplicationResult};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "kind", content = "payload", rename_all = "snake_case")]
+pub enum RemarkKind {
+    #[default]
+    Note,
+
+    Link {
+        url: String,
+        domain: String,
+    },
+
+    Quote {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        source: Option<String>,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        author: Option<String>,
+    },
+
+    Task {
+        done: bool,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        due_date: Option<NaiveDate>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemarkKindName {
+    #[default]
+    Note,
+
+    Link,
+    Quote,
+    Task,
+}
+
+impl RemarkKind {
+    pub fn from_parts(name: &str, payload: Option<&str>) -> ApplicationResult<Self> {
+        let name: RemarkKindName = name.parse()?;
+
+        let payload = payload
+            .map(serde_json::from_str::<serde_json::Value>)
+            .transpose()
+            .map_err(|err| ApplicationError::internal("malformed remark payload", err))?;
+
+        let kind = serde_json::json!({ "kind": name, "payload": payload });
+
+        serde_json::from_value(kind)
+            .map_err(|err| ApplicationError::internal("malformed remark payload", err))
+    }
+
+    pub fn name(&self) -> RemarkKindName {
+        match self {
+            Self::Note => RemarkKindName::Note,
+            Self::Link { .. } => RemarkKindName::Link,
+            Self::Quote { .. } => RemarkKindName::Quote,
+            Self::Task { .. } => RemarkKindName::Task,
+        }
+    }
+
+    pub fn payload(&self) -> Option<String> {
+        let mut kind = serde_json::to_value(self).ok()?;
+
+        kind.get_mut("payload")
+            .map(serde_json::Value::take)
+            .map(|payload| payload.to_string())
+    }
+}
+
+impl RemarkKindName {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Note => "note",
+            Self::Link => "link",
+            Self::Quote => "quote",
+            Self::Task => "task",
+        }
+    }
+}
+
+impl std::str::FromStr for RemarkKindName {
+    type Err = ApplicationError;
+
+    fn from_str(s: &str) -> ApplicationResult<Self> {
+        match s.trim() {
+            "note" => Ok(Self::Note),
+            "link" => Ok(Self::Link),
+            "quote" => Ok(Self::Quote),
+            "task" => Ok(Self::Task),
+            _ => Err(ApplicationError::invalid_argument(
+                "remark kind must be note, link, quote or task",
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for RemarkKindName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Remark, RemarkAttributes, RemarkEssence};
+
+    #[test]
+    fn test_remark_kind_round_trip() {
+        let task = RemarkKind::Task {
+            done: false,
+            due_date: NaiveDate::from_ymd_opt(2025, 5, 10),
+        };
+
+        let payload = task.payload();
+
+        assert_eq!(
+            payload.as_deref(),
+            Some(r#"{"done":false,"due_date":"2025-05-10"}"#)
+        );
+        assert_eq!(RemarkKind::Note.payload(), None);
+        assert_eq!(
+            RemarkKind::from_parts("task", payload.as_deref()).unwrap(),
+            task
+        );
+        assert_eq!(
+            RemarkKind::from_parts("note", None).unwrap(),
+            RemarkKind::Note
+        );
+        assert!(RemarkKind::from_parts("todo", None).is_err());
+
+        let remark = Remark::new(RemarkAttributes {
+            id: uuid::Uuid::from_u128(1),
+            essence: RemarkEssence::new("Ship it".to_string()).unwrap(),
+            tags: vec![],
+            created_at: chrono::DateTime::UNIX_EPOCH,
+            updated_at: chrono::DateTime::UNIX_EPOCH,
+            deleted_at: None,
+            kind: task.clone(),
+        });
+
+        let json = serde_json::to_value(&remark).unwrap();
+
+        assert_eq!(json["kind"], "task");
+        assert_eq!(json["payload"]["due_date"], "2025-05-10");
+
+        let remark: Remark = serde_json::from_value(json).unwrap();
+
+        assert_eq!(remark.kind(), &task);
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RemarkLink {
+    source_id: Uuid,
+    target_id: Uuid,
+    dangling: bool,
+}
+
+pub struct RemarkLinkAttributes {
+    pub source_id: Uuid,
+    pub target_id: Uuid,
+    pub dangling: bool,
+}
+
+impl RemarkLink {
+    pub fn is_dangling(&self) -> bool {
+        self.dangling
+    }
+
+    pub fn new(attributes: RemarkLinkAttributes) -> Self {
+        let RemarkLinkAttributes {
+            source_id,
+            target_id,
+            dangling,
+        } = attributes;
+
+        Self {
+            source_id,
+            target_id,
+            dangling,
+        }
+    }
+
+    pub fn source_id(&self) -> Uuid {
+        self.source_id
+    }
+
+    pub fn target_id(&self) -> Uuid {
+        self.target_id
+    }
+}
+
+impl std::fmt::Display for RemarkLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string_pretty(&self).map_err(|_| std::fmt::Error)?;
+
+        f.write_str(&json)
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{RemarkEssence, RemarkKind, TagTitle};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RemarkRevision {
+    remark_id: Uuid,
+    number: i32,
+    essence: RemarkEssence,
+    tags: BTreeSet<TagTitle>,
+    created_at: DateTime<Utc>,
+
+    #[serde(flatten)]
+    kind: RemarkKind,
+}
+
+pub struct RemarkRevisionAttributes {
+    pub remark_id: Uuid,
+    pub number: i32,
+    pub essence: RemarkEssence,
+    pub tags: Vec<TagTitle>,
+    pub created_at: DateTime<Utc>,
+    pub kind: RemarkKind,
+}
+
+impl RemarkRevision {
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn essence(&self) -> &RemarkEssence {
+        &self.essence
+    }
+
+    pub fn new(attributes: RemarkRevisionAttributes) -> Self {
+        let RemarkRevisionAttributes {
+            remark_id,
+            number,
+            essence,
+            tags,
+            created_at,
+            kind,
+        } = attributes;
+
+        Self {
+            remark_id,
+            number,
+            essence,
+            tags: BTreeSet::from_iter(tags),
+            created_at,
+            kind,
+        }
+    }
+
+    pub fn kind(&self) -> &RemarkKind {
+        &self.kind
+    }
+
+    pub fn number(&self) -> i32 {
+        self.number
+    }
+
+    pub fn remark_id(&self) -> Uuid {
+        self.remark_id
+    }
+
+    pub fn tags(&self) -> Vec<&TagTitle> {
+        self.tags.iter().collect()
+    }
+}
+
+impl std::fmt::Display for RemarkRevision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string_pretty(&self).map_err(|_| std::fmt::Error)?;
+
+        f.write_str(&json)
+    }
+}
@@ -0,0 +1,51 @@
+use crate::Remark;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RemarkSearchHit {
+    remark: Remark,
+    rank: f32,
+    snippet: String,
+}
+
+pub struct RemarkSearchHitAttributes {
+    pub remark: Remark,
+    pub rank: f32,
+    pub snippet: String,
+}
+
+impl RemarkSearchHit {
+    pub fn new(attributes: RemarkSearchHitAttributes) -> Self {
+        let RemarkSearchHitAttributes {
+            remark,
+            rank,
+            snippet,
+        } = attributes;
+
+        Self {
+            remark,
+            rank,
+            snippet,
+        }
+    }
+
+    pub fn rank(&self) -> f32 {
+        self.rank
+    }
+
+    pub fn remark(&self) -> &Remark {
+        &self.remark
+    }
+
+    pub fn snippet(&self) -> &str {
+        &self.snippet
+    }
+}
+
+impl std::fmt::Display for RemarkSearchHit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string_pretty(&self).map_err(|_| std::fmt::Error)?;
+
+        f.write_str(&json)
+    }
+}
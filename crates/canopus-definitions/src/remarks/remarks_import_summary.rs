@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RemarksImportSummary {
+    imported: u64,
+    skipped: u64,
+
+    #[serde(default)]
+    failures: Vec<RemarkImportFailure>,
+}
+
+pub struct RemarksImportSummaryAttributes {
+    pub imported: u64,
+    pub skipped: u64,
+    pub failures: Vec<RemarkImportFailure>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RemarkImportFailure {
+    line: u64,
+    message: String,
+}
+
+pub struct RemarkImportFailureAttributes {
+    pub line: u64,
+    pub message: String,
+}
+
+impl RemarksImportSummary {
+    pub fn failures(&self) -> &[RemarkImportFailure] {
+        &self.failures
+    }
+
+    pub fn imported(&self) -> u64 {
+        self.imported
+    }
+
+    pub fn new(attributes: RemarksImportSummaryAttributes) -> Self {
+        let RemarksImportSummaryAttributes {
+            imported,
+            skipped,
+            failures,
+        } = attributes;
+
+        Self {
+            imported,
+            skipped,
+            failures,
+        }
+    }
+
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+}
+
+impl RemarkImportFailure {
+    pub fn line(&self) -> u64 {
+        self.line
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn new(attributes: RemarkImportFailureAttributes) -> Self {
+        let RemarkImportFailureAttributes { line, message } = attributes;
+
+        Self { line, message }
+    }
+}
+
+impl std::fmt::Display for RemarksImportSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string_pretty(&self).map_err(|_| std::fmt::Error)?;
+
+        f.write_str(&json)
+    }
+}
@@ -14,6 +14,9 @@ pub struct Tag {
     title: TagTitle,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    usage_count: Option<i64>,
 }
 
 pub struct TagAttributes {
@@ -21,6 +24,7 @@ pub struct TagAttributes {
     pub title: TagTitle,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub usage_count: Option<i64>,
 }
 
 impl Tag {
@@ -38,6 +42,7 @@ impl Tag {
             title,
             created_at,
             updated_at,
+            usage_count,
         } = attributes;
 
         Self {
@@ -45,6 +50,7 @@ impl Tag {
             title,
             created_at,
             updated_at,
+            usage_count,
         }
     }
 
@@ -71,6 +77,10 @@ impl Tag {
     pub fn updated_at(&self) -> DateTime<Utc> {
         self.updated_at
     }
+
+    pub fn usage_count(&self) -> Option<i64> {
+        self.usage_count
+    }
 }
 
 impl std::fmt::Display for Tag {
@@ -1,18 +1,59 @@
 use crate::{ApplicationError, ApplicationResult};
 use serde::{Deserialize, Serialize};
 
+const SEPARATOR: char = '/';
+
 #[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq, PartialOrd, Ord, Hash)]
 pub struct TagTitle(String);
 
 impl TagTitle {
     pub fn new(value: String) -> ApplicationResult<Self> {
+        let value = value.trim().to_lowercase();
+
         if value.is_empty() {
             return Err(ApplicationError::invalid_argument(
                 "tag title can't be blank",
             ));
         }
 
-        Ok(Self(value.trim().to_lowercase()))
+        let segments = value.split(SEPARATOR).map(str::trim).collect::<Vec<&str>>();
+
+        if segments.iter().any(|segment| segment.is_empty()) {
+            return Err(ApplicationError::invalid_argument(
+                "tag title can't contain blank segments",
+            ));
+        }
+
+        Ok(Self(segments.join(&SEPARATOR.to_string())))
+    }
+
+    pub fn ancestors(&self) -> Vec<TagTitle> {
+        self.0
+            .match_indices(SEPARATOR)
+            .map(|(index, _)| Self(self.0[..index].to_string()))
+            .collect()
+    }
+
+    pub fn depth(&self) -> usize {
+        self.0.matches(SEPARATOR).count()
+    }
+
+    pub fn is_descendant_of(&self, other: &TagTitle) -> bool {
+        self.0
+            .strip_prefix(other.as_str())
+            .is_some_and(|rest| rest.starts_with(SEPARATOR))
+    }
+
+    pub fn name(&self) -> &str {
+        self.0
+            .rsplit_once(SEPARATOR)
+            .map_or(self.0.as_str(), |(_, name)| name)
+    }
+
+    pub fn parent(&self) -> Option<TagTitle> {
+        self.0
+            .rsplit_once(SEPARATOR)
+            .map(|(parent, _)| Self(parent.to_string()))
     }
 }
 
@@ -29,3 +70,38 @@ impl std::fmt::Display for TagTitle {
         std::fmt::Display::fmt(&self.0, f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn title(value: &str) -> TagTitle {
+        TagTitle::new(value.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_new_tag_title_normalizes_segments() {
+        assert_eq!(
+            title(" Project / Canopus/API ").as_str(),
+            "project/canopus/api"
+        );
+        assert!(TagTitle::new("project//api".to_string()).is_err());
+        assert!(TagTitle::new("/project".to_string()).is_err());
+        assert!(TagTitle::new("  ".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_tag_title_hierarchy() {
+        let tag = title("project/canopus/api");
+
+        assert_eq!(
+            tag.ancestors(),
+            vec![title("project"), title("project/canopus")]
+        );
+        assert_eq!(tag.parent(), Some(title("project/canopus")));
+        assert_eq!(tag.name(), "api");
+        assert_eq!(tag.depth(), 2);
+        assert!(tag.is_descendant_of(&title("project")));
+        assert!(!title("projects").is_descendant_of(&title("project")));
+    }
+}
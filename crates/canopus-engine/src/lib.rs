@@ -1,13 +1,21 @@
 pub mod remarks;
 pub mod tags;
 
-use canopus_repository::Repository;
+mod repository;
+
+use canopus_repository::AttachmentsStorage;
+use chrono::TimeDelta;
 use eyre::WrapErr;
-use sqlx::PgPool;
-use std::env;
+use repository::Repository;
+use sqlx::{
+    PgPool,
+    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions},
+};
+use std::{env, str::FromStr};
 
 pub struct Engine {
     repository: Repository,
+    idempotency_window: TimeDelta,
 }
 
 impl Engine {
@@ -17,12 +25,67 @@ impl Engine {
         let database_url =
             env::var("DATABASE_URL").wrap_err_with(|| "Missing DATABASE_URL env var")?;
 
-        let pool = PgPool::connect(&database_url)
-            .await
-            .wrap_err_with(|| "Failed to connect to database")?;
+        let repository = match database_url.split_once(':') {
+            Some(("memory", _)) => Repository::Memory(Box::default()),
+            Some(("sqlite", _)) => Repository::Sqlite(connect_sqlite(&database_url).await?),
+            Some(("postgres" | "postgresql", _)) => {
+                Repository::Postgres(connect_postgres(&database_url).await?)
+            }
+            _ => {
+                eyre::bail!("Unsupported DATABASE_URL scheme, expected postgres, sqlite or memory")
+            }
+        };
+
+        let idempotency_window = match env::var("CANOPUS_IDEMPOTENCY_WINDOW_HOURS") {
+            Ok(hours) => hours
+                .parse()
+                .ok()
+                .and_then(TimeDelta::try_hours)
+                .ok_or_else(|| eyre::eyre!("Invalid CANOPUS_IDEMPOTENCY_WINDOW_HOURS env var"))?,
+            Err(_) => TimeDelta::days(1),
+        };
 
         Ok(Self {
-            repository: Repository { pool },
+            repository,
+            idempotency_window,
         })
     }
 }
+
+async fn connect_postgres(database_url: &str) -> eyre::Result<canopus_repository::Repository> {
+    let pool = PgPool::connect(database_url)
+        .await
+        .wrap_err_with(|| "Failed to connect to database")?;
+
+    let attachments_storage = match env::var("CANOPUS_ATTACHMENTS_DIR") {
+        Ok(directory) => AttachmentsStorage::Directory(directory.into()),
+        Err(_) => AttachmentsStorage::Database,
+    };
+
+    Ok(canopus_repository::Repository {
+        pool,
+        attachments_storage,
+    })
+}
+
+async fn connect_sqlite(database_url: &str) -> eyre::Result<canopus_sqlite_repository::Repository> {
+    let options = SqliteConnectOptions::from_str(database_url)
+        .wrap_err_with(|| "Invalid DATABASE_URL")?
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Wal);
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await
+        .wrap_err_with(|| "Failed to connect to database")?;
+
+    let repository = canopus_sqlite_repository::Repository { pool };
+
+    repository
+        .migrate()
+        .await
+        .wrap_err_with(|| "Failed to migrate database")?;
+
+    Ok(repository)
+}
@@ -1,48 +1,225 @@
 use crate::Engine;
-use canopus_definitions::{ApplicationResult, Page, Remark};
+use canopus_definitions::{
+    ApplicationResult, Page, Remark, RemarkAttachment, RemarkBulkOutcome, RemarkLink,
+    RemarkRevision, RemarkSearchHit,
+};
 use canopus_operations::remarks::{
-    self, NewRemarkAttributes, RemarkChanges, RemarksPageParameters,
+    self, BulkNewRemarkAttributes, ImportedRemarkAttributes, NewRemarkAttachmentAttributes,
+    NewRemarkAttributes, RemarkChanges, RemarksPageParameters, RemarksSearchParameters,
 };
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+pub async fn add_remark_tags(
+    engine: &Engine,
+    id: Uuid,
+    tags: Vec<String>,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<Remark> {
+    let Engine { repository, .. } = engine;
+
+    remarks::add_remark_tags(id, tags, expected_updated_at, repository).await
+}
+
+pub async fn create_idempotent_remark(
+    engine: &Engine,
+    key: String,
+    new_remark: NewRemarkAttributes,
+) -> ApplicationResult<Remark> {
+    let Engine {
+        repository,
+        idempotency_window,
+    } = engine;
+
+    remarks::create_idempotent_remark(key, *idempotency_window, new_remark, repository).await
+}
+
 pub async fn create_remark(
     engine: &Engine,
     new_remark: NewRemarkAttributes,
 ) -> ApplicationResult<Remark> {
-    let Engine { repository } = engine;
+    let Engine { repository, .. } = engine;
 
     remarks::create_remark(new_remark, repository).await
 }
 
-pub async fn delete_remark(engine: &Engine, id: Uuid) -> ApplicationResult<Remark> {
-    let Engine { repository } = engine;
+pub async fn create_remarks(
+    engine: &Engine,
+    new_remarks: Vec<BulkNewRemarkAttributes>,
+    dry_run: bool,
+) -> ApplicationResult<Vec<RemarkBulkOutcome>> {
+    let Engine { repository, .. } = engine;
+
+    remarks::create_remarks(new_remarks, dry_run, repository).await
+}
+
+pub async fn create_remark_attachment(
+    engine: &Engine,
+    id: Uuid,
+    attributes: NewRemarkAttachmentAttributes,
+) -> ApplicationResult<RemarkAttachment> {
+    let Engine { repository, .. } = engine;
+
+    remarks::create_remark_attachment(id, attributes, repository).await
+}
+
+pub async fn delete_remark(
+    engine: &Engine,
+    id: Uuid,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<Remark> {
+    let Engine { repository, .. } = engine;
+
+    remarks::delete_remark(id, expected_updated_at, repository).await
+}
+
+pub async fn delete_remark_permanently(
+    engine: &Engine,
+    id: Uuid,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<Remark> {
+    let Engine { repository, .. } = engine;
 
-    remarks::delete_remark(id, repository).await
+    remarks::delete_remark_permanently(id, expected_updated_at, repository).await
+}
+
+pub async fn delete_remark_attachment(
+    engine: &Engine,
+    id: Uuid,
+    attachment_id: Uuid,
+) -> ApplicationResult<RemarkAttachment> {
+    let Engine { repository, .. } = engine;
+
+    remarks::delete_remark_attachment(id, attachment_id, repository).await
 }
 
 pub async fn get_remark(engine: &Engine, id: Uuid) -> ApplicationResult<Remark> {
-    let Engine { repository } = engine;
+    let Engine { repository, .. } = engine;
 
     remarks::get_remark(id, repository).await
 }
 
+pub async fn get_remark_attachment_content(
+    engine: &Engine,
+    id: Uuid,
+    attachment_id: Uuid,
+) -> ApplicationResult<(RemarkAttachment, Vec<u8>)> {
+    let Engine { repository, .. } = engine;
+
+    remarks::get_remark_attachment_content(id, attachment_id, repository).await
+}
+
+pub async fn import_remark(
+    engine: &Engine,
+    attributes: ImportedRemarkAttributes,
+) -> ApplicationResult<bool> {
+    let Engine { repository, .. } = engine;
+
+    remarks::import_remark(attributes, repository).await
+}
+
 pub async fn list_remarks(
     engine: &Engine,
     parameters: RemarksPageParameters,
 ) -> ApplicationResult<Page<Remark>> {
-    let Engine { repository } = engine;
+    let Engine { repository, .. } = engine;
 
     let page = remarks::list_remarks(parameters, repository).await?;
 
     Ok(page)
 }
 
+pub async fn list_remark_attachments(
+    engine: &Engine,
+    id: Uuid,
+) -> ApplicationResult<Page<RemarkAttachment>> {
+    let Engine { repository, .. } = engine;
+
+    remarks::list_remark_attachments(id, repository).await
+}
+
+pub async fn list_remark_backlinks(
+    engine: &Engine,
+    id: Uuid,
+) -> ApplicationResult<Page<RemarkLink>> {
+    let Engine { repository, .. } = engine;
+
+    remarks::list_remark_backlinks(id, repository).await
+}
+
+pub async fn list_remark_links(engine: &Engine, id: Uuid) -> ApplicationResult<Page<RemarkLink>> {
+    let Engine { repository, .. } = engine;
+
+    remarks::list_remark_links(id, repository).await
+}
+
+pub async fn list_remark_revisions(
+    engine: &Engine,
+    id: Uuid,
+) -> ApplicationResult<Page<RemarkRevision>> {
+    let Engine { repository, .. } = engine;
+
+    remarks::list_remark_revisions(id, repository).await
+}
+
+pub async fn remove_remark_tags(
+    engine: &Engine,
+    id: Uuid,
+    tags: Vec<String>,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<Remark> {
+    let Engine { repository, .. } = engine;
+
+    remarks::remove_remark_tags(id, tags, expected_updated_at, repository).await
+}
+
+pub async fn restore_remark_revision(
+    engine: &Engine,
+    id: Uuid,
+    number: i32,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<Remark> {
+    let Engine { repository, .. } = engine;
+
+    remarks::restore_remark_revision(id, number, expected_updated_at, repository).await
+}
+
+pub async fn restore_remark(
+    engine: &Engine,
+    id: Uuid,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<Remark> {
+    let Engine { repository, .. } = engine;
+
+    remarks::restore_remark(id, expected_updated_at, repository).await
+}
+
+pub async fn search_remarks(
+    engine: &Engine,
+    parameters: RemarksSearchParameters,
+) -> ApplicationResult<Page<RemarkSearchHit>> {
+    let Engine { repository, .. } = engine;
+
+    remarks::search_remarks(parameters, repository).await
+}
+
+pub async fn toggle_remark_item(
+    engine: &Engine,
+    id: Uuid,
+    number: usize,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<Remark> {
+    let Engine { repository, .. } = engine;
+
+    remarks::toggle_remark_item(id, number, expected_updated_at, repository).await
+}
+
 pub async fn update_remark(
     engine: &Engine,
     id: Uuid,
     changes: RemarkChanges,
 ) -> ApplicationResult<Remark> {
-    let Engine { repository } = engine;
+    let Engine { repository, .. } = engine;
 
     remarks::update_remark(id, changes, repository).await
 }
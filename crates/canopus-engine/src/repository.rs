@@ -0,0 +1,411 @@
+use canopus_definitions::{
+    ApplicationResult, Page, PageSize, Remark, RemarkAttachment, RemarkBulkOutcome, RemarkLink,
+    RemarkRevision, RemarkSearchHit, Tag, TagTitle,
+};
+use canopus_operations::{
+    remarks::{
+        AddRemarkTags, BulkNewRemark, DeleteRemark, DeleteRemarkAttachment, GetRemark,
+        GetRemarkAttachment, GetRemarkAttachmentContent, GetTrashedRemark, ImportRemark,
+        InsertIdempotentRemark, InsertRemark, InsertRemarkAttachment, InsertRemarks,
+        ListRemarkAttachments, ListRemarkBacklinks, ListRemarkLinks, ListRemarkRevisions,
+        ListRemarks, NewRemark, NewRemarkAttachment, RemarkIdempotencyKey, RemarksPageParameters,
+        RemarksSearchParameters, RemoveRemarkTags, RestoreRemark, RestoreRemarkRevision,
+        SearchRemarks, TrashRemark, UpdateRemark,
+    },
+    tags::{
+        GetTag, ListTagChildren, ListTags, MergeTags, SuggestTags, TagsPageParameters, UpdateTag,
+    },
+};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+pub enum Repository {
+    Memory(Box<canopus_memory_repository::Repository>),
+    Postgres(canopus_repository::Repository),
+    Sqlite(canopus_sqlite_repository::Repository),
+}
+
+impl AddRemarkTags for Repository {
+    async fn add_remark_tags(
+        &self,
+        remark: &mut Remark,
+        tags: &[TagTitle],
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
+        match self {
+            Self::Memory(repository) => {
+                repository
+                    .add_remark_tags(remark, tags, expected_updated_at)
+                    .await
+            }
+            Self::Postgres(repository) => {
+                repository
+                    .add_remark_tags(remark, tags, expected_updated_at)
+                    .await
+            }
+            Self::Sqlite(repository) => {
+                repository
+                    .add_remark_tags(remark, tags, expected_updated_at)
+                    .await
+            }
+        }
+    }
+}
+
+impl DeleteRemark for Repository {
+    async fn delete_remark(
+        &self,
+        remark: &Remark,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
+        match self {
+            Self::Memory(repository) => repository.delete_remark(remark, expected_updated_at).await,
+            Self::Postgres(repository) => {
+                repository.delete_remark(remark, expected_updated_at).await
+            }
+            Self::Sqlite(repository) => repository.delete_remark(remark, expected_updated_at).await,
+        }
+    }
+}
+
+impl DeleteRemarkAttachment for Repository {
+    async fn delete_remark_attachment(
+        &self,
+        attachment: &RemarkAttachment,
+    ) -> ApplicationResult<()> {
+        match self {
+            Self::Memory(repository) => repository.delete_remark_attachment(attachment).await,
+            Self::Postgres(repository) => repository.delete_remark_attachment(attachment).await,
+            Self::Sqlite(repository) => repository.delete_remark_attachment(attachment).await,
+        }
+    }
+}
+
+impl GetRemark for Repository {
+    async fn get_remark(&self, id: Uuid) -> ApplicationResult<Remark> {
+        match self {
+            Self::Memory(repository) => repository.get_remark(id).await,
+            Self::Postgres(repository) => repository.get_remark(id).await,
+            Self::Sqlite(repository) => repository.get_remark(id).await,
+        }
+    }
+}
+
+impl GetRemarkAttachment for Repository {
+    async fn get_remark_attachment(
+        &self,
+        remark_id: Uuid,
+        id: Uuid,
+    ) -> ApplicationResult<RemarkAttachment> {
+        match self {
+            Self::Memory(repository) => repository.get_remark_attachment(remark_id, id).await,
+            Self::Postgres(repository) => repository.get_remark_attachment(remark_id, id).await,
+            Self::Sqlite(repository) => repository.get_remark_attachment(remark_id, id).await,
+        }
+    }
+}
+
+impl GetRemarkAttachmentContent for Repository {
+    async fn get_remark_attachment_content(
+        &self,
+        attachment: &RemarkAttachment,
+    ) -> ApplicationResult<Vec<u8>> {
+        match self {
+            Self::Memory(repository) => repository.get_remark_attachment_content(attachment).await,
+            Self::Postgres(repository) => {
+                repository.get_remark_attachment_content(attachment).await
+            }
+            Self::Sqlite(repository) => repository.get_remark_attachment_content(attachment).await,
+        }
+    }
+}
+
+impl GetTag for Repository {
+    async fn get_tag(&self, id: Uuid) -> ApplicationResult<Tag> {
+        match self {
+            Self::Memory(repository) => repository.get_tag(id).await,
+            Self::Postgres(repository) => repository.get_tag(id).await,
+            Self::Sqlite(repository) => repository.get_tag(id).await,
+        }
+    }
+}
+
+impl GetTrashedRemark for Repository {
+    async fn get_trashed_remark(&self, id: Uuid) -> ApplicationResult<Remark> {
+        match self {
+            Self::Memory(repository) => repository.get_trashed_remark(id).await,
+            Self::Postgres(repository) => repository.get_trashed_remark(id).await,
+            Self::Sqlite(repository) => repository.get_trashed_remark(id).await,
+        }
+    }
+}
+
+impl ImportRemark for Repository {
+    async fn import_remark(&self, remark: &mut Remark) -> ApplicationResult<bool> {
+        match self {
+            Self::Memory(repository) => repository.import_remark(remark).await,
+            Self::Postgres(repository) => repository.import_remark(remark).await,
+            Self::Sqlite(repository) => repository.import_remark(remark).await,
+        }
+    }
+}
+
+impl InsertIdempotentRemark for Repository {
+    async fn insert_idempotent_remark(
+        &self,
+        remark: NewRemark,
+        key: RemarkIdempotencyKey,
+    ) -> ApplicationResult<Remark> {
+        match self {
+            Self::Memory(repository) => repository.insert_idempotent_remark(remark, key).await,
+            Self::Postgres(repository) => repository.insert_idempotent_remark(remark, key).await,
+            Self::Sqlite(repository) => repository.insert_idempotent_remark(remark, key).await,
+        }
+    }
+}
+
+impl InsertRemark for Repository {
+    async fn insert_remark(&self, remark: NewRemark) -> ApplicationResult<Remark> {
+        match self {
+            Self::Memory(repository) => repository.insert_remark(remark).await,
+            Self::Postgres(repository) => repository.insert_remark(remark).await,
+            Self::Sqlite(repository) => repository.insert_remark(remark).await,
+        }
+    }
+}
+
+impl InsertRemarks for Repository {
+    async fn insert_remarks(
+        &self,
+        remarks: Vec<BulkNewRemark>,
+        dry_run: bool,
+    ) -> ApplicationResult<Vec<RemarkBulkOutcome>> {
+        match self {
+            Self::Memory(repository) => repository.insert_remarks(remarks, dry_run).await,
+            Self::Postgres(repository) => repository.insert_remarks(remarks, dry_run).await,
+            Self::Sqlite(repository) => repository.insert_remarks(remarks, dry_run).await,
+        }
+    }
+}
+
+impl InsertRemarkAttachment for Repository {
+    async fn insert_remark_attachment(
+        &self,
+        attachment: NewRemarkAttachment,
+    ) -> ApplicationResult<RemarkAttachment> {
+        match self {
+            Self::Memory(repository) => repository.insert_remark_attachment(attachment).await,
+            Self::Postgres(repository) => repository.insert_remark_attachment(attachment).await,
+            Self::Sqlite(repository) => repository.insert_remark_attachment(attachment).await,
+        }
+    }
+}
+
+impl ListRemarkAttachments for Repository {
+    async fn list_remark_attachments(
+        &self,
+        remark_id: Uuid,
+    ) -> ApplicationResult<Vec<RemarkAttachment>> {
+        match self {
+            Self::Memory(repository) => repository.list_remark_attachments(remark_id).await,
+            Self::Postgres(repository) => repository.list_remark_attachments(remark_id).await,
+            Self::Sqlite(repository) => repository.list_remark_attachments(remark_id).await,
+        }
+    }
+}
+
+impl ListRemarkBacklinks for Repository {
+    async fn list_remark_backlinks(&self, remark_id: Uuid) -> ApplicationResult<Vec<RemarkLink>> {
+        match self {
+            Self::Memory(repository) => repository.list_remark_backlinks(remark_id).await,
+            Self::Postgres(repository) => repository.list_remark_backlinks(remark_id).await,
+            Self::Sqlite(repository) => repository.list_remark_backlinks(remark_id).await,
+        }
+    }
+}
+
+impl ListRemarkLinks for Repository {
+    async fn list_remark_links(&self, remark_id: Uuid) -> ApplicationResult<Vec<RemarkLink>> {
+        match self {
+            Self::Memory(repository) => repository.list_remark_links(remark_id).await,
+            Self::Postgres(repository) => repository.list_remark_links(remark_id).await,
+            Self::Sqlite(repository) => repository.list_remark_links(remark_id).await,
+        }
+    }
+}
+
+impl ListRemarkRevisions for Repository {
+    async fn list_remark_revisions(
+        &self,
+        remark_id: Uuid,
+    ) -> ApplicationResult<Vec<RemarkRevision>> {
+        match self {
+            Self::Memory(repository) => repository.list_remark_revisions(remark_id).await,
+            Self::Postgres(repository) => repository.list_remark_revisions(remark_id).await,
+            Self::Sqlite(repository) => repository.list_remark_revisions(remark_id).await,
+        }
+    }
+}
+
+impl ListRemarks for Repository {
+    async fn list_remarks(
+        &self,
+        parameters: RemarksPageParameters,
+    ) -> ApplicationResult<Page<Remark>> {
+        match self {
+            Self::Memory(repository) => repository.list_remarks(parameters).await,
+            Self::Postgres(repository) => repository.list_remarks(parameters).await,
+            Self::Sqlite(repository) => repository.list_remarks(parameters).await,
+        }
+    }
+}
+
+impl ListTagChildren for Repository {
+    async fn list_tag_children(&self, tag: &Tag) -> ApplicationResult<Vec<Tag>> {
+        match self {
+            Self::Memory(repository) => repository.list_tag_children(tag).await,
+            Self::Postgres(repository) => repository.list_tag_children(tag).await,
+            Self::Sqlite(repository) => repository.list_tag_children(tag).await,
+        }
+    }
+}
+
+impl ListTags for Repository {
+    async fn list_tags(&self, parameters: TagsPageParameters) -> ApplicationResult<Page<Tag>> {
+        match self {
+            Self::Memory(repository) => repository.list_tags(parameters).await,
+            Self::Postgres(repository) => repository.list_tags(parameters).await,
+            Self::Sqlite(repository) => repository.list_tags(parameters).await,
+        }
+    }
+}
+
+impl MergeTags for Repository {
+    async fn merge_tags(&self, source: &Tag, target: &mut Tag) -> ApplicationResult<()> {
+        match self {
+            Self::Memory(repository) => repository.merge_tags(source, target).await,
+            Self::Postgres(repository) => repository.merge_tags(source, target).await,
+            Self::Sqlite(repository) => repository.merge_tags(source, target).await,
+        }
+    }
+}
+
+impl RemoveRemarkTags for Repository {
+    async fn remove_remark_tags(
+        &self,
+        remark: &mut Remark,
+        tags: &[TagTitle],
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
+        match self {
+            Self::Memory(repository) => {
+                repository
+                    .remove_remark_tags(remark, tags, expected_updated_at)
+                    .await
+            }
+            Self::Postgres(repository) => {
+                repository
+                    .remove_remark_tags(remark, tags, expected_updated_at)
+                    .await
+            }
+            Self::Sqlite(repository) => {
+                repository
+                    .remove_remark_tags(remark, tags, expected_updated_at)
+                    .await
+            }
+        }
+    }
+}
+
+impl RestoreRemark for Repository {
+    async fn restore_remark(
+        &self,
+        remark: &mut Remark,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
+        match self {
+            Self::Memory(repository) => {
+                repository.restore_remark(remark, expected_updated_at).await
+            }
+            Self::Postgres(repository) => {
+                repository.restore_remark(remark, expected_updated_at).await
+            }
+            Self::Sqlite(repository) => {
+                repository.restore_remark(remark, expected_updated_at).await
+            }
+        }
+    }
+}
+
+impl RestoreRemarkRevision for Repository {
+    async fn restore_remark_revision(
+        &self,
+        remark: &mut Remark,
+        number: i32,
+    ) -> ApplicationResult<()> {
+        match self {
+            Self::Memory(repository) => repository.restore_remark_revision(remark, number).await,
+            Self::Postgres(repository) => repository.restore_remark_revision(remark, number).await,
+            Self::Sqlite(repository) => repository.restore_remark_revision(remark, number).await,
+        }
+    }
+}
+
+impl SearchRemarks for Repository {
+    async fn search_remarks(
+        &self,
+        parameters: RemarksSearchParameters,
+    ) -> ApplicationResult<Page<RemarkSearchHit>> {
+        match self {
+            Self::Memory(repository) => repository.search_remarks(parameters).await,
+            Self::Postgres(repository) => repository.search_remarks(parameters).await,
+            Self::Sqlite(repository) => repository.search_remarks(parameters).await,
+        }
+    }
+}
+
+impl SuggestTags for Repository {
+    async fn suggest_tags(&self, prefix: &str, limit: PageSize) -> ApplicationResult<Vec<Tag>> {
+        match self {
+            Self::Memory(repository) => repository.suggest_tags(prefix, limit).await,
+            Self::Postgres(repository) => repository.suggest_tags(prefix, limit).await,
+            Self::Sqlite(repository) => repository.suggest_tags(prefix, limit).await,
+        }
+    }
+}
+
+impl TrashRemark for Repository {
+    async fn trash_remark(
+        &self,
+        remark: &mut Remark,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
+        match self {
+            Self::Memory(repository) => repository.trash_remark(remark, expected_updated_at).await,
+            Self::Postgres(repository) => {
+                repository.trash_remark(remark, expected_updated_at).await
+            }
+            Self::Sqlite(repository) => repository.trash_remark(remark, expected_updated_at).await,
+        }
+    }
+}
+
+impl UpdateRemark for Repository {
+    async fn update_remark(&self, remark: &mut Remark) -> ApplicationResult<()> {
+        match self {
+            Self::Memory(repository) => repository.update_remark(remark).await,
+            Self::Postgres(repository) => repository.update_remark(remark).await,
+            Self::Sqlite(repository) => repository.update_remark(remark).await,
+        }
+    }
+}
+
+impl UpdateTag for Repository {
+    async fn update_tag(&self, tag: &mut Tag) -> ApplicationResult<()> {
+        match self {
+            Self::Memory(repository) => repository.update_tag(tag).await,
+            Self::Postgres(repository) => repository.update_tag(tag).await,
+            Self::Sqlite(repository) => repository.update_tag(tag).await,
+        }
+    }
+}
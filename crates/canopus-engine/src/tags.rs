@@ -1,25 +1,51 @@
 use crate::Engine;
-use canopus_definitions::{ApplicationResult, Page, Tag};
-use canopus_operations::tags::{self, TagsPageParameters};
+use canopus_definitions::{ApplicationResult, Page, PageSize, Tag};
+use canopus_operations::tags::{self, TagChanges, TagsPageParameters};
 use uuid::Uuid;
 
 pub async fn get_tag(engine: &Engine, id: Uuid) -> ApplicationResult<Tag> {
-    let Engine { repository } = engine;
+    let Engine { repository, .. } = engine;
 
     tags::get_tag(id, repository).await
 }
 
+pub async fn list_tag_children(engine: &Engine, id: Uuid) -> ApplicationResult<Page<Tag>> {
+    let Engine { repository, .. } = engine;
+
+    tags::list_tag_children(id, repository).await
+}
+
 pub async fn list_tags(
     engine: &Engine,
     parameters: TagsPageParameters,
 ) -> ApplicationResult<Page<Tag>> {
-    let Engine { repository } = engine;
+    let Engine { repository, .. } = engine;
 
     tags::list_tags(parameters, repository).await
 }
 
-pub async fn update_tag(engine: &Engine, id: Uuid, title: String) -> ApplicationResult<Tag> {
-    let Engine { repository } = engine;
+pub async fn merge_tags(
+    engine: &Engine,
+    source_id: Uuid,
+    target_id: Uuid,
+) -> ApplicationResult<Tag> {
+    let Engine { repository, .. } = engine;
+
+    tags::merge_tags(source_id, target_id, repository).await
+}
+
+pub async fn suggest_tags(
+    engine: &Engine,
+    prefix: String,
+    limit: Option<PageSize>,
+) -> ApplicationResult<Page<Tag>> {
+    let Engine { repository, .. } = engine;
+
+    tags::suggest_tags(prefix, limit, repository).await
+}
+
+pub async fn update_tag(engine: &Engine, id: Uuid, changes: TagChanges) -> ApplicationResult<Tag> {
+    let Engine { repository, .. } = engine;
 
-    tags::update_tag(id, title, repository).await
+    tags::update_tag(id, changes, repository).await
 }
@@ -11,7 +11,7 @@ impl Repo {
         Ok(Self { pool })
     }
 
-    pub async fn transaction(&self) -> sqlx::Result<PgTransaction> {
+    pub async fn transaction(&self) -> sqlx::Result<PgTransaction<'_>> {
         self.pool.begin().await
     }
 
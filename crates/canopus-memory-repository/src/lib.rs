@@ -0,0 +1,82 @@
+mod remark_attachments;
+mod remark_content_hashes;
+mod remark_idempotency_keys;
+mod remark_links;
+mod remark_revisions;
+mod remarks;
+mod remarks_search;
+mod tags;
+
+use base64::{
+    alphabet,
+    engine::{GeneralPurpose, general_purpose},
+};
+use canopus_definitions::{ApplicationError, ApplicationResult, PageSize};
+use remark_attachments::RemarkAttachmentRecord;
+use remark_idempotency_keys::RemarkIdempotencyKeyRecord;
+use remark_revisions::RemarkRevisionRecord;
+use remarks::RemarkRecord;
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::{Mutex, MutexGuard},
+};
+use tags::TagRecord;
+use uuid::Uuid;
+
+const URL_SAFE_NO_PAD_ENGINE: GeneralPurpose =
+    GeneralPurpose::new(&alphabet::URL_SAFE, general_purpose::NO_PAD);
+
+#[derive(Default)]
+pub struct Repository {
+    state: Mutex<State>,
+}
+
+#[derive(Clone, Default)]
+struct State {
+    remarks: HashMap<Uuid, RemarkRecord>,
+    remarks_tags: BTreeSet<(Uuid, Uuid)>,
+    tags: HashMap<Uuid, TagRecord>,
+    tag_aliases: HashMap<String, Uuid>,
+    remark_revisions: Vec<RemarkRevisionRecord>,
+    remark_links: BTreeSet<(Uuid, Uuid)>,
+    remark_attachments: HashMap<Uuid, RemarkAttachmentRecord>,
+    remark_idempotency_keys: HashMap<String, RemarkIdempotencyKeyRecord>,
+    remark_content_hashes: HashMap<String, Uuid>,
+}
+
+impl Repository {
+    fn read<T>(&self, f: impl FnOnce(&State) -> ApplicationResult<T>) -> ApplicationResult<T> {
+        f(&*self.lock()?)
+    }
+
+    fn transaction<T>(
+        &self,
+        f: impl FnOnce(&mut State) -> ApplicationResult<T>,
+    ) -> ApplicationResult<T> {
+        let mut state = self.lock()?;
+        let mut draft = state.clone();
+
+        let value = f(&mut draft)?;
+
+        *state = draft;
+
+        Ok(value)
+    }
+
+    fn lock(&self) -> ApplicationResult<MutexGuard<'_, State>> {
+        self.state
+            .lock()
+            .map_err(|_err| ApplicationError::repository("in-memory repository state is poisoned"))
+    }
+}
+
+fn resolve_page_size(
+    page_size: Option<PageSize>,
+    token_page_size: Option<i64>,
+) -> ApplicationResult<PageSize> {
+    match (page_size, token_page_size) {
+        (Some(page_size), _) => Ok(page_size),
+        (None, Some(page_size)) => PageSize::new(page_size),
+        (None, None) => Ok(PageSize::default()),
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{Repository, State};
+use canopus_definitions::{
+    ApplicationError, ApplicationResult, RemarkAttachment, RemarkAttachmentAttributes,
+};
+use canopus_operations::remarks::{
+    DeleteRemarkAttachment, GetRemarkAttachment, GetRemarkAttachmentContent,
+    InsertRemarkAttachment, ListRemarkAttachments, NewRemarkAttachment,
+};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct RemarkAttachmentRecord {
+    pub id: Uuid,
+    pub remark_id: Uuid,
+    pub file_name: String,
+    pub media_type: String,
+    pub size: i64,
+    pub checksum: String,
+    pub created_at: DateTime<Utc>,
+    pub content: Arc<[u8]>,
+}
+
+impl DeleteRemarkAttachment for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn delete_remark_attachment(
+        &self,
+        attachment: &RemarkAttachment,
+    ) -> ApplicationResult<()> {
+        self.transaction(|state| {
+            state
+                .remark_attachments
+                .remove(&attachment.id())
+                .map(|_record| ())
+                .ok_or(ApplicationError::NotFound)
+        })
+    }
+}
+
+impl GetRemarkAttachment for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn get_remark_attachment(
+        &self,
+        remark_id: Uuid,
+        id: Uuid,
+    ) -> ApplicationResult<RemarkAttachment> {
+        self.read(|state| {
+            state
+                .remark_attachments
+                .get(&id)
+                .filter(|record| record.remark_id == remark_id)
+                .map(|record| record.clone().into())
+                .ok_or(ApplicationError::NotFound)
+        })
+    }
+}
+
+impl GetRemarkAttachmentContent for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn get_remark_attachment_content(
+        &self,
+        attachment: &RemarkAttachment,
+    ) -> ApplicationResult<Vec<u8>> {
+        self.read(|state| {
+            state
+                .remark_attachments
+                .get(&attachment.id())
+                .map(|record| record.content.to_vec())
+                .ok_or(ApplicationError::NotFound)
+        })
+    }
+}
+
+impl InsertRemarkAttachment for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn insert_remark_attachment(
+        &self,
+        attachment: NewRemarkAttachment,
+    ) -> ApplicationResult<RemarkAttachment> {
+        let NewRemarkAttachment {
+            remark_id,
+            file_name,
+            media_type,
+            checksum,
+            content,
+        } = attachment;
+
+        self.transaction(|state| {
+            let record = RemarkAttachmentRecord {
+                id: Uuid::new_v4(),
+                remark_id,
+                file_name,
+                media_type,
+                size: content.len() as i64,
+                checksum,
+                created_at: Utc::now(),
+                content: content.into(),
+            };
+
+            state.remark_attachments.insert(record.id, record.clone());
+
+            Ok(record.into())
+        })
+    }
+}
+
+impl ListRemarkAttachments for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn list_remark_attachments(
+        &self,
+        remark_id: Uuid,
+    ) -> ApplicationResult<Vec<RemarkAttachment>> {
+        self.read(|state| {
+            let mut records: Vec<&RemarkAttachmentRecord> = state
+                .remark_attachments
+                .values()
+                .filter(|record| record.remark_id == remark_id)
+                .collect();
+
+            records.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+
+            Ok(records
+                .into_iter()
+                .map(|record| record.clone().into())
+                .collect())
+        })
+    }
+}
+
+pub fn delete(state: &mut State, remark_id: Uuid) {
+    state
+        .remark_attachments
+        .retain(|_, record| record.remark_id != remark_id);
+}
+
+impl From<RemarkAttachmentRecord> for RemarkAttachment {
+    fn from(value: RemarkAttachmentRecord) -> Self {
+        let RemarkAttachmentRecord {
+            id,
+            remark_id,
+            file_name,
+            media_type,
+            size,
+            checksum,
+            created_at,
+            content: _,
+        } = value;
+
+        Self::new(RemarkAttachmentAttributes {
+            id,
+            remark_id,
+            file_name,
+            media_type,
+            size,
+            checksum,
+            created_at,
+        })
+    }
+}
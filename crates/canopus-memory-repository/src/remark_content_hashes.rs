@@ -0,0 +1,65 @@
+use crate::{Repository, State, remarks};
+use canopus_definitions::{ApplicationResult, RemarkBulkOutcome, RemarkBulkOutcomeAttributes};
+use canopus_operations::remarks::{BulkNewRemark, InsertRemarks};
+use uuid::Uuid;
+
+impl InsertRemarks for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn insert_remarks(
+        &self,
+        remarks: Vec<BulkNewRemark>,
+        dry_run: bool,
+    ) -> ApplicationResult<Vec<RemarkBulkOutcome>> {
+        self.transaction(|state| {
+            let mut outcomes = Vec::with_capacity(remarks.len());
+
+            for BulkNewRemark {
+                remark,
+                content_hash,
+            } in remarks
+            {
+                if let Some(remark_id) = content_hash
+                    .as_ref()
+                    .and_then(|content_hash| state.remark_content_hashes.get(content_hash))
+                {
+                    outcomes.push(RemarkBulkOutcome::new(RemarkBulkOutcomeAttributes {
+                        remark_id: Some(*remark_id),
+                        skipped: true,
+                    }));
+
+                    continue;
+                }
+
+                if dry_run {
+                    outcomes.push(RemarkBulkOutcome::new(RemarkBulkOutcomeAttributes {
+                        remark_id: None,
+                        skipped: false,
+                    }));
+
+                    continue;
+                }
+
+                let remark = remarks::insert_remark(state, remark)?;
+
+                if let Some(content_hash) = content_hash {
+                    state
+                        .remark_content_hashes
+                        .insert(content_hash, remark.id());
+                }
+
+                outcomes.push(RemarkBulkOutcome::new(RemarkBulkOutcomeAttributes {
+                    remark_id: Some(remark.id()),
+                    skipped: false,
+                }));
+            }
+
+            Ok(outcomes)
+        })
+    }
+}
+
+pub fn delete(state: &mut State, remark_id: Uuid) {
+    state
+        .remark_content_hashes
+        .retain(|_, content_remark_id| *content_remark_id != remark_id);
+}
@@ -0,0 +1,65 @@
+use crate::{Repository, State, remarks};
+use canopus_definitions::{ApplicationError, ApplicationResult, Remark};
+use canopus_operations::remarks::{InsertIdempotentRemark, NewRemark, RemarkIdempotencyKey};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct RemarkIdempotencyKeyRecord {
+    pub request_hash: String,
+    pub remark_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl InsertIdempotentRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn insert_idempotent_remark(
+        &self,
+        new_remark: NewRemark,
+        key: RemarkIdempotencyKey,
+    ) -> ApplicationResult<Remark> {
+        let RemarkIdempotencyKey {
+            key,
+            request_hash,
+            created_after,
+        } = key;
+
+        self.transaction(|state| {
+            state
+                .remark_idempotency_keys
+                .retain(|_, record| record.created_at >= created_after);
+
+            if let Some(record) = state.remark_idempotency_keys.get(&key) {
+                if record.request_hash != request_hash {
+                    return Err(ApplicationError::Conflict);
+                }
+
+                let record = state
+                    .remarks
+                    .get(&record.remark_id)
+                    .ok_or(ApplicationError::NotFound)?;
+
+                return remarks::to_remark(state, record);
+            }
+
+            let remark = remarks::insert_remark(state, new_remark)?;
+
+            state.remark_idempotency_keys.insert(
+                key,
+                RemarkIdempotencyKeyRecord {
+                    request_hash,
+                    remark_id: remark.id(),
+                    created_at: Utc::now(),
+                },
+            );
+
+            Ok(remark)
+        })
+    }
+}
+
+pub fn delete(state: &mut State, remark_id: Uuid) {
+    state
+        .remark_idempotency_keys
+        .retain(|_, record| record.remark_id != remark_id);
+}
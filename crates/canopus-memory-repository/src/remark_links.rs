@@ -0,0 +1,77 @@
+use crate::{Repository, State};
+use canopus_definitions::{ApplicationResult, Remark, RemarkLink, RemarkLinkAttributes};
+use canopus_operations::remarks::{ListRemarkBacklinks, ListRemarkLinks};
+use uuid::Uuid;
+
+impl ListRemarkBacklinks for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn list_remark_backlinks(&self, remark_id: Uuid) -> ApplicationResult<Vec<RemarkLink>> {
+        self.read(|state| {
+            let mut sources: Vec<_> = state
+                .remark_links
+                .iter()
+                .filter(|(_, target_id)| *target_id == remark_id)
+                .filter_map(|(source_id, _)| state.remarks.get(source_id))
+                .filter(|source| source.deleted_at.is_none())
+                .collect();
+
+            sources.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+
+            let links = sources
+                .into_iter()
+                .map(|source| {
+                    RemarkLink::new(RemarkLinkAttributes {
+                        source_id: source.id,
+                        target_id: remark_id,
+                        dangling: false,
+                    })
+                })
+                .collect();
+
+            Ok(links)
+        })
+    }
+}
+
+impl ListRemarkLinks for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn list_remark_links(&self, remark_id: Uuid) -> ApplicationResult<Vec<RemarkLink>> {
+        self.read(|state| {
+            let links = state
+                .remark_links
+                .iter()
+                .filter(|(source_id, _)| *source_id == remark_id)
+                .map(|(source_id, target_id)| {
+                    let dangling = state
+                        .remarks
+                        .get(target_id)
+                        .is_none_or(|target| target.deleted_at.is_some());
+
+                    RemarkLink::new(RemarkLinkAttributes {
+                        source_id: *source_id,
+                        target_id: *target_id,
+                        dangling,
+                    })
+                })
+                .collect();
+
+            Ok(links)
+        })
+    }
+}
+
+pub fn delete(state: &mut State, remark_id: Uuid) {
+    state
+        .remark_links
+        .retain(|(source_id, _)| *source_id != remark_id);
+}
+
+pub fn save(state: &mut State, remark: &Remark) {
+    delete(state, remark.id());
+
+    for target_id in remark.essence().linked_remark_ids() {
+        if target_id != remark.id() {
+            state.remark_links.insert((remark.id(), target_id));
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{Repository, State, remarks};
+use canopus_definitions::{
+    ApplicationError, ApplicationResult, Remark, RemarkEssence, RemarkKind, RemarkRevision,
+    RemarkRevisionAttributes, TagTitle,
+};
+use canopus_operations::remarks::{ListRemarkRevisions, RestoreRemarkRevision};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct RemarkRevisionRecord {
+    pub remark_id: Uuid,
+    pub number: i32,
+    pub essence: String,
+    pub tags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub kind: RemarkKind,
+}
+
+impl ListRemarkRevisions for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn list_remark_revisions(
+        &self,
+        remark_id: Uuid,
+    ) -> ApplicationResult<Vec<RemarkRevision>> {
+        self.read(|state| {
+            let mut records: Vec<&RemarkRevisionRecord> = state
+                .remark_revisions
+                .iter()
+                .filter(|record| record.remark_id == remark_id)
+                .collect();
+
+            records.sort_by_key(|record| record.number);
+
+            records
+                .into_iter()
+                .map(|record| record.clone().try_into())
+                .collect()
+        })
+    }
+}
+
+impl RestoreRemarkRevision for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn restore_remark_revision(
+        &self,
+        remark: &mut Remark,
+        number: i32,
+    ) -> ApplicationResult<()> {
+        self.transaction(|state| {
+            let RemarkRevisionRecord {
+                essence,
+                tags,
+                kind,
+                ..
+            } = state
+                .remark_revisions
+                .iter()
+                .find(|record| record.remark_id == remark.id() && record.number == number)
+                .cloned()
+                .ok_or(ApplicationError::NotFound)?;
+
+            remark.set_essence(RemarkEssence::new(essence)?);
+            remark.set_tags(
+                tags.into_iter()
+                    .map(TagTitle::new)
+                    .collect::<ApplicationResult<Vec<TagTitle>>>()?,
+            );
+            remark.set_kind(kind);
+
+            remarks::save_remark(state, remark)
+        })
+    }
+}
+
+pub fn create(state: &mut State, remark: &Remark) {
+    let number = state
+        .remark_revisions
+        .iter()
+        .filter(|record| record.remark_id == remark.id())
+        .map(|record| record.number)
+        .max()
+        .unwrap_or(0)
+        + 1;
+
+    state.remark_revisions.push(RemarkRevisionRecord {
+        remark_id: remark.id(),
+        number,
+        essence: remark.essence().to_string(),
+        tags: remark.tags().iter().map(ToString::to_string).collect(),
+        created_at: remark.updated_at(),
+        kind: remark.kind().clone(),
+    });
+}
+
+pub fn delete(state: &mut State, remark_id: Uuid) {
+    state
+        .remark_revisions
+        .retain(|record| record.remark_id != remark_id);
+}
+
+impl TryFrom<RemarkRevisionRecord> for RemarkRevision {
+    type Error = ApplicationError;
+
+    fn try_from(value: RemarkRevisionRecord) -> ApplicationResult<Self> {
+        let RemarkRevisionRecord {
+            remark_id,
+            number,
+            essence,
+            tags,
+            created_at,
+            kind,
+        } = value;
+
+        let revision = Self::new(RemarkRevisionAttributes {
+            remark_id,
+            number,
+            essence: RemarkEssence::new(essence)?,
+            tags: tags
+                .into_iter()
+                .map(TagTitle::new)
+                .collect::<ApplicationResult<Vec<TagTitle>>>()?,
+            created_at,
+            kind,
+        });
+
+        Ok(revision)
+    }
+}
+
+pub fn last_before(
+    state: &State,
+    remark_id: Uuid,
+    as_of: DateTime<Utc>,
+) -> Option<&RemarkRevisionRecord> {
+    state
+        .remark_revisions
+        .iter()
+        .filter(|record| record.remark_id == remark_id && record.created_at <= as_of)
+        .max_by_key(|record| record.number)
+}
@@ -0,0 +1,786 @@
+use crate::{
+    Repository, State, URL_SAFE_NO_PAD_ENGINE, remark_attachments, remark_content_hashes,
+    remark_idempotency_keys, remark_links, remark_revisions, resolve_page_size,
+    tags::{self, TagRecord},
+};
+use canopus_definitions::{
+    ApplicationError, ApplicationResult, Page, PageSize, Remark, RemarkAttributes, RemarkEssence,
+    RemarkKind, RemarkKindName, TagTitle,
+};
+use canopus_operations::remarks::{
+    AddRemarkTags, DeleteRemark, GetRemark, GetTrashedRemark, ImportRemark, InsertRemark,
+    ListRemarks, NewRemark, RemarksDatesFilter, RemarksOrderBy, RemarksPageParameters,
+    RemarksTagsFilter, RemoveRemarkTags, RestoreRemark, TrashRemark, UpdateRemark,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, str::FromStr};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct RemarkRecord {
+    pub id: Uuid,
+    pub essence: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub kind: RemarkKind,
+}
+
+impl AddRemarkTags for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn add_remark_tags(
+        &self,
+        remark: &mut Remark,
+        tags: &[TagTitle],
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
+        self.transaction(|state| {
+            check_updated_at(state, remark.id(), expected_updated_at)?;
+
+            *remark = find_remark(state, remark.id(), false)?;
+
+            let titles: Vec<TagTitle> = remark.tags().into_iter().chain(tags).cloned().collect();
+
+            save_remark_tags(state, remark, titles)
+        })
+    }
+}
+
+impl DeleteRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn delete_remark(
+        &self,
+        remark: &Remark,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
+        self.transaction(|state| {
+            check_updated_at(state, remark.id(), expected_updated_at)?;
+
+            if state.remarks.remove(&remark.id()).is_none() {
+                return Err(ApplicationError::NotFound);
+            }
+
+            unlink_tags(state, remark.id());
+            remark_revisions::delete(state, remark.id());
+            remark_links::delete(state, remark.id());
+            remark_idempotency_keys::delete(state, remark.id());
+            remark_content_hashes::delete(state, remark.id());
+            remark_attachments::delete(state, remark.id());
+            delete_unused_tags(state);
+
+            Ok(())
+        })
+    }
+}
+
+impl GetRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn get_remark(&self, id: Uuid) -> ApplicationResult<Remark> {
+        self.read(|state| find_remark(state, id, false))
+    }
+}
+
+impl GetTrashedRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn get_trashed_remark(&self, id: Uuid) -> ApplicationResult<Remark> {
+        self.read(|state| find_remark(state, id, true))
+    }
+}
+
+impl ImportRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn import_remark(&self, remark: &mut Remark) -> ApplicationResult<bool> {
+        self.transaction(|state| {
+            if state.remarks.contains_key(&remark.id()) {
+                return Ok(false);
+            }
+
+            let record = RemarkRecord {
+                id: remark.id(),
+                essence: remark.essence().to_string(),
+                created_at: remark.created_at(),
+                updated_at: remark.updated_at(),
+                deleted_at: remark.deleted_at(),
+                kind: remark.kind().clone(),
+            };
+
+            state.remarks.insert(record.id, record);
+
+            let tags = link_tags(state, remark.id(), remark.tags())?;
+            remark.set_tags(tags);
+
+            remark_revisions::create(state, remark);
+            remark_links::save(state, remark);
+
+            Ok(true)
+        })
+    }
+}
+
+impl InsertRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn insert_remark(&self, new_remark: NewRemark) -> ApplicationResult<Remark> {
+        self.transaction(|state| insert_remark(state, new_remark))
+    }
+}
+
+impl ListRemarks for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn list_remarks(
+        &self,
+        parameters: RemarksPageParameters,
+    ) -> ApplicationResult<Page<Remark>> {
+        let RemarksPageParameters {
+            page_token,
+            page_size,
+            order_by,
+            dates_filter,
+            tags_filter,
+            kind,
+            trashed,
+        } = parameters;
+
+        let page_token = page_token.map(TryInto::<PageToken>::try_into).transpose()?;
+
+        let page_size = resolve_page_size(
+            page_size,
+            page_token.as_ref().and_then(|token| token.page_size),
+        )?;
+
+        let order_by = match &page_token {
+            Some(token) => token.order_by()?,
+            None => order_by,
+        };
+
+        let last_id = page_token
+            .as_ref()
+            .map(|token| token.id)
+            .unwrap_or(Uuid::nil());
+
+        let last_position = page_token.as_ref().map(|token| token.position);
+
+        let (dates_filter, tags_filter, kind, as_of, total_size) = match page_token {
+            Some(token) => (
+                token.dates_filter,
+                token.tags_filter,
+                token.kind,
+                token.as_of,
+                token.total_size,
+            ),
+            None => (dates_filter.into(), tags_filter.into(), kind, None, None),
+        };
+
+        let as_of = as_of.unwrap_or_else(Utc::now);
+
+        self.read(|state| {
+            let mut records: Vec<(&RemarkRecord, DateTime<Utc>)> = state
+                .remarks
+                .values()
+                .filter_map(|record| {
+                    snapshot_updated_at(state, record, as_of).map(|updated_at| (record, updated_at))
+                })
+                .filter(|(record, _)| record.deleted_at.is_some() == trashed)
+                .filter(|(record, updated_at)| dates_filter.matches(record, *updated_at))
+                .filter(|(record, _)| kind.is_none_or(|kind| record.kind.name() == kind))
+                .filter(|(record, _)| tags_filter.matches(&remark_tag_titles(state, record.id)))
+                .map(|(record, updated_at)| match order_by {
+                    RemarksOrderBy::CreatedAtAsc | RemarksOrderBy::CreatedAtDesc => {
+                        (record, record.created_at)
+                    }
+                    RemarksOrderBy::UpdatedAtAsc | RemarksOrderBy::UpdatedAtDesc => {
+                        (record, updated_at)
+                    }
+                })
+                .collect();
+
+            let total_size = total_size.unwrap_or(records.len() as i64);
+            let descending = matches!(
+                order_by,
+                RemarksOrderBy::CreatedAtDesc | RemarksOrderBy::UpdatedAtDesc
+            );
+
+            records.sort_by(|(a, a_position), (b, b_position)| {
+                let ordering = a_position.cmp(b_position).then(a.id.cmp(&b.id));
+
+                if descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+
+            let rows: Vec<(&RemarkRecord, DateTime<Utc>)> = records
+                .into_iter()
+                .filter(|(record, position)| {
+                    last_position.is_none_or(|last_position| {
+                        let key = (*position, record.id);
+
+                        if descending {
+                            key < (last_position, last_id)
+                        } else {
+                            key > (last_position, last_id)
+                        }
+                    })
+                })
+                .take(*page_size as usize)
+                .collect();
+
+            let next_page_token = PageToken {
+                id: Uuid::nil(),
+                position: as_of,
+                order_by: Some(order_by.as_str().to_string()),
+                page_size: Some(*page_size),
+                dates_filter,
+                tags_filter,
+                kind,
+                as_of: Some(as_of),
+                total_size: Some(total_size),
+            }
+            .next(&rows, page_size)
+            .map(Into::into);
+
+            let items = rows
+                .into_iter()
+                .map(|(record, _)| to_remark(state, record))
+                .collect::<ApplicationResult<Vec<Remark>>>()?;
+
+            Ok(Page {
+                next_page_token,
+                items,
+                total_size: Some(total_size),
+            })
+        })
+    }
+}
+
+impl RemoveRemarkTags for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn remove_remark_tags(
+        &self,
+        remark: &mut Remark,
+        tags: &[TagTitle],
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
+        self.transaction(|state| {
+            check_updated_at(state, remark.id(), expected_updated_at)?;
+
+            *remark = find_remark(state, remark.id(), false)?;
+
+            let titles: Vec<TagTitle> = remark
+                .tags()
+                .into_iter()
+                .filter(|title| !tags.contains(title))
+                .cloned()
+                .collect();
+
+            save_remark_tags(state, remark, titles)
+        })
+    }
+}
+
+impl RestoreRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn restore_remark(
+        &self,
+        remark: &mut Remark,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
+        self.transaction(|state| {
+            check_updated_at(state, remark.id(), expected_updated_at)?;
+
+            let record = state
+                .remarks
+                .get_mut(&remark.id())
+                .filter(|record| record.deleted_at.is_some())
+                .ok_or(ApplicationError::NotFound)?;
+
+            record.deleted_at = None;
+
+            Ok(())
+        })?;
+
+        remark.set_deleted_at(None);
+
+        Ok(())
+    }
+}
+
+impl TrashRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn trash_remark(
+        &self,
+        remark: &mut Remark,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
+        let deleted_at = self.transaction(|state| {
+            check_updated_at(state, remark.id(), expected_updated_at)?;
+
+            let record = state
+                .remarks
+                .get_mut(&remark.id())
+                .filter(|record| record.deleted_at.is_none())
+                .ok_or(ApplicationError::NotFound)?;
+
+            let deleted_at = Utc::now();
+            record.deleted_at = Some(deleted_at);
+
+            Ok(deleted_at)
+        })?;
+
+        remark.set_deleted_at(Some(deleted_at));
+
+        Ok(())
+    }
+}
+
+impl UpdateRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn update_remark(&self, remark: &mut Remark) -> ApplicationResult<()> {
+        self.transaction(|state| save_remark(state, remark))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PageToken {
+    id: Uuid,
+
+    position: DateTime<Utc>,
+
+    #[serde(default)]
+    order_by: Option<String>,
+
+    #[serde(default)]
+    page_size: Option<i64>,
+
+    #[serde(default)]
+    dates_filter: DatesFilter,
+
+    #[serde(default)]
+    tags_filter: TagsFilter,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kind: Option<RemarkKindName>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    as_of: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    total_size: Option<i64>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+struct DatesFilter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_after: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_before: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_after: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_before: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+struct TagsFilter {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    include_all: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    include_any: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    exclude: Vec<String>,
+}
+
+impl DatesFilter {
+    fn matches(&self, record: &RemarkRecord, updated_at: DateTime<Utc>) -> bool {
+        self.created_after
+            .is_none_or(|created_after| record.created_at >= created_after)
+            && self
+                .created_before
+                .is_none_or(|created_before| record.created_at < created_before)
+            && self
+                .updated_after
+                .is_none_or(|updated_after| updated_at >= updated_after)
+            && self
+                .updated_before
+                .is_none_or(|updated_before| updated_at < updated_before)
+    }
+}
+
+impl TagsFilter {
+    fn matches(&self, titles: &[String]) -> bool {
+        let has_tag = |wanted: &String| {
+            titles
+                .iter()
+                .any(|title| tags::is_same_or_descendant(title, wanted))
+        };
+
+        self.include_all.iter().all(has_tag)
+            && (self.include_any.is_empty() || self.include_any.iter().any(has_tag))
+            && !self.exclude.iter().any(has_tag)
+    }
+}
+
+impl PageToken {
+    fn next(self, rows: &[(&RemarkRecord, DateTime<Utc>)], page_size: PageSize) -> Option<Self> {
+        if rows.len() < *page_size as usize {
+            return None;
+        }
+
+        rows.last().map(|(row, position)| PageToken {
+            id: row.id,
+            position: *position,
+            ..self
+        })
+    }
+
+    fn order_by(&self) -> ApplicationResult<RemarksOrderBy> {
+        self.order_by
+            .as_deref()
+            .map(str::parse)
+            .unwrap_or(Ok(RemarksOrderBy::default()))
+            .map_err(|_err| ApplicationError::invalid_argument("malformed remarks page token"))
+    }
+}
+
+impl From<RemarksDatesFilter> for DatesFilter {
+    fn from(value: RemarksDatesFilter) -> Self {
+        let RemarksDatesFilter {
+            created_after,
+            created_before,
+            updated_after,
+            updated_before,
+        } = value;
+
+        DatesFilter {
+            created_after,
+            created_before,
+            updated_after,
+            updated_before,
+        }
+    }
+}
+
+impl From<RemarksTagsFilter> for TagsFilter {
+    fn from(value: RemarksTagsFilter) -> Self {
+        let RemarksTagsFilter {
+            include_all,
+            include_any,
+            exclude,
+        } = value;
+
+        let into_strings =
+            |titles: BTreeSet<TagTitle>| titles.iter().map(ToString::to_string).collect();
+
+        TagsFilter {
+            include_all: into_strings(include_all),
+            include_any: into_strings(include_any),
+            exclude: into_strings(exclude),
+        }
+    }
+}
+
+impl FromStr for PageToken {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> eyre::Result<Self> {
+        use base64::Engine;
+
+        let json = URL_SAFE_NO_PAD_ENGINE.decode(s)?;
+        let token = serde_json::from_slice(&json)?;
+
+        Ok(token)
+    }
+}
+
+impl TryFrom<canopus_definitions::PageToken> for PageToken {
+    type Error = ApplicationError;
+
+    fn try_from(value: canopus_definitions::PageToken) -> ApplicationResult<Self> {
+        value
+            .parse()
+            .map_err(|_err| ApplicationError::invalid_argument("malformed remarks page token"))
+    }
+}
+
+impl std::fmt::Display for PageToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use base64::Engine;
+
+        let json = serde_json::to_string(&self).map_err(|_err| std::fmt::Error)?;
+
+        let encoded_json = URL_SAFE_NO_PAD_ENGINE.encode(json);
+
+        f.write_str(&encoded_json)
+    }
+}
+
+impl From<PageToken> for canopus_definitions::PageToken {
+    fn from(value: PageToken) -> Self {
+        value.to_string().into()
+    }
+}
+
+pub fn create_ancestor_tags(state: &mut State, title: &TagTitle) {
+    for ancestor in title.ancestors() {
+        if find_tag_by_title(state, &ancestor).is_none() {
+            insert_tag(state, ancestor);
+        }
+    }
+}
+
+pub fn delete_unused_tags(state: &mut State) {
+    let used_titles: BTreeSet<String> = state
+        .remarks_tags
+        .iter()
+        .filter_map(|(_, tag_id)| state.tags.get(tag_id))
+        .map(|tag| tag.title.clone())
+        .collect();
+
+    state.tags.retain(|_, tag| {
+        used_titles
+            .iter()
+            .any(|used_title| tags::is_same_or_descendant(used_title, &tag.title))
+    });
+
+    let tags = &state.tags;
+    state
+        .tag_aliases
+        .retain(|_, tag_id| tags.contains_key(tag_id));
+}
+
+// Remarks edited after the listing snapshot keep the position of their last revision before it
+fn snapshot_updated_at(
+    state: &State,
+    record: &RemarkRecord,
+    as_of: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    if record.updated_at <= as_of {
+        return Some(record.updated_at);
+    }
+
+    if record.created_at > as_of {
+        return None;
+    }
+
+    remark_revisions::last_before(state, record.id, as_of).map(|revision| revision.created_at)
+}
+
+pub fn remark_tag_titles(state: &State, remark_id: Uuid) -> Vec<String> {
+    let mut titles: Vec<String> = state
+        .remarks_tags
+        .iter()
+        .filter(|(id, _)| *id == remark_id)
+        .filter_map(|(_, tag_id)| state.tags.get(tag_id))
+        .map(|tag| tag.title.clone())
+        .collect();
+
+    titles.sort_by(|a, b| b.cmp(a));
+
+    titles
+}
+
+fn save_remark_tags(
+    state: &mut State,
+    remark: &mut Remark,
+    titles: Vec<TagTitle>,
+) -> ApplicationResult<()> {
+    let titles = BTreeSet::from_iter(titles);
+
+    if titles.iter().eq(remark.tags()) {
+        return Ok(());
+    }
+
+    remark.set_tags(titles.into_iter().collect());
+
+    save_remark(state, remark)
+}
+
+pub fn save_remark(state: &mut State, remark: &mut Remark) -> ApplicationResult<()> {
+    let record = state
+        .remarks
+        .get_mut(&remark.id())
+        .ok_or(ApplicationError::NotFound)?;
+
+    if record.updated_at != remark.updated_at() {
+        return Err(ApplicationError::Conflict);
+    }
+
+    let updated_at = Utc::now();
+
+    record.essence = remark.essence().to_string();
+    record.kind = remark.kind().clone();
+    record.updated_at = updated_at;
+
+    remark.set_updated_at(updated_at)?;
+
+    unlink_tags(state, remark.id());
+    let tags = link_tags(state, remark.id(), remark.tags())?;
+    delete_unused_tags(state);
+    remark.set_tags(tags);
+
+    remark_revisions::create(state, remark);
+    remark_links::save(state, remark);
+
+    Ok(())
+}
+
+pub fn to_remark(state: &State, record: &RemarkRecord) -> ApplicationResult<Remark> {
+    let RemarkRecord {
+        id,
+        essence,
+        created_at,
+        updated_at,
+        deleted_at,
+        kind,
+    } = record.clone();
+
+    let tags = remark_tag_titles(state, id)
+        .into_iter()
+        .map(TagTitle::new)
+        .collect::<ApplicationResult<Vec<TagTitle>>>()?;
+
+    let remark = Remark::new(RemarkAttributes {
+        id,
+        essence: RemarkEssence::new(essence)?,
+        tags,
+        created_at,
+        updated_at,
+        deleted_at,
+        kind,
+    });
+
+    Ok(remark)
+}
+
+fn find_or_create_tag(state: &mut State, title: &TagTitle) -> TagRecord {
+    if let Some(tag) = find_tag_by_title(state, title) {
+        return tag.clone();
+    }
+
+    if let Some(tag) = find_tag_by_alias(state, title) {
+        return tag.clone();
+    }
+
+    create_ancestor_tags(state, title);
+
+    insert_tag(state, title.clone())
+}
+
+fn check_updated_at(
+    state: &State,
+    id: Uuid,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<()> {
+    let Some(expected_updated_at) = expected_updated_at else {
+        return Ok(());
+    };
+
+    let record = state.remarks.get(&id).ok_or(ApplicationError::NotFound)?;
+
+    if record.updated_at != expected_updated_at {
+        return Err(ApplicationError::Conflict);
+    }
+
+    Ok(())
+}
+
+pub fn find_remark(state: &State, id: Uuid, trashed: bool) -> ApplicationResult<Remark> {
+    let record = state
+        .remarks
+        .get(&id)
+        .filter(|record| record.deleted_at.is_some() == trashed)
+        .ok_or(ApplicationError::NotFound)?;
+
+    to_remark(state, record)
+}
+
+fn find_tag_by_alias<'a>(state: &'a State, title: &TagTitle) -> Option<&'a TagRecord> {
+    state
+        .tag_aliases
+        .get(title.as_str())
+        .and_then(|tag_id| state.tags.get(tag_id))
+}
+
+fn find_tag_by_title<'a>(state: &'a State, title: &TagTitle) -> Option<&'a TagRecord> {
+    state.tags.values().find(|tag| tag.title == title.as_str())
+}
+
+pub fn insert_remark(state: &mut State, new_remark: NewRemark) -> ApplicationResult<Remark> {
+    let NewRemark {
+        essence,
+        tags,
+        created_at,
+        kind,
+    } = new_remark;
+
+    let created_at = created_at.unwrap_or_else(Utc::now);
+
+    let record = RemarkRecord {
+        id: Uuid::new_v4(),
+        essence: essence.to_string(),
+        created_at,
+        updated_at: created_at,
+        deleted_at: None,
+        kind: kind.clone(),
+    };
+
+    state.remarks.insert(record.id, record.clone());
+
+    let tags = link_tags(state, record.id, tags.iter().collect())?;
+
+    let remark = Remark::new(RemarkAttributes {
+        id: record.id,
+        essence,
+        tags,
+        created_at: record.created_at,
+        updated_at: record.updated_at,
+        deleted_at: None,
+        kind,
+    });
+
+    remark_revisions::create(state, &remark);
+    remark_links::save(state, &remark);
+
+    Ok(remark)
+}
+
+fn insert_tag(state: &mut State, title: TagTitle) -> TagRecord {
+    let now = Utc::now();
+
+    let tag = TagRecord {
+        id: Uuid::new_v4(),
+        title: title.to_string(),
+        created_at: now,
+        updated_at: now,
+    };
+
+    state.tags.insert(tag.id, tag.clone());
+
+    tag
+}
+
+fn link_tags(
+    state: &mut State,
+    remark_id: Uuid,
+    tags: Vec<&TagTitle>,
+) -> ApplicationResult<Vec<TagTitle>> {
+    let mut linked_titles = Vec::with_capacity(tags.len());
+
+    for title in tags {
+        let tag = find_or_create_tag(state, title);
+        state.remarks_tags.insert((remark_id, tag.id));
+        linked_titles.push(TagTitle::new(tag.title)?);
+    }
+
+    Ok(linked_titles)
+}
+
+fn unlink_tags(state: &mut State, remark_id: Uuid) {
+    state.remarks_tags.retain(|(id, _)| *id != remark_id);
+}
@@ -0,0 +1,249 @@
+use crate::{
+    Repository, URL_SAFE_NO_PAD_ENGINE,
+    remarks::{self, RemarkRecord},
+    resolve_page_size,
+};
+use canopus_definitions::{
+    ApplicationError, ApplicationResult, Page, PageSize, RemarkSearchHit, RemarkSearchHitAttributes,
+};
+use canopus_operations::remarks::{RemarksSearchParameters, SearchRemarks};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+// Every group of included terms must match, terms joined with OR form one group
+struct SearchQuery {
+    included: Vec<Vec<String>>,
+    excluded: Vec<String>,
+}
+
+impl SearchRemarks for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn search_remarks(
+        &self,
+        parameters: RemarksSearchParameters,
+    ) -> ApplicationResult<Page<RemarkSearchHit>> {
+        let RemarksSearchParameters {
+            query,
+            page_token,
+            page_size,
+        } = parameters;
+
+        let page_token = page_token.map(TryInto::<PageToken>::try_into).transpose()?;
+
+        let page_size = resolve_page_size(
+            page_size,
+            page_token.as_ref().and_then(|token| token.page_size),
+        )?;
+
+        let offset = page_token.map(|token| token.offset).unwrap_or_default();
+
+        let query = SearchQuery::parse(&query);
+
+        self.read(|state| {
+            let mut hits: Vec<(&RemarkRecord, f32)> = state
+                .remarks
+                .values()
+                .filter(|record| record.deleted_at.is_none())
+                .filter_map(|record| query.rank(&record.essence).map(|rank| (record, rank)))
+                .collect();
+
+            let total_size = hits.len() as i64;
+
+            hits.sort_by(|(a, a_rank), (b, b_rank)| b_rank.total_cmp(a_rank).then(a.id.cmp(&b.id)));
+
+            let rows: Vec<(&RemarkRecord, f32)> = hits
+                .into_iter()
+                .skip(offset as usize)
+                .take(*page_size as usize)
+                .collect();
+
+            let next_page_token = PageToken::from_rows(&rows, page_size, offset).map(Into::into);
+
+            let items = rows
+                .into_iter()
+                .map(|(record, rank)| {
+                    Ok(RemarkSearchHit::new(RemarkSearchHitAttributes {
+                        remark: remarks::to_remark(state, record)?,
+                        rank,
+                        snippet: query.highlight(&record.essence),
+                    }))
+                })
+                .collect::<ApplicationResult<Vec<RemarkSearchHit>>>()?;
+
+            Ok(Page {
+                next_page_token,
+                items,
+                total_size: Some(total_size),
+            })
+        })
+    }
+}
+
+impl SearchQuery {
+    fn parse(query: &str) -> Self {
+        let mut included: Vec<Vec<String>> = Vec::new();
+        let mut excluded = Vec::new();
+        let mut any = false;
+
+        for term in query.split_whitespace() {
+            if term.eq_ignore_ascii_case("or") {
+                any = !included.is_empty();
+                continue;
+            }
+
+            let (negated, term) = match term.strip_prefix('-') {
+                Some(term) => (true, term),
+                None => (false, term),
+            };
+
+            let term: String = term
+                .chars()
+                .filter(|c| c.is_alphanumeric())
+                .collect::<String>()
+                .to_lowercase();
+
+            if term.is_empty() {
+                continue;
+            }
+
+            if negated {
+                excluded.push(term);
+                continue;
+            }
+
+            match included.last_mut() {
+                Some(group) if any => group.push(term),
+                _ => included.push(vec![term]),
+            }
+
+            any = false;
+        }
+
+        Self { included, excluded }
+    }
+
+    fn highlight(&self, essence: &str) -> String {
+        let mut snippet = String::with_capacity(essence.len());
+        let mut word = String::new();
+
+        for c in essence.chars() {
+            if c.is_alphanumeric() {
+                word.push(c);
+                continue;
+            }
+
+            self.push_word(&mut snippet, &mut word);
+            snippet.push(c);
+        }
+
+        self.push_word(&mut snippet, &mut word);
+
+        snippet
+    }
+
+    fn push_word(&self, snippet: &mut String, word: &mut String) {
+        if word.is_empty() {
+            return;
+        }
+
+        if self.matches(&word.to_lowercase()) {
+            snippet.push_str("<b>");
+            snippet.push_str(word);
+            snippet.push_str("</b>");
+        } else {
+            snippet.push_str(word);
+        }
+
+        word.clear();
+    }
+
+    fn matches(&self, word: &str) -> bool {
+        self.included
+            .iter()
+            .flatten()
+            .any(|term| word.starts_with(term.as_str()))
+    }
+
+    fn rank(&self, essence: &str) -> Option<f32> {
+        let words: Vec<String> = essence
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+
+        let contains = |term: &String| words.iter().any(|word| word.starts_with(term.as_str()));
+
+        if self.included.is_empty()
+            || !self.included.iter().all(|group| group.iter().any(contains))
+            || self.excluded.iter().any(contains)
+        {
+            return None;
+        }
+
+        let matches = words.iter().filter(|word| self.matches(word)).count();
+
+        Some(matches as f32 / words.len() as f32)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PageToken {
+    offset: i64,
+
+    #[serde(default)]
+    page_size: Option<i64>,
+}
+
+impl PageToken {
+    fn from_rows(rows: &[(&RemarkRecord, f32)], page_size: PageSize, offset: i64) -> Option<Self> {
+        if rows.len() < *page_size as usize {
+            return None;
+        }
+
+        Some(PageToken {
+            offset: offset + rows.len() as i64,
+            page_size: Some(*page_size),
+        })
+    }
+}
+
+impl FromStr for PageToken {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> eyre::Result<Self> {
+        use base64::Engine;
+
+        let json = URL_SAFE_NO_PAD_ENGINE.decode(s)?;
+        let token = serde_json::from_slice(&json)?;
+
+        Ok(token)
+    }
+}
+
+impl TryFrom<canopus_definitions::PageToken> for PageToken {
+    type Error = ApplicationError;
+
+    fn try_from(value: canopus_definitions::PageToken) -> ApplicationResult<Self> {
+        value
+            .parse()
+            .map_err(|_err| ApplicationError::invalid_argument("malformed search page token"))
+    }
+}
+
+impl std::fmt::Display for PageToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use base64::Engine;
+
+        let json = serde_json::to_string(&self).map_err(|_err| std::fmt::Error)?;
+
+        let encoded_json = URL_SAFE_NO_PAD_ENGINE.encode(json);
+
+        f.write_str(&encoded_json)
+    }
+}
+
+impl From<PageToken> for canopus_definitions::PageToken {
+    fn from(value: PageToken) -> Self {
+        value.to_string().into()
+    }
+}
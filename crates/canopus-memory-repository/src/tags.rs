@@ -0,0 +1,440 @@
+use crate::{
+    Repository, State, URL_SAFE_NO_PAD_ENGINE, remark_revisions, remarks, resolve_page_size,
+};
+use canopus_definitions::{
+    ApplicationError, ApplicationResult, Page, PageSize, Tag, TagAttributes, TagTitle,
+};
+use canopus_operations::tags::{
+    GetTag, ListTagChildren, ListTags, MergeTags, SuggestTags, TagsOrderBy, TagsPageParameters,
+    UpdateTag,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, str::FromStr};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct TagRecord {
+    pub id: Uuid,
+    pub title: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl GetTag for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn get_tag(&self, id: Uuid) -> ApplicationResult<Tag> {
+        self.read(|state| {
+            state
+                .tags
+                .get(&id)
+                .ok_or(ApplicationError::NotFound)?
+                .to_tag(None)
+        })
+    }
+}
+
+impl ListTagChildren for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn list_tag_children(&self, tag: &Tag) -> ApplicationResult<Vec<Tag>> {
+        self.read(|state| {
+            let mut children: Vec<&TagRecord> = state
+                .tags
+                .values()
+                .filter(|child| {
+                    is_descendant(&child.title, tag.title())
+                        && !child.title[tag.title().len() + 1..].contains('/')
+                })
+                .collect();
+
+            children.sort_by(|a, b| a.title.cmp(&b.title));
+
+            children
+                .into_iter()
+                .map(|child| child.to_tag(None))
+                .collect()
+        })
+    }
+}
+
+impl ListTags for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn list_tags(&self, parameters: TagsPageParameters) -> ApplicationResult<Page<Tag>> {
+        let TagsPageParameters {
+            page_token,
+            page_size,
+            order_by,
+            with_usage_count,
+        } = parameters;
+
+        let page_token = page_token.map(TryInto::<PageToken>::try_into).transpose()?;
+
+        let page_size = resolve_page_size(
+            page_size,
+            page_token.as_ref().and_then(|token| token.page_size),
+        )?;
+
+        let order_by = match &page_token {
+            Some(token) => token.order_by()?,
+            None => order_by,
+        };
+
+        let last_id = page_token
+            .as_ref()
+            .map(|token| token.id)
+            .unwrap_or(Uuid::nil());
+
+        let last_created_at = page_token.as_ref().and_then(|token| token.created_at);
+        let last_title = page_token.as_ref().and_then(|token| token.title.clone());
+        let last_usage_count = page_token.as_ref().and_then(|token| token.usage_count);
+
+        let as_of = page_token
+            .as_ref()
+            .and_then(|token| token.as_of)
+            .unwrap_or_else(Utc::now);
+        let total_size = page_token.as_ref().and_then(|token| token.total_size);
+
+        self.read(|state| {
+            let mut records: Vec<(&TagRecord, i64)> = state
+                .tags
+                .values()
+                .filter(|tag| tag.created_at <= as_of)
+                .map(|tag| (tag, usage_count(state, tag, as_of)))
+                .collect();
+
+            let total_size = total_size.unwrap_or(records.len() as i64);
+
+            records.sort_by(|(a, a_usage_count), (b, b_usage_count)| {
+                let by_position = match order_by {
+                    TagsOrderBy::CreatedAtDesc => b.created_at.cmp(&a.created_at),
+                    TagsOrderBy::Title => a.title.cmp(&b.title),
+                    TagsOrderBy::UsageCount => b_usage_count.cmp(a_usage_count),
+                };
+
+                by_position.then(a.id.cmp(&b.id))
+            });
+
+            let rows: Vec<(&TagRecord, i64)> = records
+                .into_iter()
+                .filter(|(tag, usage_count)| match order_by {
+                    TagsOrderBy::CreatedAtDesc => last_created_at.is_none_or(|last_created_at| {
+                        tag.created_at < last_created_at
+                            || (tag.created_at == last_created_at && tag.id > last_id)
+                    }),
+                    TagsOrderBy::Title => last_title.as_ref().is_none_or(|last_title| {
+                        tag.title > *last_title || (tag.title == *last_title && tag.id > last_id)
+                    }),
+                    TagsOrderBy::UsageCount => last_usage_count.is_none_or(|last_usage_count| {
+                        *usage_count < last_usage_count
+                            || (*usage_count == last_usage_count && tag.id > last_id)
+                    }),
+                })
+                .take(*page_size as usize)
+                .collect();
+
+            let next_page_token =
+                PageToken::from_rows(&rows, page_size, order_by, as_of, total_size).map(Into::into);
+
+            let items = rows
+                .into_iter()
+                .map(|(tag, usage_count)| tag.to_tag(with_usage_count.then_some(usage_count)))
+                .collect::<ApplicationResult<Vec<Tag>>>()?;
+
+            Ok(Page {
+                next_page_token,
+                items,
+                total_size: Some(total_size),
+            })
+        })
+    }
+}
+
+impl MergeTags for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn merge_tags(&self, source: &Tag, target: &mut Tag) -> ApplicationResult<()> {
+        let updated_at = self.transaction(|state| {
+            if state
+                .tags
+                .values()
+                .any(|tag| is_descendant(&tag.title, source.title()))
+            {
+                return Err(ApplicationError::invalid_argument(
+                    "can't merge a tag that has child tags",
+                ));
+            }
+
+            let remark_ids: Vec<Uuid> = state
+                .remarks_tags
+                .iter()
+                .filter(|(_, tag_id)| *tag_id == source.id())
+                .map(|(remark_id, _)| *remark_id)
+                .collect();
+
+            for remark_id in remark_ids {
+                state.remarks_tags.remove(&(remark_id, source.id()));
+                state.remarks_tags.insert((remark_id, target.id()));
+            }
+
+            for tag_id in state.tag_aliases.values_mut() {
+                if *tag_id == source.id() {
+                    *tag_id = target.id();
+                }
+            }
+
+            state
+                .tag_aliases
+                .insert(source.title().to_string(), target.id());
+
+            state.tags.remove(&source.id());
+
+            let record = state
+                .tags
+                .get_mut(&target.id())
+                .ok_or(ApplicationError::NotFound)?;
+
+            let updated_at = Utc::now();
+            record.updated_at = updated_at;
+
+            remarks::delete_unused_tags(state);
+
+            Ok(updated_at)
+        })?;
+
+        target.set_updated_at(updated_at)
+    }
+}
+
+impl SuggestTags for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn suggest_tags(&self, prefix: &str, limit: PageSize) -> ApplicationResult<Vec<Tag>> {
+        self.read(|state| {
+            let mut records: Vec<(&TagRecord, i64)> = state
+                .tags
+                .values()
+                .filter(|tag| tag.title.starts_with(prefix))
+                .map(|tag| (tag, usage_count(state, tag, Utc::now())))
+                .collect();
+
+            records.sort_by(|(a, a_usage_count), (b, b_usage_count)| {
+                b_usage_count.cmp(a_usage_count).then(a.title.cmp(&b.title))
+            });
+
+            records
+                .into_iter()
+                .take(*limit as usize)
+                .map(|(tag, usage_count)| tag.to_tag(Some(usage_count)))
+                .collect()
+        })
+    }
+}
+
+impl UpdateTag for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn update_tag(&self, tag: &mut Tag) -> ApplicationResult<()> {
+        let updated_at = self.transaction(|state| {
+            let previous = state
+                .tags
+                .get(&tag.id())
+                .ok_or(ApplicationError::NotFound)?;
+
+            if previous.updated_at != tag.updated_at() {
+                return Err(ApplicationError::Conflict);
+            }
+
+            let previous_title = previous.title.clone();
+
+            let renamed_title =
+                |title: &str| format!("{}{}", tag.title(), &title[previous_title.len()..]);
+
+            let descendant_titles: BTreeSet<String> = state
+                .tags
+                .values()
+                .filter(|other| is_descendant(&other.title, &previous_title))
+                .map(|other| renamed_title(&other.title))
+                .collect();
+
+            let title_taken = state.tags.values().any(|other| {
+                other.id != tag.id()
+                    && (other.title == tag.title().as_str()
+                        || descendant_titles.contains(&other.title))
+            });
+
+            if title_taken {
+                return Err(ApplicationError::invalid_argument(
+                    "tag title is already taken, merge the tags instead",
+                ));
+            }
+
+            let updated_at = Utc::now();
+
+            for record in state.tags.values_mut() {
+                if record.id == tag.id() {
+                    record.title = tag.title().to_string();
+                } else if is_descendant(&record.title, &previous_title) {
+                    record.title = renamed_title(&record.title);
+                } else {
+                    continue;
+                }
+
+                record.updated_at = updated_at;
+            }
+
+            state.tag_aliases.remove(tag.title().as_str());
+
+            remarks::create_ancestor_tags(state, tag.title());
+
+            remarks::delete_unused_tags(state);
+
+            Ok(updated_at)
+        })?;
+
+        tag.set_updated_at(updated_at)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PageToken {
+    id: Uuid,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_at: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    usage_count: Option<i64>,
+
+    #[serde(default)]
+    order_by: Option<String>,
+
+    #[serde(default)]
+    page_size: Option<i64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    as_of: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    total_size: Option<i64>,
+}
+
+impl PageToken {
+    fn from_rows(
+        rows: &[(&TagRecord, i64)],
+        page_size: PageSize,
+        order_by: TagsOrderBy,
+        as_of: DateTime<Utc>,
+        total_size: i64,
+    ) -> Option<Self> {
+        if rows.len() < *page_size as usize {
+            return None;
+        }
+
+        rows.last().map(|(row, usage_count)| PageToken {
+            id: row.id,
+            created_at: (order_by == TagsOrderBy::CreatedAtDesc).then_some(row.created_at),
+            title: (order_by == TagsOrderBy::Title).then(|| row.title.clone()),
+            usage_count: (order_by == TagsOrderBy::UsageCount).then_some(*usage_count),
+            order_by: Some(order_by.as_str().to_string()),
+            page_size: Some(*page_size),
+            as_of: Some(as_of),
+            total_size: Some(total_size),
+        })
+    }
+
+    fn order_by(&self) -> ApplicationResult<TagsOrderBy> {
+        self.order_by
+            .as_deref()
+            .map(str::parse)
+            .unwrap_or(Ok(TagsOrderBy::default()))
+            .map_err(|_err| ApplicationError::invalid_argument("malformed tags page token"))
+    }
+}
+
+impl FromStr for PageToken {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> eyre::Result<Self> {
+        use base64::Engine;
+
+        let json = URL_SAFE_NO_PAD_ENGINE.decode(s)?;
+        let token = serde_json::from_slice(&json)?;
+
+        Ok(token)
+    }
+}
+
+impl TryFrom<canopus_definitions::PageToken> for PageToken {
+    type Error = ApplicationError;
+
+    fn try_from(value: canopus_definitions::PageToken) -> ApplicationResult<Self> {
+        value
+            .parse()
+            .map_err(|_err| ApplicationError::invalid_argument("malformed tags page token"))
+    }
+}
+
+impl std::fmt::Display for PageToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use base64::Engine;
+
+        let json = serde_json::to_string(&self).map_err(|_err| std::fmt::Error)?;
+
+        let encoded_json = URL_SAFE_NO_PAD_ENGINE.encode(json);
+
+        f.write_str(&encoded_json)
+    }
+}
+
+impl From<PageToken> for canopus_definitions::PageToken {
+    fn from(value: PageToken) -> Self {
+        value.to_string().into()
+    }
+}
+
+impl TagRecord {
+    fn to_tag(&self, usage_count: Option<i64>) -> ApplicationResult<Tag> {
+        let tag = Tag::new(TagAttributes {
+            id: self.id,
+            title: TagTitle::new(self.title.clone())?,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            usage_count,
+        });
+
+        Ok(tag)
+    }
+}
+
+pub fn is_same_or_descendant(title: &str, ancestor: &str) -> bool {
+    title == ancestor || is_descendant(title, ancestor)
+}
+
+fn is_descendant(title: &str, ancestor: &str) -> bool {
+    title
+        .strip_prefix(ancestor)
+        .is_some_and(|rest| rest.starts_with('/'))
+}
+
+// Usage counts are taken as of the listing snapshot, remarks edited after it count their last
+// revision before it
+fn usage_count(state: &State, tag: &TagRecord, as_of: DateTime<Utc>) -> i64 {
+    state
+        .remarks
+        .values()
+        .filter(|remark| {
+            remark
+                .deleted_at
+                .is_none_or(|deleted_at| deleted_at > as_of)
+        })
+        .filter(|remark| {
+            if remark.updated_at <= as_of {
+                return state.remarks_tags.contains(&(remark.id, tag.id));
+            }
+
+            remark.created_at <= as_of
+                && remark_revisions::last_before(state, remark.id, as_of)
+                    .is_some_and(|revision| revision.tags.contains(&tag.title))
+        })
+        .count() as i64
+}
@@ -1,41 +1,209 @@
 use canopus_definitions::{
-    ApplicationError, ApplicationResult, Page, PageToken, Remark, RemarkEssence, TagTitle,
+    ApplicationError, ApplicationResult, Page, PageSize, PageToken, Remark, RemarkAttachment,
+    RemarkAttributes, RemarkBulkOutcome, RemarkEssence, RemarkKind, RemarkKindName, RemarkLink,
+    RemarkRevision, RemarkSearchHit, TagTitle,
 };
-use std::future::Future;
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeSet, future::Future};
 use uuid::Uuid;
 
+pub const MAX_BULK_REMARKS: usize = 100;
+
+pub struct BulkNewRemark {
+    pub remark: NewRemark,
+    pub content_hash: Option<String>,
+}
+
+pub struct BulkNewRemarkAttributes {
+    pub essence: String,
+    pub tags: Vec<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub content_hash: Option<String>,
+    pub kind: RemarkKindAttributes,
+}
+
+pub struct ImportedRemarkAttributes {
+    pub id: Uuid,
+    pub essence: String,
+    pub tags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub kind: RemarkKindAttributes,
+}
+
 pub struct NewRemark {
     pub essence: RemarkEssence,
     pub tags: Vec<TagTitle>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub kind: RemarkKind,
 }
 
 pub struct NewRemarkAttributes {
     pub essence: String,
     pub tags: Vec<String>,
+    pub kind: RemarkKindAttributes,
+}
+
+pub struct NewRemarkAttachment {
+    pub remark_id: Uuid,
+    pub file_name: String,
+    pub media_type: String,
+    pub checksum: String,
+    pub content: Vec<u8>,
+}
+
+pub struct NewRemarkAttachmentAttributes {
+    pub file_name: String,
+    pub media_type: String,
+    pub content: Vec<u8>,
+}
+
+pub struct RemarkIdempotencyKey {
+    pub key: String,
+    pub request_hash: String,
+    pub created_after: DateTime<Utc>,
 }
 
 pub struct RemarkChanges {
     pub essence: Option<String>,
     pub tags: Option<Vec<String>>,
+    pub kind: Option<RemarkKindAttributes>,
+    pub expected_updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Default)]
+pub struct RemarkKindAttributes {
+    pub kind: Option<String>,
+    pub url: Option<String>,
+    pub source: Option<String>,
+    pub author: Option<String>,
+    pub done: Option<bool>,
+    pub due_date: Option<String>,
+}
+
+#[derive(Default)]
+pub struct RemarksDatesFilter {
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub updated_after: Option<DateTime<Utc>>,
+    pub updated_before: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RemarksOrderBy {
+    CreatedAtAsc,
+
+    #[default]
+    CreatedAtDesc,
+
+    UpdatedAtAsc,
+    UpdatedAtDesc,
 }
 
 #[derive(Default)]
 pub struct RemarksPageParameters {
     pub page_token: Option<PageToken>,
+    pub page_size: Option<PageSize>,
+    pub order_by: RemarksOrderBy,
+    pub dates_filter: RemarksDatesFilter,
+    pub tags_filter: RemarksTagsFilter,
+    pub kind: Option<RemarkKindName>,
+    pub trashed: bool,
+}
+
+#[derive(Default)]
+pub struct RemarksTagsFilter {
+    pub include_all: BTreeSet<TagTitle>,
+    pub include_any: BTreeSet<TagTitle>,
+    pub exclude: BTreeSet<TagTitle>,
+}
+
+pub struct RemarksSearchParameters {
+    pub query: String,
+    pub page_token: Option<PageToken>,
+    pub page_size: Option<PageSize>,
+}
+
+pub trait AddRemarkTags {
+    fn add_remark_tags(
+        &self,
+        remark: &mut Remark,
+        tags: &[TagTitle],
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> impl Future<Output = ApplicationResult<()>>;
+}
+
+pub trait DeleteRemarkAttachment {
+    fn delete_remark_attachment(
+        &self,
+        attachment: &RemarkAttachment,
+    ) -> impl Future<Output = ApplicationResult<()>>;
 }
 
 pub trait DeleteRemark {
-    fn delete_remark(&self, remark: &Remark) -> impl Future<Output = ApplicationResult<()>>;
+    fn delete_remark(
+        &self,
+        remark: &Remark,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> impl Future<Output = ApplicationResult<()>>;
 }
 
 pub trait GetRemark {
     fn get_remark(&self, id: Uuid) -> impl Future<Output = ApplicationResult<Remark>>;
 }
 
+pub trait GetRemarkAttachment {
+    fn get_remark_attachment(
+        &self,
+        remark_id: Uuid,
+        id: Uuid,
+    ) -> impl Future<Output = ApplicationResult<RemarkAttachment>>;
+}
+
+pub trait GetRemarkAttachmentContent {
+    fn get_remark_attachment_content(
+        &self,
+        attachment: &RemarkAttachment,
+    ) -> impl Future<Output = ApplicationResult<Vec<u8>>>;
+}
+
+pub trait GetTrashedRemark {
+    fn get_trashed_remark(&self, id: Uuid) -> impl Future<Output = ApplicationResult<Remark>>;
+}
+
+pub trait ImportRemark {
+    fn import_remark(&self, remark: &mut Remark) -> impl Future<Output = ApplicationResult<bool>>;
+}
+
 pub trait InsertRemark {
     fn insert_remark(&self, remark: NewRemark) -> impl Future<Output = ApplicationResult<Remark>>;
 }
 
+pub trait InsertIdempotentRemark {
+    fn insert_idempotent_remark(
+        &self,
+        remark: NewRemark,
+        key: RemarkIdempotencyKey,
+    ) -> impl Future<Output = ApplicationResult<Remark>>;
+}
+
+pub trait InsertRemarks {
+    fn insert_remarks(
+        &self,
+        remarks: Vec<BulkNewRemark>,
+        dry_run: bool,
+    ) -> impl Future<Output = ApplicationResult<Vec<RemarkBulkOutcome>>>;
+}
+
+pub trait InsertRemarkAttachment {
+    fn insert_remark_attachment(
+        &self,
+        attachment: NewRemarkAttachment,
+    ) -> impl Future<Output = ApplicationResult<RemarkAttachment>>;
+}
+
 pub trait UpdateRemark {
     fn update_remark(&self, remark: &mut Remark) -> impl Future<Output = ApplicationResult<()>>;
 }
@@ -47,6 +215,118 @@ pub trait ListRemarks {
     ) -> impl Future<Output = ApplicationResult<Page<Remark>>>;
 }
 
+pub trait ListRemarkAttachments {
+    fn list_remark_attachments(
+        &self,
+        remark_id: Uuid,
+    ) -> impl Future<Output = ApplicationResult<Vec<RemarkAttachment>>>;
+}
+
+pub trait ListRemarkBacklinks {
+    fn list_remark_backlinks(
+        &self,
+        remark_id: Uuid,
+    ) -> impl Future<Output = ApplicationResult<Vec<RemarkLink>>>;
+}
+
+pub trait ListRemarkLinks {
+    fn list_remark_links(
+        &self,
+        remark_id: Uuid,
+    ) -> impl Future<Output = ApplicationResult<Vec<RemarkLink>>>;
+}
+
+pub trait ListRemarkRevisions {
+    fn list_remark_revisions(
+        &self,
+        remark_id: Uuid,
+    ) -> impl Future<Output = ApplicationResult<Vec<RemarkRevision>>>;
+}
+
+pub trait RemoveRemarkTags {
+    fn remove_remark_tags(
+        &self,
+        remark: &mut Remark,
+        tags: &[TagTitle],
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> impl Future<Output = ApplicationResult<()>>;
+}
+
+pub trait RestoreRemarkRevision {
+    fn restore_remark_revision(
+        &self,
+        remark: &mut Remark,
+        number: i32,
+    ) -> impl Future<Output = ApplicationResult<()>>;
+}
+
+pub trait RestoreRemark {
+    fn restore_remark(
+        &self,
+        remark: &mut Remark,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> impl Future<Output = ApplicationResult<()>>;
+}
+
+pub trait SearchRemarks {
+    fn search_remarks(
+        &self,
+        parameters: RemarksSearchParameters,
+    ) -> impl Future<Output = ApplicationResult<Page<RemarkSearchHit>>>;
+}
+
+pub trait TrashRemark {
+    fn trash_remark(
+        &self,
+        remark: &mut Remark,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> impl Future<Output = ApplicationResult<()>>;
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn add_remark_tags(
+    id: Uuid,
+    tags: Vec<String>,
+    expected_updated_at: Option<DateTime<Utc>>,
+    repository: &(impl AddRemarkTags + GetRemark),
+) -> ApplicationResult<Remark> {
+    let tags = parse_tag_titles(tags)?;
+
+    let mut remark = repository.get_remark(id).await?;
+
+    repository
+        .add_remark_tags(&mut remark, &tags, expected_updated_at)
+        .await?;
+
+    Ok(remark)
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn create_idempotent_remark(
+    key: String,
+    window: TimeDelta,
+    attributes: NewRemarkAttributes,
+    repository: &impl InsertIdempotentRemark,
+) -> ApplicationResult<Remark> {
+    let key = key.trim().to_string();
+
+    if key.is_empty() || key.len() > 255 {
+        return Err(ApplicationError::invalid_argument(
+            "idempotency key must be between 1 and 255 characters",
+        ));
+    }
+
+    let new_remark = NewRemark::new(attributes)?;
+
+    let key = RemarkIdempotencyKey {
+        key,
+        request_hash: new_remark.request_hash(),
+        created_after: Utc::now() - window,
+    };
+
+    repository.insert_idempotent_remark(new_remark, key).await
+}
+
 #[tracing::instrument(skip_all)]
 pub async fn create_remark(
     attributes: NewRemarkAttributes,
@@ -57,31 +337,359 @@ pub async fn create_remark(
     repository.insert_remark(new_remark).await
 }
 
+#[tracing::instrument(skip_all)]
+pub async fn create_remarks(
+    attributes: Vec<BulkNewRemarkAttributes>,
+    dry_run: bool,
+    repository: &impl InsertRemarks,
+) -> ApplicationResult<Vec<RemarkBulkOutcome>> {
+    if attributes.is_empty() {
+        return Err(ApplicationError::invalid_argument("no remarks provided"));
+    }
+
+    if attributes.len() > MAX_BULK_REMARKS {
+        return Err(ApplicationError::InvalidArgument(format!(
+            "at most {} remarks can be created at once",
+            MAX_BULK_REMARKS
+        )));
+    }
+
+    let remarks = attributes
+        .into_iter()
+        .enumerate()
+        .map(|(index, attributes)| {
+            BulkNewRemark::new(attributes).map_err(|err| match err {
+                ApplicationError::InvalidArgument(description) => {
+                    ApplicationError::InvalidArgument(format!(
+                        "remark {}: {}",
+                        index + 1,
+                        description
+                    ))
+                }
+                err => err,
+            })
+        })
+        .collect::<ApplicationResult<Vec<BulkNewRemark>>>()?;
+
+    repository.insert_remarks(remarks, dry_run).await
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn create_remark_attachment(
+    id: Uuid,
+    attributes: NewRemarkAttachmentAttributes,
+    repository: &(impl GetRemark + InsertRemarkAttachment),
+) -> ApplicationResult<RemarkAttachment> {
+    let remark = repository.get_remark(id).await?;
+
+    let new_attachment = NewRemarkAttachment::new(remark.id(), attributes)?;
+
+    repository.insert_remark_attachment(new_attachment).await
+}
+
 #[tracing::instrument(skip_all)]
 pub async fn delete_remark(
     id: Uuid,
-    repository: &(impl DeleteRemark + GetRemark),
+    expected_updated_at: Option<DateTime<Utc>>,
+    repository: &(impl GetRemark + TrashRemark),
 ) -> ApplicationResult<Remark> {
-    let remark = repository.get_remark(id).await?;
+    let mut remark = repository.get_remark(id).await?;
+
+    repository
+        .trash_remark(&mut remark, expected_updated_at)
+        .await?;
+
+    Ok(remark)
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn delete_remark_permanently(
+    id: Uuid,
+    expected_updated_at: Option<DateTime<Utc>>,
+    repository: &(impl DeleteRemark + GetRemark + GetTrashedRemark),
+) -> ApplicationResult<Remark> {
+    let remark = match repository.get_remark(id).await {
+        Err(ApplicationError::NotFound) => repository.get_trashed_remark(id).await?,
+        result => result?,
+    };
 
-    repository.delete_remark(&remark).await?;
+    repository
+        .delete_remark(&remark, expected_updated_at)
+        .await?;
 
     Ok(remark)
 }
 
+#[tracing::instrument(skip_all)]
+pub async fn delete_remark_attachment(
+    id: Uuid,
+    attachment_id: Uuid,
+    repository: &(impl DeleteRemarkAttachment + GetRemark + GetRemarkAttachment),
+) -> ApplicationResult<RemarkAttachment> {
+    let remark = repository.get_remark(id).await?;
+
+    let attachment = repository
+        .get_remark_attachment(remark.id(), attachment_id)
+        .await?;
+
+    repository.delete_remark_attachment(&attachment).await?;
+
+    Ok(attachment)
+}
+
 #[tracing::instrument(skip_all)]
 pub async fn get_remark(id: Uuid, repository: &impl GetRemark) -> ApplicationResult<Remark> {
     repository.get_remark(id).await
 }
 
+#[tracing::instrument(skip_all)]
+pub async fn get_remark_attachment_content(
+    id: Uuid,
+    attachment_id: Uuid,
+    repository: &(impl GetRemark + GetRemarkAttachment + GetRemarkAttachmentContent),
+) -> ApplicationResult<(RemarkAttachment, Vec<u8>)> {
+    let remark = repository.get_remark(id).await?;
+
+    let attachment = repository
+        .get_remark_attachment(remark.id(), attachment_id)
+        .await?;
+
+    let content = repository
+        .get_remark_attachment_content(&attachment)
+        .await?;
+
+    Ok((attachment, content))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn import_remark(
+    attributes: ImportedRemarkAttributes,
+    repository: &impl ImportRemark,
+) -> ApplicationResult<bool> {
+    let ImportedRemarkAttributes {
+        id,
+        essence,
+        tags,
+        created_at,
+        updated_at,
+        deleted_at,
+        kind,
+    } = attributes;
+
+    if updated_at < created_at {
+        return Err(ApplicationError::invalid_argument(
+            "remark can't be updated before it was created",
+        ));
+    }
+
+    let mut remark = Remark::new(RemarkAttributes {
+        id,
+        essence: RemarkEssence::new(essence)?,
+        tags: tags
+            .into_iter()
+            .map(TagTitle::new)
+            .collect::<ApplicationResult<Vec<TagTitle>>>()?,
+        created_at,
+        updated_at,
+        deleted_at,
+        kind: kind.into_remark_kind(&RemarkKind::Note)?,
+    });
+
+    repository.import_remark(&mut remark).await
+}
+
 #[tracing::instrument(skip_all)]
 pub async fn list_remarks(
     parameters: RemarksPageParameters,
     repository: &impl ListRemarks,
 ) -> ApplicationResult<Page<Remark>> {
+    let RemarksTagsFilter {
+        include_all,
+        include_any,
+        exclude,
+    } = &parameters.tags_filter;
+
+    if include_all
+        .iter()
+        .chain(include_any)
+        .any(|tag| exclude.contains(tag))
+    {
+        return Err(ApplicationError::invalid_argument(
+            "tag can't be both included and excluded",
+        ));
+    }
+
+    let RemarksDatesFilter {
+        created_after,
+        created_before,
+        updated_after,
+        updated_before,
+    } = &parameters.dates_filter;
+
+    if matches!((created_after, created_before), (Some(after), Some(before)) if after >= before)
+        || matches!((updated_after, updated_before), (Some(after), Some(before)) if after >= before)
+    {
+        return Err(ApplicationError::invalid_argument(
+            "date range must end after it starts",
+        ));
+    }
+
     repository.list_remarks(parameters).await
 }
 
+#[tracing::instrument(skip_all)]
+pub async fn list_remark_attachments(
+    id: Uuid,
+    repository: &(impl GetRemark + ListRemarkAttachments),
+) -> ApplicationResult<Page<RemarkAttachment>> {
+    let remark = repository.get_remark(id).await?;
+
+    let items = repository.list_remark_attachments(remark.id()).await?;
+
+    Ok(Page {
+        total_size: Some(items.len() as i64),
+        items,
+        next_page_token: None,
+    })
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn list_remark_backlinks(
+    id: Uuid,
+    repository: &(impl GetRemark + ListRemarkBacklinks),
+) -> ApplicationResult<Page<RemarkLink>> {
+    let remark = repository.get_remark(id).await?;
+
+    let items = repository.list_remark_backlinks(remark.id()).await?;
+
+    Ok(Page {
+        total_size: Some(items.len() as i64),
+        items,
+        next_page_token: None,
+    })
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn list_remark_links(
+    id: Uuid,
+    repository: &(impl GetRemark + ListRemarkLinks),
+) -> ApplicationResult<Page<RemarkLink>> {
+    let remark = repository.get_remark(id).await?;
+
+    let items = repository.list_remark_links(remark.id()).await?;
+
+    Ok(Page {
+        total_size: Some(items.len() as i64),
+        items,
+        next_page_token: None,
+    })
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn list_remark_revisions(
+    id: Uuid,
+    repository: &(impl GetRemark + ListRemarkRevisions),
+) -> ApplicationResult<Page<RemarkRevision>> {
+    let remark = repository.get_remark(id).await?;
+
+    let items = repository.list_remark_revisions(remark.id()).await?;
+
+    Ok(Page {
+        total_size: Some(items.len() as i64),
+        items,
+        next_page_token: None,
+    })
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn remove_remark_tags(
+    id: Uuid,
+    tags: Vec<String>,
+    expected_updated_at: Option<DateTime<Utc>>,
+    repository: &(impl GetRemark + RemoveRemarkTags),
+) -> ApplicationResult<Remark> {
+    let tags = parse_tag_titles(tags)?;
+
+    let mut remark = repository.get_remark(id).await?;
+
+    repository
+        .remove_remark_tags(&mut remark, &tags, expected_updated_at)
+        .await?;
+
+    Ok(remark)
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn restore_remark_revision(
+    id: Uuid,
+    number: i32,
+    expected_updated_at: Option<DateTime<Utc>>,
+    repository: &(impl GetRemark + RestoreRemarkRevision),
+) -> ApplicationResult<Remark> {
+    let mut remark = repository.get_remark(id).await?;
+
+    if expected_updated_at.is_some_and(|updated_at| updated_at != remark.updated_at()) {
+        return Err(ApplicationError::Conflict);
+    }
+
+    repository
+        .restore_remark_revision(&mut remark, number)
+        .await?;
+
+    Ok(remark)
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn restore_remark(
+    id: Uuid,
+    expected_updated_at: Option<DateTime<Utc>>,
+    repository: &(impl GetTrashedRemark + RestoreRemark),
+) -> ApplicationResult<Remark> {
+    let mut remark = repository.get_trashed_remark(id).await?;
+
+    repository
+        .restore_remark(&mut remark, expected_updated_at)
+        .await?;
+
+    Ok(remark)
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn search_remarks(
+    parameters: RemarksSearchParameters,
+    repository: &impl SearchRemarks,
+) -> ApplicationResult<Page<RemarkSearchHit>> {
+    if parameters.query.trim().is_empty() {
+        return Err(ApplicationError::invalid_argument(
+            "search query can't be blank",
+        ));
+    }
+
+    repository.search_remarks(parameters).await
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn toggle_remark_item(
+    id: Uuid,
+    number: usize,
+    expected_updated_at: Option<DateTime<Utc>>,
+    repository: &(impl GetRemark + UpdateRemark),
+) -> ApplicationResult<Remark> {
+    let mut remark = repository.get_remark(id).await?;
+
+    if expected_updated_at.is_some_and(|updated_at| updated_at != remark.updated_at()) {
+        return Err(ApplicationError::Conflict);
+    }
+
+    let essence = remark.essence().toggle_checklist_item(number)?;
+
+    remark.set_essence(essence);
+
+    repository.update_remark(&mut remark).await?;
+
+    Ok(remark)
+}
+
 #[tracing::instrument(skip_all)]
 pub async fn update_remark(
     id: Uuid,
@@ -96,7 +704,16 @@ pub async fn update_remark(
 
     let mut remark = repository.get_remark(id).await?;
 
-    let RemarkChanges { essence, tags } = changes;
+    let RemarkChanges {
+        essence,
+        tags,
+        kind,
+        expected_updated_at,
+    } = changes;
+
+    if expected_updated_at.is_some_and(|updated_at| updated_at != remark.updated_at()) {
+        return Err(ApplicationError::Conflict);
+    }
 
     if let Some(essence) = essence {
         remark.set_essence(RemarkEssence::new(essence)?);
@@ -111,14 +728,67 @@ pub async fn update_remark(
         remark.set_tags(tags);
     }
 
+    if let Some(kind) = kind {
+        let kind = kind.into_remark_kind(remark.kind())?;
+
+        remark.set_kind(kind);
+    }
+
     repository.update_remark(&mut remark).await?;
 
     Ok(remark)
 }
 
+fn parse_tag_titles(tags: Vec<String>) -> ApplicationResult<Vec<TagTitle>> {
+    if tags.is_empty() {
+        return Err(ApplicationError::invalid_argument("no tags provided"));
+    }
+
+    tags.into_iter().map(TagTitle::new).collect()
+}
+
+impl BulkNewRemark {
+    fn new(attributes: BulkNewRemarkAttributes) -> ApplicationResult<Self> {
+        let BulkNewRemarkAttributes {
+            essence,
+            tags,
+            created_at,
+            content_hash,
+            kind,
+        } = attributes;
+
+        let content_hash = content_hash.map(|hash| hash.trim().to_string());
+
+        if content_hash
+            .as_ref()
+            .is_some_and(|hash| hash.is_empty() || hash.len() > 255)
+        {
+            return Err(ApplicationError::invalid_argument(
+                "content hash must be between 1 and 255 characters",
+            ));
+        }
+
+        let mut remark = NewRemark::new(NewRemarkAttributes {
+            essence,
+            tags,
+            kind,
+        })?;
+        remark.created_at = created_at;
+
+        Ok(BulkNewRemark {
+            remark,
+            content_hash,
+        })
+    }
+}
+
 impl NewRemark {
     fn new(attributes: NewRemarkAttributes) -> ApplicationResult<Self> {
-        let NewRemarkAttributes { essence, tags } = attributes;
+        let NewRemarkAttributes {
+            essence,
+            tags,
+            kind,
+        } = attributes;
 
         Ok(NewRemark {
             essence: RemarkEssence::new(essence)?,
@@ -126,6 +796,69 @@ impl NewRemark {
                 .into_iter()
                 .map(TagTitle::new)
                 .collect::<ApplicationResult<Vec<TagTitle>>>()?,
+            created_at: None,
+            kind: kind.into_remark_kind(&RemarkKind::Note)?,
+        })
+    }
+
+    fn request_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+
+        hasher.update(self.essence.as_str());
+
+        for tag in BTreeSet::from_iter(&self.tags) {
+            hasher.update([0]);
+            hasher.update(tag.as_str());
+        }
+
+        if let Some(payload) = self.kind.payload() {
+            hasher.update([1]);
+            hasher.update(self.kind.name().as_str());
+            hasher.update(payload);
+        }
+
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+impl NewRemarkAttachment {
+    fn new(remark_id: Uuid, attributes: NewRemarkAttachmentAttributes) -> ApplicationResult<Self> {
+        let NewRemarkAttachmentAttributes {
+            file_name,
+            media_type,
+            content,
+        } = attributes;
+
+        let file_name = file_name
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+
+        if file_name.is_empty() {
+            return Err(ApplicationError::invalid_argument(
+                "attachment file name can't be blank",
+            ));
+        }
+
+        if content.is_empty() {
+            return Err(ApplicationError::invalid_argument(
+                "attachment content can't be empty",
+            ));
+        }
+
+        let media_type = match media_type.trim() {
+            "" => "application/octet-stream".to_string(),
+            media_type => media_type.to_lowercase(),
+        };
+
+        Ok(NewRemarkAttachment {
+            remark_id,
+            file_name,
+            media_type,
+            checksum: format!("{:x}", Sha256::digest(&content)),
+            content,
         })
     }
 }
@@ -135,6 +868,34 @@ impl NewRemarkAttributes {
         NewRemarkAttributes {
             essence: String::new(),
             tags: Vec::new(),
+            kind: RemarkKindAttributes::default(),
+        }
+    }
+}
+
+impl RemarksOrderBy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CreatedAtAsc => "created_at_asc",
+            Self::CreatedAtDesc => "created_at_desc",
+            Self::UpdatedAtAsc => "updated_at_asc",
+            Self::UpdatedAtDesc => "updated_at_desc",
+        }
+    }
+}
+
+impl std::str::FromStr for RemarksOrderBy {
+    type Err = ApplicationError;
+
+    fn from_str(s: &str) -> ApplicationResult<Self> {
+        match s {
+            "created_at_asc" => Ok(Self::CreatedAtAsc),
+            "created_at_desc" => Ok(Self::CreatedAtDesc),
+            "updated_at_asc" => Ok(Self::UpdatedAtAsc),
+            "updated_at_desc" => Ok(Self::UpdatedAtDesc),
+            _ => Err(ApplicationError::invalid_argument(
+                "remarks can be ordered by created_at_asc, created_at_desc, updated_at_asc or updated_at_desc",
+            )),
         }
     }
 }
@@ -144,10 +905,125 @@ impl RemarkChanges {
         RemarkChanges {
             essence: None,
             tags: None,
+            kind: None,
+            expected_updated_at: None,
         }
     }
 
     fn is_empty(&self) -> bool {
-        self.essence.is_none() && self.tags.is_none()
+        self.essence.is_none() && self.tags.is_none() && self.kind.is_none()
+    }
+}
+
+impl RemarkKindAttributes {
+    fn into_remark_kind(self, current: &RemarkKind) -> ApplicationResult<RemarkKind> {
+        let RemarkKindAttributes {
+            kind,
+            url,
+            source,
+            author,
+            done,
+            due_date,
+        } = self;
+
+        let name = match kind {
+            Some(kind) => kind.parse()?,
+            None => current.name(),
+        };
+
+        if url.is_some() && name != RemarkKindName::Link {
+            return Err(ApplicationError::invalid_argument(
+                "url can only be set on link remarks",
+            ));
+        }
+
+        if (source.is_some() || author.is_some()) && name != RemarkKindName::Quote {
+            return Err(ApplicationError::invalid_argument(
+                "source and author can only be set on quote remarks",
+            ));
+        }
+
+        if (done.is_some() || due_date.is_some()) && name != RemarkKindName::Task {
+            return Err(ApplicationError::invalid_argument(
+                "done and due date can only be set on task remarks",
+            ));
+        }
+
+        match (name, current) {
+            (RemarkKindName::Note, _) => Ok(RemarkKind::Note),
+            (RemarkKindName::Link, current) => {
+                let url = match (url, current) {
+                    (Some(url), _) => url,
+                    (None, RemarkKind::Link { url, .. }) => url.clone(),
+                    (None, _) => {
+                        return Err(ApplicationError::invalid_argument(
+                            "link remark requires a url",
+                        ));
+                    }
+                };
+
+                parse_link(url)
+            }
+            (RemarkKindName::Quote, current) => {
+                let (current_source, current_author) = match current {
+                    RemarkKind::Quote { source, author } => (source.clone(), author.clone()),
+                    _ => (None, None),
+                };
+
+                Ok(RemarkKind::Quote {
+                    source: source.map_or(current_source, non_blank),
+                    author: author.map_or(current_author, non_blank),
+                })
+            }
+            (RemarkKindName::Task, current) => {
+                let (current_done, current_due_date) = match current {
+                    RemarkKind::Task { done, due_date } => (*done, *due_date),
+                    _ => (false, None),
+                };
+
+                let due_date = match due_date {
+                    Some(due_date) => non_blank(due_date)
+                        .map(|due_date| {
+                            NaiveDate::parse_from_str(&due_date, "%Y-%m-%d").map_err(|_| {
+                                ApplicationError::invalid_argument(
+                                    "task due date must be formatted as YYYY-MM-DD",
+                                )
+                            })
+                        })
+                        .transpose()?,
+                    None => current_due_date,
+                };
+
+                Ok(RemarkKind::Task {
+                    done: done.unwrap_or(current_done),
+                    due_date,
+                })
+            }
+        }
     }
 }
+
+fn non_blank(value: String) -> Option<String> {
+    let value = value.trim();
+
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+fn parse_link(url: String) -> ApplicationResult<RemarkKind> {
+    let url = url.trim().to_string();
+
+    let domain = url::Url::parse(&url)
+        .ok()
+        .filter(|parsed| matches!(parsed.scheme(), "http" | "https"))
+        .and_then(|parsed| {
+            parsed
+                .host_str()
+                .map(|host| host.trim_start_matches("www.").to_string())
+        })
+        .filter(|domain| !domain.is_empty())
+        .ok_or_else(|| {
+            ApplicationError::invalid_argument("link url must be a valid http or https URL")
+        })?;
+
+    Ok(RemarkKind::Link { url, domain })
+}
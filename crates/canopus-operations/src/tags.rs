@@ -1,4 +1,7 @@
-use canopus_definitions::{ApplicationResult, Page, PageToken, Tag, TagTitle};
+use canopus_definitions::{
+    ApplicationError, ApplicationResult, Page, PageSize, PageToken, Tag, TagTitle,
+};
+use chrono::{DateTime, Utc};
 use std::future::Future;
 use uuid::Uuid;
 
@@ -6,6 +9,10 @@ pub trait GetTag {
     fn get_tag(&self, tag_id: Uuid) -> impl Future<Output = ApplicationResult<Tag>>;
 }
 
+pub trait ListTagChildren {
+    fn list_tag_children(&self, tag: &Tag) -> impl Future<Output = ApplicationResult<Vec<Tag>>>;
+}
+
 pub trait ListTags {
     fn list_tags(
         &self,
@@ -13,13 +20,46 @@ pub trait ListTags {
     ) -> impl Future<Output = ApplicationResult<Page<Tag>>>;
 }
 
+pub trait MergeTags {
+    fn merge_tags(
+        &self,
+        source: &Tag,
+        target: &mut Tag,
+    ) -> impl Future<Output = ApplicationResult<()>>;
+}
+
+pub trait SuggestTags {
+    fn suggest_tags(
+        &self,
+        prefix: &str,
+        limit: PageSize,
+    ) -> impl Future<Output = ApplicationResult<Vec<Tag>>>;
+}
+
 pub trait UpdateTag {
     fn update_tag(&self, tag: &mut Tag) -> impl Future<Output = ApplicationResult<()>>;
 }
 
+pub struct TagChanges {
+    pub title: String,
+    pub expected_updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TagsOrderBy {
+    #[default]
+    CreatedAtDesc,
+
+    Title,
+    UsageCount,
+}
+
 #[derive(Default)]
 pub struct TagsPageParameters {
     pub page_token: Option<PageToken>,
+    pub page_size: Option<PageSize>,
+    pub order_by: TagsOrderBy,
+    pub with_usage_count: bool,
 }
 
 #[tracing::instrument(skip_all)]
@@ -27,6 +67,22 @@ pub async fn get_tag(id: Uuid, repository: &impl GetTag) -> ApplicationResult<Ta
     repository.get_tag(id).await
 }
 
+#[tracing::instrument(skip_all)]
+pub async fn list_tag_children(
+    id: Uuid,
+    repository: &(impl GetTag + ListTagChildren),
+) -> ApplicationResult<Page<Tag>> {
+    let tag = repository.get_tag(id).await?;
+
+    let items = repository.list_tag_children(&tag).await?;
+
+    Ok(Page {
+        total_size: Some(items.len() as i64),
+        items,
+        next_page_token: None,
+    })
+}
+
 #[tracing::instrument(skip_all)]
 pub async fn list_tags(
     parameters: TagsPageParameters,
@@ -35,18 +91,96 @@ pub async fn list_tags(
     repository.list_tags(parameters).await
 }
 
+#[tracing::instrument(skip_all)]
+pub async fn merge_tags(
+    source_id: Uuid,
+    target_id: Uuid,
+    repository: &(impl GetTag + MergeTags),
+) -> ApplicationResult<Tag> {
+    if source_id == target_id {
+        return Err(ApplicationError::invalid_argument(
+            "can't merge a tag into itself",
+        ));
+    }
+
+    let source = repository.get_tag(source_id).await?;
+    let mut target = repository.get_tag(target_id).await?;
+
+    repository.merge_tags(&source, &mut target).await?;
+
+    Ok(target)
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn suggest_tags(
+    prefix: String,
+    limit: Option<PageSize>,
+    repository: &impl SuggestTags,
+) -> ApplicationResult<Page<Tag>> {
+    let prefix = prefix
+        .to_lowercase()
+        .split('/')
+        .map(str::trim)
+        .collect::<Vec<&str>>()
+        .join("/");
+
+    let items = repository
+        .suggest_tags(&prefix, limit.unwrap_or_default())
+        .await?;
+
+    Ok(Page {
+        total_size: Some(items.len() as i64),
+        items,
+        next_page_token: None,
+    })
+}
+
 #[tracing::instrument(skip_all)]
 pub async fn update_tag(
     id: Uuid,
-    title: String,
+    changes: TagChanges,
     repository: &(impl UpdateTag + GetTag),
 ) -> ApplicationResult<Tag> {
+    let TagChanges {
+        title,
+        expected_updated_at,
+    } = changes;
+
     let title = TagTitle::new(title)?;
 
     let mut tag = repository.get_tag(id).await?;
 
+    if expected_updated_at.is_some_and(|updated_at| updated_at != tag.updated_at()) {
+        return Err(ApplicationError::Conflict);
+    }
+
     tag.set_title(title);
     repository.update_tag(&mut tag).await?;
 
     Ok(tag)
 }
+
+impl TagsOrderBy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CreatedAtDesc => "created_at_desc",
+            Self::Title => "title",
+            Self::UsageCount => "usage_count",
+        }
+    }
+}
+
+impl std::str::FromStr for TagsOrderBy {
+    type Err = ApplicationError;
+
+    fn from_str(s: &str) -> ApplicationResult<Self> {
+        match s {
+            "created_at_desc" => Ok(Self::CreatedAtDesc),
+            "title" => Ok(Self::Title),
+            "usage_count" => Ok(Self::UsageCount),
+            _ => Err(ApplicationError::invalid_argument(
+                "tags can be ordered by created_at_desc, title or usage_count",
+            )),
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use canopus_definitions::{PageSize, Remark, Tag};
+use canopus_operations::{
+    remarks::{
+        self, AddRemarkTags, DeleteRemark, DeleteRemarkAttachment, GetRemark, GetRemarkAttachment,
+        GetRemarkAttachmentContent, GetTrashedRemark, ImportRemark, InsertIdempotentRemark,
+        InsertRemark, InsertRemarkAttachment, InsertRemarks, ListRemarkAttachments,
+        ListRemarkBacklinks, ListRemarkLinks, ListRemarkRevisions, ListRemarks,
+        NewRemarkAttributes, RemoveRemarkTags, RestoreRemark, RestoreRemarkRevision, SearchRemarks,
+        TrashRemark, UpdateRemark,
+    },
+    tags::{
+        self, GetTag, ListTagChildren, ListTags, MergeTags, SuggestTags, TagsOrderBy,
+        TagsPageParameters, UpdateTag,
+    },
+};
+use canopus_repository::{AttachmentsStorage, Repository};
+use sqlx::{
+    PgPool,
+    postgres::{PgConnectOptions, PgPoolOptions},
+    sqlite::SqlitePoolOptions,
+};
+use std::str::FromStr;
+use uuid::Uuid;
+
+pub trait Backend:
+    AddRemarkTags
+    + DeleteRemark
+    + DeleteRemarkAttachment
+    + GetRemark
+    + GetRemarkAttachment
+    + GetRemarkAttachmentContent
+    + GetTrashedRemark
+    + ImportRemark
+    + InsertIdempotentRemark
+    + InsertRemark
+    + InsertRemarks
+    + InsertRemarkAttachment
+    + ListRemarkAttachments
+    + ListRemarkBacklinks
+    + ListRemarkLinks
+    + ListRemarkRevisions
+    + ListRemarks
+    + RemoveRemarkTags
+    + RestoreRemark
+    + RestoreRemarkRevision
+    + SearchRemarks
+    + TrashRemark
+    + UpdateRemark
+    + GetTag
+    + ListTagChildren
+    + ListTags
+    + MergeTags
+    + SuggestTags
+    + UpdateTag
+{
+}
+
+impl<T> Backend for T where
+    T: AddRemarkTags
+        + DeleteRemark
+        + DeleteRemarkAttachment
+        + GetRemark
+        + GetRemarkAttachment
+        + GetRemarkAttachmentContent
+        + GetTrashedRemark
+        + ImportRemark
+        + InsertIdempotentRemark
+        + InsertRemark
+        + InsertRemarks
+        + InsertRemarkAttachment
+        + ListRemarkAttachments
+        + ListRemarkBacklinks
+        + ListRemarkLinks
+        + ListRemarkRevisions
+        + ListRemarks
+        + RemoveRemarkTags
+        + RestoreRemark
+        + RestoreRemarkRevision
+        + SearchRemarks
+        + TrashRemark
+        + UpdateRemark
+        + GetTag
+        + ListTagChildren
+        + ListTags
+        + MergeTags
+        + SuggestTags
+        + UpdateTag
+{
+}
+
+pub struct TestDatabase {
+    pub repository: Repository,
+    schema: String,
+}
+
+impl TestDatabase {
+    pub async fn create() -> Self {
+        dotenvy::dotenv().ok();
+
+        let database_url = std::env::var("DATABASE_URL")
+            .ok()
+            .filter(|url| url.starts_with("postgres"))
+            .expect("DATABASE_URL should point to PostgreSQL");
+
+        let schema = format!("canopus_test_{}", Uuid::new_v4().simple());
+
+        let options = PgConnectOptions::from_str(&database_url)
+            .expect("DATABASE_URL should be valid")
+            .options([("search_path", schema.as_str())]);
+
+        let pool = PgPoolOptions::new()
+            .max_connections(2)
+            .connect_with(options)
+            .await
+            .expect("test database should be reachable");
+
+        sqlx::query(&format!("CREATE SCHEMA {schema}"))
+            .execute(&pool)
+            .await
+            .expect("test schema should be created");
+
+        sqlx::migrate!("../canopus-repository/migrations")
+            .run(&pool)
+            .await
+            .expect("migrations should run");
+
+        Self {
+            repository: Repository {
+                pool,
+                attachments_storage: AttachmentsStorage::Database,
+            },
+            schema,
+        }
+    }
+
+    pub async fn cleanup(self) {
+        let pool: PgPool = self.repository.pool;
+
+        sqlx::query(&format!("DROP SCHEMA {} CASCADE", self.schema))
+            .execute(&pool)
+            .await
+            .expect("test schema should be dropped");
+    }
+}
+
+pub async fn create_sqlite_repository() -> canopus_sqlite_repository::Repository {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("in-memory SQLite database should open");
+
+    let repository = canopus_sqlite_repository::Repository { pool };
+
+    repository.migrate().await.expect("migrations should run");
+
+    repository
+}
+
+macro_rules! backend_tests {
+    ($($name:ident),* $(,)?) => {
+        mod memory {
+            $(
+                #[tokio::test]
+                async fn $name() {
+                    super::$name(&canopus_memory_repository::Repository::default()).await;
+                }
+            )*
+        }
+
+        mod sqlite {
+            $(
+                #[tokio::test]
+                async fn $name() {
+                    let repository = crate::backends::create_sqlite_repository().await;
+
+                    super::$name(&repository).await;
+                }
+            )*
+        }
+
+        mod postgres {
+            $(
+                #[tokio::test]
+                #[ignore = "needs a PostgreSQL DATABASE_URL"]
+                async fn $name() {
+                    let database = crate::backends::TestDatabase::create().await;
+
+                    super::$name(&database.repository).await;
+
+                    database.cleanup().await;
+                }
+            )*
+        }
+    };
+}
+
+pub(crate) use backend_tests;
+
+pub async fn create_remark(repository: &impl Backend, essence: &str, tags: &[&str]) -> Remark {
+    remarks::create_remark(
+        NewRemarkAttributes {
+            essence: essence.to_string(),
+            tags: tags.iter().map(ToString::to_string).collect(),
+            kind: Default::default(),
+        },
+        repository,
+    )
+    .await
+    .expect("remark should be created")
+}
+
+pub async fn list_all_tags(repository: &impl Backend) -> Vec<Tag> {
+    tags::list_tags(
+        TagsPageParameters {
+            page_size: Some(PageSize::new(PageSize::MAX).expect("page size should be valid")),
+            order_by: TagsOrderBy::Title,
+            ..Default::default()
+        },
+        repository,
+    )
+    .await
+    .expect("tags should be listed")
+    .items
+}
+
+pub async fn tag_titles(repository: &impl Backend) -> Vec<String> {
+    list_all_tags(repository)
+        .await
+        .iter()
+        .map(|tag| tag.title().to_string())
+        .collect()
+}
@@ -0,0 +1,1342 @@
+mod backends;
+
+use backends::{Backend, backend_tests, create_remark, tag_titles};
+use canopus_definitions::{
+    ApplicationError, Page, PageSize, Remark, RemarkKind, RemarkKindName, RemarkSearchHit, TagTitle,
+};
+use canopus_operations::remarks::{
+    self, BulkNewRemarkAttributes, ImportedRemarkAttributes, NewRemarkAttachmentAttributes,
+    NewRemarkAttributes, RemarkChanges, RemarkKindAttributes, RemarksDatesFilter, RemarksOrderBy,
+    RemarksPageParameters, RemarksSearchParameters, RemarksTagsFilter,
+};
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+backend_tests!(
+    test_create_remark,
+    test_create_idempotent_remark,
+    test_get_missing_remark,
+    test_update_remark_deletes_unused_tags,
+    test_update_remark_detects_conflicts,
+    test_add_and_remove_remark_tags,
+    test_trash_and_restore_remark,
+    test_import_remark,
+    test_create_remarks_in_bulk,
+    test_delete_remark_permanently,
+    test_list_remarks_paginates,
+    test_list_remarks_pages_a_snapshot,
+    test_list_remarks_orders_ties_by_id,
+    test_list_remarks_filters_by_tags,
+    test_list_remarks_filters_by_dates,
+    test_remark_kinds,
+    test_toggle_remark_item,
+    test_restore_remark_revision,
+    test_remark_links_and_backlinks,
+    test_remark_attachments,
+    test_search_remarks,
+);
+
+async fn test_create_remark(repository: &impl Backend) {
+    let remark = create_remark(repository, "First remark", &["Project/Canopus", "rust"]).await;
+
+    assert_eq!(titles(&remark), ["project/canopus", "rust"]);
+
+    let found = remarks::get_remark(remark.id(), repository).await.unwrap();
+
+    assert_eq!(found.essence().as_str(), "First remark");
+    assert_eq!(titles(&found), ["project/canopus", "rust"]);
+    assert_eq!(
+        tag_titles(repository).await,
+        ["project", "project/canopus", "rust"]
+    );
+}
+
+async fn test_create_idempotent_remark(repository: &impl Backend) {
+    let attributes = |essence: &str| NewRemarkAttributes {
+        essence: essence.to_string(),
+        tags: vec!["b".to_string(), "a".to_string()],
+        kind: Default::default(),
+    };
+
+    let window = TimeDelta::days(1);
+
+    let created = remarks::create_idempotent_remark(
+        "key".to_string(),
+        window,
+        attributes("Remark"),
+        repository,
+    )
+    .await
+    .unwrap();
+
+    let repeated = remarks::create_idempotent_remark(
+        "key".to_string(),
+        window,
+        attributes("Remark"),
+        repository,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(repeated.id(), created.id());
+    assert_eq!(titles(&repeated), ["a", "b"]);
+
+    assert!(matches!(
+        remarks::create_idempotent_remark(
+            "key".to_string(),
+            window,
+            attributes("Other"),
+            repository
+        )
+        .await,
+        Err(ApplicationError::Conflict)
+    ));
+    assert!(matches!(
+        remarks::create_idempotent_remark(
+            " ".to_string(),
+            window,
+            attributes("Remark"),
+            repository
+        )
+        .await,
+        Err(ApplicationError::InvalidArgument(_))
+    ));
+
+    let expired = remarks::create_idempotent_remark(
+        "key".to_string(),
+        TimeDelta::zero(),
+        attributes("Other"),
+        repository,
+    )
+    .await
+    .unwrap();
+
+    assert_ne!(expired.id(), created.id());
+}
+
+async fn test_get_missing_remark(repository: &impl Backend) {
+    let result = remarks::get_remark(Uuid::new_v4(), repository).await;
+
+    assert!(matches!(result, Err(ApplicationError::NotFound)));
+}
+
+async fn test_update_remark_deletes_unused_tags(repository: &impl Backend) {
+    let remark = create_remark(repository, "Remark", &["old/child", "kept"]).await;
+
+    let changes = RemarkChanges {
+        essence: Some("Updated remark".to_string()),
+        tags: Some(vec!["kept".to_string(), "new".to_string()]),
+        kind: None,
+        expected_updated_at: None,
+    };
+
+    let updated = remarks::update_remark(remark.id(), changes, repository)
+        .await
+        .unwrap();
+
+    assert_eq!(updated.essence().as_str(), "Updated remark");
+    assert_eq!(titles(&updated), ["kept", "new"]);
+    assert!(updated.updated_at() >= remark.updated_at());
+    assert_eq!(tag_titles(repository).await, ["kept", "new"]);
+}
+
+async fn test_update_remark_detects_conflicts(repository: &impl Backend) {
+    let mut stale = create_remark(repository, "Remark", &[]).await;
+
+    let changes = RemarkChanges {
+        essence: Some("First edit".to_string()),
+        tags: None,
+        kind: None,
+        expected_updated_at: Some(stale.updated_at()),
+    };
+
+    remarks::update_remark(stale.id(), changes, repository)
+        .await
+        .unwrap();
+
+    let changes = RemarkChanges {
+        essence: Some("Second edit".to_string()),
+        tags: None,
+        kind: None,
+        expected_updated_at: Some(stale.updated_at()),
+    };
+
+    assert!(matches!(
+        remarks::update_remark(stale.id(), changes, repository).await,
+        Err(ApplicationError::Conflict)
+    ));
+    assert!(matches!(
+        repository.update_remark(&mut stale).await,
+        Err(ApplicationError::Conflict)
+    ));
+
+    let found = remarks::get_remark(stale.id(), repository).await.unwrap();
+
+    assert_eq!(found.essence().as_str(), "First edit");
+}
+
+async fn test_add_and_remove_remark_tags(repository: &impl Backend) {
+    let remark = create_remark(repository, "Remark", &["kept", "old/child"]).await;
+
+    let changes = RemarkChanges {
+        essence: Some("Edited elsewhere".to_string()),
+        tags: None,
+        kind: None,
+        expected_updated_at: None,
+    };
+
+    remarks::update_remark(remark.id(), changes, repository)
+        .await
+        .unwrap();
+
+    let tags = vec!["New".to_string(), "kept".to_string()];
+
+    let added = remarks::add_remark_tags(remark.id(), tags, None, repository)
+        .await
+        .unwrap();
+
+    assert_eq!(added.essence().as_str(), "Edited elsewhere");
+    assert_eq!(titles(&added), ["kept", "new", "old/child"]);
+
+    let tags = vec!["old/child".to_string(), "missing".to_string()];
+
+    let removed = remarks::remove_remark_tags(remark.id(), tags, None, repository)
+        .await
+        .unwrap();
+
+    assert_eq!(titles(&removed), ["kept", "new"]);
+    assert_eq!(tag_titles(repository).await, ["kept", "new"]);
+    assert!(matches!(
+        remarks::remove_remark_tags(
+            remark.id(),
+            vec!["kept".to_string()],
+            Some(remark.updated_at()),
+            repository
+        )
+        .await,
+        Err(ApplicationError::Conflict)
+    ));
+
+    let mut read = remarks::get_remark(remark.id(), repository).await.unwrap();
+    let expected_updated_at = Some(read.updated_at());
+
+    remarks::add_remark_tags(
+        remark.id(),
+        vec!["concurrent".to_string()],
+        None,
+        repository,
+    )
+    .await
+    .unwrap();
+
+    assert!(matches!(
+        repository
+            .remove_remark_tags(
+                &mut read,
+                &[TagTitle::new("kept".to_string()).unwrap()],
+                expected_updated_at
+            )
+            .await,
+        Err(ApplicationError::Conflict)
+    ));
+    assert_eq!(tag_titles(repository).await, ["concurrent", "kept", "new"]);
+
+    assert!(matches!(
+        remarks::add_remark_tags(
+            remark.id(),
+            vec!["stale".to_string()],
+            Some(remark.updated_at()),
+            repository
+        )
+        .await,
+        Err(ApplicationError::Conflict)
+    ));
+    assert!(matches!(
+        remarks::add_remark_tags(remark.id(), Vec::new(), None, repository).await,
+        Err(ApplicationError::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        remarks::remove_remark_tags(Uuid::new_v4(), vec!["kept".to_string()], None, repository)
+            .await,
+        Err(ApplicationError::NotFound)
+    ));
+}
+
+async fn test_import_remark(repository: &impl Backend) {
+    let created_at: DateTime<Utc> = "2025-01-01T08:30:00Z".parse().unwrap();
+
+    let attributes = |essence: &str| ImportedRemarkAttributes {
+        id: Uuid::from_u128(42),
+        essence: essence.to_string(),
+        tags: vec!["archive".to_string()],
+        created_at,
+        updated_at: created_at + TimeDelta::days(1),
+        deleted_at: Some(created_at + TimeDelta::days(2)),
+        kind: Default::default(),
+    };
+
+    assert!(
+        remarks::import_remark(attributes("Imported"), repository)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !remarks::import_remark(attributes("Changed"), repository)
+            .await
+            .unwrap()
+    );
+
+    let remark = repository
+        .get_trashed_remark(Uuid::from_u128(42))
+        .await
+        .unwrap();
+
+    assert_eq!(remark.essence().as_str(), "Imported");
+    assert_eq!(titles(&remark), ["archive"]);
+    assert_eq!(remark.created_at(), created_at);
+    assert_eq!(remark.updated_at(), created_at + TimeDelta::days(1));
+    assert_eq!(remark.deleted_at(), Some(created_at + TimeDelta::days(2)));
+
+    assert!(matches!(
+        remarks::import_remark(
+            ImportedRemarkAttributes {
+                id: Uuid::from_u128(43),
+                updated_at: created_at - TimeDelta::days(1),
+                ..attributes("Invalid")
+            },
+            repository
+        )
+        .await,
+        Err(ApplicationError::InvalidArgument(_))
+    ));
+}
+
+async fn test_create_remarks_in_bulk(repository: &impl Backend) {
+    let created_at: DateTime<Utc> = "2025-01-01T08:30:00Z".parse().unwrap();
+
+    let attributes = |essence: &str, content_hash: Option<&str>| BulkNewRemarkAttributes {
+        essence: essence.to_string(),
+        tags: vec!["notes".to_string()],
+        created_at: Some(created_at),
+        content_hash: content_hash.map(str::to_string),
+        kind: Default::default(),
+    };
+
+    let outcomes = remarks::create_remarks(vec![attributes("First", Some("a"))], true, repository)
+        .await
+        .unwrap();
+
+    assert!(outcomes[0].remark_id().is_none());
+    assert!(!outcomes[0].is_skipped());
+
+    let outcomes = remarks::create_remarks(
+        vec![attributes("First", Some("a")), attributes("Second", None)],
+        false,
+        repository,
+    )
+    .await
+    .unwrap();
+
+    let id = outcomes[0].remark_id().unwrap();
+    let remark = repository.get_remark(id).await.unwrap();
+
+    assert_eq!(remark.essence().as_str(), "First");
+    assert_eq!(titles(&remark), ["notes"]);
+    assert_eq!(remark.created_at(), created_at);
+    assert!(outcomes[1].remark_id().is_some());
+
+    let outcomes =
+        remarks::create_remarks(vec![attributes("Changed", Some("a"))], false, repository)
+            .await
+            .unwrap();
+
+    assert!(outcomes[0].is_skipped());
+    assert_eq!(outcomes[0].remark_id(), Some(id));
+
+    assert!(matches!(
+        remarks::create_remarks(Vec::new(), false, repository).await,
+        Err(ApplicationError::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        remarks::create_remarks(
+            (0..=remarks::MAX_BULK_REMARKS)
+                .map(|_| attributes("Remark", None))
+                .collect(),
+            false,
+            repository
+        )
+        .await,
+        Err(ApplicationError::InvalidArgument(_))
+    ));
+}
+
+async fn test_trash_and_restore_remark(repository: &impl Backend) {
+    let remark = create_remark(repository, "Remark", &[]).await;
+
+    remarks::add_remark_tags(remark.id(), vec!["edited".to_string()], None, repository)
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        remarks::delete_remark(remark.id(), Some(remark.updated_at()), repository).await,
+        Err(ApplicationError::Conflict)
+    ));
+
+    let trashed = remarks::delete_remark(remark.id(), None, repository)
+        .await
+        .unwrap();
+
+    assert!(trashed.is_trashed());
+    assert!(matches!(
+        remarks::get_remark(remark.id(), repository).await,
+        Err(ApplicationError::NotFound)
+    ));
+    assert!(matches!(
+        remarks::delete_remark(remark.id(), None, repository).await,
+        Err(ApplicationError::NotFound)
+    ));
+    assert!(
+        list_remarks(repository, RemarksPageParameters::default())
+            .await
+            .is_empty()
+    );
+    assert_eq!(tag_titles(repository).await, ["edited"]);
+
+    let parameters = RemarksPageParameters {
+        trashed: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        ids(&list_remarks(repository, parameters).await),
+        [remark.id()]
+    );
+
+    let restored = remarks::restore_remark(remark.id(), None, repository)
+        .await
+        .unwrap();
+
+    assert!(!restored.is_trashed());
+    assert!(remarks::get_remark(remark.id(), repository).await.is_ok());
+    assert!(matches!(
+        remarks::restore_remark(remark.id(), None, repository).await,
+        Err(ApplicationError::NotFound)
+    ));
+}
+
+async fn test_delete_remark_permanently(repository: &impl Backend) {
+    let remark = create_remark(repository, "Remark", &["gone"]).await;
+    create_remark(repository, "Kept remark", &["kept"]).await;
+
+    remarks::delete_remark(remark.id(), None, repository)
+        .await
+        .unwrap();
+    remarks::delete_remark_permanently(remark.id(), None, repository)
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        remarks::delete_remark_permanently(remark.id(), None, repository).await,
+        Err(ApplicationError::NotFound)
+    ));
+    assert_eq!(tag_titles(repository).await, ["kept"]);
+}
+
+async fn test_list_remarks_paginates(repository: &impl Backend) {
+    let mut created = Vec::new();
+
+    for number in 1..=5 {
+        let remark = create_remark(repository, &format!("Remark {number}"), &[]).await;
+        created.push(remark.id());
+    }
+
+    let mut listed = Vec::new();
+    let mut page_token = None;
+
+    loop {
+        let parameters = RemarksPageParameters {
+            page_size: page_token.is_none().then(|| PageSize::new(2).unwrap()),
+            page_token,
+            order_by: RemarksOrderBy::CreatedAtAsc,
+            ..Default::default()
+        };
+
+        let page = remarks::list_remarks(parameters, repository).await.unwrap();
+
+        assert_eq!(page.total_size, Some(5));
+        assert!(page.items.len() <= 2);
+
+        listed.extend(ids(&page.items));
+
+        match page.next_page_token {
+            Some(token) => page_token = Some(token),
+            None => break,
+        }
+    }
+
+    assert_eq!(listed, created);
+
+    let newest = list_remarks(repository, RemarksPageParameters::default()).await;
+
+    assert_eq!(newest.first().map(Remark::id), created.last().copied());
+    assert!(PageSize::new(0).is_err());
+    assert!(PageSize::new(PageSize::MAX + 1).is_err());
+}
+
+async fn test_list_remarks_pages_a_snapshot(repository: &impl Backend) {
+    let created_at = DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+        .unwrap()
+        .to_utc();
+
+    for number in 1..=3 {
+        let attributes = ImportedRemarkAttributes {
+            id: Uuid::from_u128(number),
+            essence: format!("Remark {number}"),
+            tags: vec![],
+            created_at,
+            updated_at: created_at + TimeDelta::days(number as i64),
+            deleted_at: None,
+            kind: Default::default(),
+        };
+
+        remarks::import_remark(attributes, repository)
+            .await
+            .unwrap();
+    }
+
+    let list_while_editing = async |order_by: RemarksOrderBy, edited: u128| {
+        let mut listed = Vec::new();
+        let mut page_token = None;
+
+        loop {
+            let parameters = RemarksPageParameters {
+                page_token,
+                page_size: Some(PageSize::new(1).unwrap()),
+                order_by,
+                ..Default::default()
+            };
+
+            let page = remarks::list_remarks(parameters, repository).await.unwrap();
+
+            assert_eq!(page.total_size, Some(3));
+
+            listed.extend(ids(&page.items));
+
+            if listed.len() == 1 {
+                let changes = RemarkChanges {
+                    essence: Some("Edited".to_string()),
+                    tags: None,
+                    kind: None,
+                    expected_updated_at: None,
+                };
+
+                remarks::update_remark(Uuid::from_u128(edited), changes, repository)
+                    .await
+                    .unwrap();
+            }
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+
+        listed
+    };
+
+    assert_eq!(
+        list_while_editing(RemarksOrderBy::UpdatedAtDesc, 1).await,
+        [3, 2, 1].map(Uuid::from_u128)
+    );
+    assert_eq!(
+        list_while_editing(RemarksOrderBy::UpdatedAtAsc, 2).await,
+        [2, 3, 1].map(Uuid::from_u128)
+    );
+}
+
+async fn test_list_remarks_orders_ties_by_id(repository: &impl Backend) {
+    let tied_at: DateTime<Utc> = "2025-01-02T00:00:00Z".parse().unwrap();
+    let earlier_at = tied_at - TimeDelta::days(1);
+
+    for (number, timestamp) in [(1, tied_at), (2, earlier_at), (3, tied_at), (4, tied_at)] {
+        let attributes = ImportedRemarkAttributes {
+            id: Uuid::from_u128(number),
+            essence: format!("Remark {number}"),
+            tags: vec![],
+            created_at: timestamp,
+            updated_at: timestamp,
+            deleted_at: None,
+            kind: Default::default(),
+        };
+
+        remarks::import_remark(attributes, repository)
+            .await
+            .unwrap();
+    }
+
+    let list_one_by_one = async |order_by: RemarksOrderBy| {
+        let mut listed = Vec::new();
+        let mut page_token = None;
+
+        loop {
+            let parameters = RemarksPageParameters {
+                page_token,
+                page_size: Some(PageSize::new(1).unwrap()),
+                order_by,
+                ..Default::default()
+            };
+
+            let page = remarks::list_remarks(parameters, repository).await.unwrap();
+
+            listed.extend(ids(&page.items));
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+
+        listed
+    };
+
+    let ascending = [2, 1, 3, 4].map(Uuid::from_u128);
+    let descending = [4, 3, 1, 2].map(Uuid::from_u128);
+
+    assert_eq!(
+        list_one_by_one(RemarksOrderBy::CreatedAtAsc).await,
+        ascending
+    );
+    assert_eq!(
+        list_one_by_one(RemarksOrderBy::CreatedAtDesc).await,
+        descending
+    );
+    assert_eq!(
+        list_one_by_one(RemarksOrderBy::UpdatedAtAsc).await,
+        ascending
+    );
+    assert_eq!(
+        list_one_by_one(RemarksOrderBy::UpdatedAtDesc).await,
+        descending
+    );
+}
+
+async fn test_list_remarks_filters_by_tags(repository: &impl Backend) {
+    let rust = create_remark(repository, "Rust", &["lang/rust"]).await.id();
+    let go = create_remark(repository, "Go", &["lang/go"]).await.id();
+    let misc = create_remark(repository, "Misc", &["misc"]).await.id();
+    let tooling = create_remark(repository, "Cargo", &["lang/rust", "tooling"])
+        .await
+        .id();
+
+    let filtered = async |tags_filter: RemarksTagsFilter| {
+        let parameters = RemarksPageParameters {
+            tags_filter,
+            ..Default::default()
+        };
+
+        ids(&list_remarks(repository, parameters).await)
+            .into_iter()
+            .collect::<BTreeSet<Uuid>>()
+    };
+
+    let include_all = RemarksTagsFilter {
+        include_all: tag_set(&["lang"]),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        filtered(include_all).await,
+        BTreeSet::from([rust, go, tooling])
+    );
+
+    let include_all = RemarksTagsFilter {
+        include_all: tag_set(&["lang/rust", "tooling"]),
+        ..Default::default()
+    };
+
+    assert_eq!(filtered(include_all).await, BTreeSet::from([tooling]));
+
+    let exclude = RemarksTagsFilter {
+        exclude: tag_set(&["lang/go"]),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        filtered(exclude).await,
+        BTreeSet::from([rust, misc, tooling])
+    );
+
+    let include_any = RemarksTagsFilter {
+        include_any: tag_set(&["lang/go", "misc"]),
+        ..Default::default()
+    };
+
+    assert_eq!(filtered(include_any).await, BTreeSet::from([go, misc]));
+
+    let combined = RemarksTagsFilter {
+        include_all: tag_set(&["lang"]),
+        include_any: tag_set(&["lang/rust", "misc"]),
+        exclude: tag_set(&["tooling"]),
+    };
+
+    assert_eq!(filtered(combined).await, BTreeSet::from([rust]));
+
+    let conflicting = RemarksPageParameters {
+        tags_filter: RemarksTagsFilter {
+            include_all: tag_set(&["misc"]),
+            exclude: tag_set(&["misc"]),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        remarks::list_remarks(conflicting, repository).await,
+        Err(ApplicationError::InvalidArgument(_))
+    ));
+}
+
+async fn test_list_remarks_filters_by_dates(repository: &impl Backend) {
+    let middle: DateTime<Utc> = "2025-01-02T00:00:00Z".parse().unwrap();
+    let earlier = import_remark(repository, "Earlier", middle - TimeDelta::days(1)).await;
+    let later = import_remark(repository, "Later", middle + TimeDelta::days(1)).await;
+
+    let created_after = RemarksPageParameters {
+        dates_filter: RemarksDatesFilter {
+            created_after: Some(middle),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        ids(&list_remarks(repository, created_after).await),
+        [later.id()]
+    );
+
+    let created_before = RemarksPageParameters {
+        dates_filter: RemarksDatesFilter {
+            created_before: Some(middle),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        ids(&list_remarks(repository, created_before).await),
+        [earlier.id()]
+    );
+
+    let changes = RemarkChanges {
+        essence: Some("Earlier, edited".to_string()),
+        ..RemarkChanges::empty()
+    };
+
+    remarks::update_remark(earlier.id(), changes, repository)
+        .await
+        .unwrap();
+
+    let updated_after = RemarksPageParameters {
+        dates_filter: RemarksDatesFilter {
+            updated_after: Some(middle + TimeDelta::days(2)),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        ids(&list_remarks(repository, updated_after).await),
+        [earlier.id()]
+    );
+
+    let updated_before = RemarksPageParameters {
+        dates_filter: RemarksDatesFilter {
+            updated_before: Some(middle + TimeDelta::days(2)),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        ids(&list_remarks(repository, updated_before).await),
+        [later.id()]
+    );
+
+    let first_page = RemarksPageParameters {
+        page_size: Some(PageSize::new(1).unwrap()),
+        dates_filter: RemarksDatesFilter {
+            created_after: Some(middle - TimeDelta::days(2)),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let page = remarks::list_remarks(first_page, repository).await.unwrap();
+
+    assert_eq!(page.total_size, Some(2));
+    assert_eq!(ids(&page.items), [later.id()]);
+
+    let second_page = RemarksPageParameters {
+        page_token: page.next_page_token,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        ids(&list_remarks(repository, second_page).await),
+        [earlier.id()]
+    );
+
+    let inverted = RemarksPageParameters {
+        dates_filter: RemarksDatesFilter {
+            created_after: Some(middle),
+            created_before: Some(middle),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        remarks::list_remarks(inverted, repository).await,
+        Err(ApplicationError::InvalidArgument(_))
+    ));
+}
+
+async fn test_remark_kinds(repository: &impl Backend) {
+    let attributes = |essence: &str, kind: RemarkKindAttributes| NewRemarkAttributes {
+        essence: essence.to_string(),
+        tags: vec![],
+        kind,
+    };
+
+    let link = remarks::create_remark(
+        attributes(
+            "Rust",
+            RemarkKindAttributes {
+                kind: Some("link".to_string()),
+                url: Some(" https://www.rust-lang.org/learn ".to_string()),
+                ..Default::default()
+            },
+        ),
+        repository,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        link.kind(),
+        &RemarkKind::Link {
+            url: "https://www.rust-lang.org/learn".to_string(),
+            domain: "rust-lang.org".to_string(),
+        }
+    );
+
+    let task = remarks::create_remark(
+        attributes(
+            "Ship it",
+            RemarkKindAttributes {
+                kind: Some("task".to_string()),
+                due_date: Some("2025-05-10".to_string()),
+                ..Default::default()
+            },
+        ),
+        repository,
+    )
+    .await
+    .unwrap();
+
+    let note = create_remark(repository, "Note", &[]).await;
+
+    assert_eq!(note.kind(), &RemarkKind::Note);
+
+    let page = remarks::list_remarks(
+        RemarksPageParameters {
+            kind: Some(RemarkKindName::Task),
+            ..Default::default()
+        },
+        repository,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(ids(&page.items), [task.id()]);
+    assert_eq!(page.total_size, Some(1));
+
+    let changes = RemarkChanges {
+        kind: Some(RemarkKindAttributes {
+            done: Some(true),
+            ..Default::default()
+        }),
+        ..RemarkChanges::empty()
+    };
+
+    remarks::update_remark(task.id(), changes, repository)
+        .await
+        .unwrap();
+
+    let task = remarks::add_remark_tags(task.id(), vec!["work".to_string()], None, repository)
+        .await
+        .unwrap();
+
+    let expected = RemarkKind::Task {
+        done: true,
+        due_date: NaiveDate::from_ymd_opt(2025, 5, 10),
+    };
+
+    assert_eq!(task.kind(), &expected);
+    assert_eq!(
+        remarks::get_remark(task.id(), repository)
+            .await
+            .unwrap()
+            .kind(),
+        &expected
+    );
+
+    let changes = RemarkChanges {
+        kind: Some(RemarkKindAttributes {
+            kind: Some("quote".to_string()),
+            author: Some("Ada Lovelace".to_string()),
+            ..Default::default()
+        }),
+        ..RemarkChanges::empty()
+    };
+
+    let quote = remarks::update_remark(note.id(), changes, repository)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        quote.kind(),
+        &RemarkKind::Quote {
+            source: None,
+            author: Some("Ada Lovelace".to_string()),
+        }
+    );
+
+    for kind in [
+        RemarkKindAttributes {
+            kind: Some("link".to_string()),
+            url: Some("ftp://example.com".to_string()),
+            ..Default::default()
+        },
+        RemarkKindAttributes {
+            kind: Some("link".to_string()),
+            ..Default::default()
+        },
+        RemarkKindAttributes {
+            url: Some("https://example.com".to_string()),
+            ..Default::default()
+        },
+        RemarkKindAttributes {
+            kind: Some("task".to_string()),
+            due_date: Some("tomorrow".to_string()),
+            ..Default::default()
+        },
+        RemarkKindAttributes {
+            kind: Some("event".to_string()),
+            ..Default::default()
+        },
+    ] {
+        assert!(matches!(
+            remarks::create_remark(attributes("Invalid", kind), repository).await,
+            Err(ApplicationError::InvalidArgument(_))
+        ));
+    }
+}
+
+async fn test_toggle_remark_item(repository: &impl Backend) {
+    let remark = import_remark(
+        repository,
+        "Trip\n\n- [ ] Book flights\n- [x] Pack",
+        "2025-01-01T08:30:00Z".parse().unwrap(),
+    )
+    .await;
+
+    let items: Vec<(usize, &str, bool)> = remark
+        .items()
+        .iter()
+        .map(|item| (item.number(), item.text(), item.is_checked()))
+        .collect();
+
+    assert_eq!(items, [(1, "Book flights", false), (2, "Pack", true)]);
+
+    let toggled =
+        remarks::toggle_remark_item(remark.id(), 1, Some(remark.updated_at()), repository)
+            .await
+            .unwrap();
+
+    assert_eq!(
+        toggled.essence().as_str(),
+        "Trip\n\n- [x] Book flights\n- [x] Pack"
+    );
+    assert!(toggled.items().iter().all(|item| item.is_checked()));
+
+    let found = remarks::get_remark(remark.id(), repository).await.unwrap();
+
+    assert_eq!(found.essence().as_str(), toggled.essence().as_str());
+    assert!(found.items()[0].is_checked());
+    assert!(matches!(
+        remarks::toggle_remark_item(remark.id(), 3, None, repository).await,
+        Err(ApplicationError::NotFound)
+    ));
+    assert!(matches!(
+        remarks::toggle_remark_item(remark.id(), 2, Some(remark.updated_at()), repository).await,
+        Err(ApplicationError::Conflict)
+    ));
+
+    let untoggled = remarks::toggle_remark_item(remark.id(), 2, None, repository)
+        .await
+        .unwrap();
+
+    assert!(untoggled.essence().ends_with("- [ ] Pack"));
+}
+
+async fn test_restore_remark_revision(repository: &impl Backend) {
+    let remark = create_remark(repository, "First", &["one"]).await;
+
+    let changes = RemarkChanges {
+        essence: Some("Second".to_string()),
+        tags: Some(vec!["two".to_string()]),
+        kind: Some(RemarkKindAttributes {
+            kind: Some("quote".to_string()),
+            author: Some("Ada Lovelace".to_string()),
+            ..Default::default()
+        }),
+        expected_updated_at: None,
+    };
+
+    remarks::update_remark(remark.id(), changes, repository)
+        .await
+        .unwrap();
+
+    let revisions = remarks::list_remark_revisions(remark.id(), repository)
+        .await
+        .unwrap();
+
+    let history: Vec<(i32, &str, Vec<String>)> = revisions
+        .items
+        .iter()
+        .map(|revision| {
+            let tags = revision.tags().iter().map(ToString::to_string).collect();
+
+            (revision.number(), revision.essence().as_str(), tags)
+        })
+        .collect();
+
+    assert_eq!(
+        history,
+        [
+            (1, "First", vec!["one".to_string()]),
+            (2, "Second", vec!["two".to_string()]),
+        ]
+    );
+    assert!(revisions.items[0].created_at() <= revisions.items[1].created_at());
+    assert_eq!(revisions.items[0].kind(), &RemarkKind::Note);
+    assert_eq!(
+        revisions.items[1].kind(),
+        &RemarkKind::Quote {
+            source: None,
+            author: Some("Ada Lovelace".to_string()),
+        }
+    );
+    assert!(matches!(
+        remarks::list_remark_revisions(Uuid::new_v4(), repository).await,
+        Err(ApplicationError::NotFound)
+    ));
+
+    let restored = remarks::restore_remark_revision(remark.id(), 1, None, repository)
+        .await
+        .unwrap();
+
+    assert_eq!(restored.essence().as_str(), "First");
+    assert_eq!(restored.kind(), &RemarkKind::Note);
+    assert_eq!(
+        remarks::get_remark(remark.id(), repository)
+            .await
+            .unwrap()
+            .kind(),
+        &RemarkKind::Note
+    );
+    assert_eq!(titles(&restored), ["one"]);
+    assert_eq!(tag_titles(repository).await, ["one"]);
+
+    let revisions = remarks::list_remark_revisions(remark.id(), repository)
+        .await
+        .unwrap();
+
+    assert_eq!(revisions.total_size, Some(3));
+    assert!(matches!(
+        remarks::restore_remark_revision(remark.id(), 1, Some(remark.updated_at()), repository)
+            .await,
+        Err(ApplicationError::Conflict)
+    ));
+    assert!(matches!(
+        remarks::restore_remark_revision(remark.id(), 9, None, repository).await,
+        Err(ApplicationError::NotFound)
+    ));
+}
+
+async fn test_remark_links_and_backlinks(repository: &impl Backend) {
+    let target = create_remark(repository, "Target", &[]).await;
+    let missing_id = Uuid::new_v4();
+
+    let source = create_remark(
+        repository,
+        &format!("See [[{}]] and [[{missing_id}]]", target.id()),
+        &[],
+    )
+    .await;
+
+    let dangling_targets = async || {
+        remarks::list_remark_links(source.id(), repository)
+            .await
+            .unwrap()
+            .items
+            .iter()
+            .map(|link| (link.target_id(), link.is_dangling()))
+            .collect::<BTreeSet<(Uuid, bool)>>()
+    };
+
+    assert_eq!(
+        dangling_targets().await,
+        BTreeSet::from([(target.id(), false), (missing_id, true)])
+    );
+
+    let backlinks = remarks::list_remark_backlinks(target.id(), repository)
+        .await
+        .unwrap();
+
+    let sources: Vec<Uuid> = backlinks.items.iter().map(|l| l.source_id()).collect();
+
+    assert_eq!(sources, [source.id()]);
+
+    remarks::delete_remark(target.id(), None, repository)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        dangling_targets().await,
+        BTreeSet::from([(target.id(), true), (missing_id, true)])
+    );
+
+    remarks::restore_remark(target.id(), None, repository)
+        .await
+        .unwrap();
+    remarks::delete_remark(source.id(), None, repository)
+        .await
+        .unwrap();
+
+    let backlinks = remarks::list_remark_backlinks(target.id(), repository)
+        .await
+        .unwrap();
+
+    assert!(backlinks.items.is_empty());
+
+    remarks::restore_remark(source.id(), None, repository)
+        .await
+        .unwrap();
+
+    let changes = RemarkChanges {
+        essence: Some(format!("Only [[{missing_id}]] now")),
+        ..RemarkChanges::empty()
+    };
+
+    remarks::update_remark(source.id(), changes, repository)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        dangling_targets().await,
+        BTreeSet::from([(missing_id, true)])
+    );
+    assert!(
+        remarks::list_remark_backlinks(target.id(), repository)
+            .await
+            .unwrap()
+            .items
+            .is_empty()
+    );
+}
+
+async fn test_remark_attachments(repository: &impl Backend) {
+    let remark = create_remark(repository, "Remark", &[]).await;
+    let other = create_remark(repository, "Other remark", &[]).await;
+
+    let attributes = NewRemarkAttachmentAttributes {
+        file_name: "docs/notes.txt".to_string(),
+        media_type: String::new(),
+        content: b"hello".to_vec(),
+    };
+
+    let attachment = remarks::create_remark_attachment(remark.id(), attributes, repository)
+        .await
+        .unwrap();
+
+    assert_eq!(attachment.file_name(), "notes.txt");
+    assert_eq!(attachment.media_type(), "application/octet-stream");
+    assert_eq!(attachment.size(), 5);
+    assert_eq!(
+        attachment.checksum(),
+        "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+    );
+
+    let listed = remarks::list_remark_attachments(remark.id(), repository)
+        .await
+        .unwrap();
+
+    assert_eq!(listed.total_size, Some(1));
+
+    let (_, content) =
+        remarks::get_remark_attachment_content(remark.id(), attachment.id(), repository)
+            .await
+            .unwrap();
+
+    assert_eq!(content, b"hello");
+    assert!(matches!(
+        remarks::get_remark_attachment_content(other.id(), attachment.id(), repository).await,
+        Err(ApplicationError::NotFound)
+    ));
+
+    remarks::delete_remark_attachment(remark.id(), attachment.id(), repository)
+        .await
+        .unwrap();
+
+    let listed = remarks::list_remark_attachments(remark.id(), repository)
+        .await
+        .unwrap();
+
+    assert!(listed.items.is_empty());
+    assert!(matches!(
+        remarks::delete_remark_attachment(remark.id(), attachment.id(), repository).await,
+        Err(ApplicationError::NotFound)
+    ));
+
+    let attributes = NewRemarkAttachmentAttributes {
+        file_name: "kept.txt".to_string(),
+        media_type: "text/plain".to_string(),
+        content: b"kept".to_vec(),
+    };
+
+    remarks::create_remark_attachment(other.id(), attributes, repository)
+        .await
+        .unwrap();
+    remarks::delete_remark(other.id(), None, repository)
+        .await
+        .unwrap();
+    remarks::delete_remark_permanently(other.id(), None, repository)
+        .await
+        .unwrap();
+
+    let attributes = ImportedRemarkAttributes {
+        id: other.id(),
+        essence: other.essence().to_string(),
+        tags: vec![],
+        created_at: other.created_at(),
+        updated_at: other.updated_at(),
+        deleted_at: None,
+        kind: Default::default(),
+    };
+
+    remarks::import_remark(attributes, repository)
+        .await
+        .unwrap();
+
+    let listed = remarks::list_remark_attachments(other.id(), repository)
+        .await
+        .unwrap();
+
+    assert!(listed.items.is_empty());
+}
+
+async fn test_search_remarks(repository: &impl Backend) {
+    let remark = create_remark(repository, "Learning rust ownership", &[]).await;
+    let frequent = create_remark(repository, "Rust traits, rust macros and rust", &[]).await;
+    let pasta = create_remark(repository, "Cooking pasta", &[]).await;
+    let trashed = create_remark(repository, "Rust in the trash", &[]).await;
+
+    remarks::delete_remark(trashed.id(), None, repository)
+        .await
+        .unwrap();
+
+    let search = async |query: &str| {
+        let parameters = RemarksSearchParameters {
+            query: query.to_string(),
+            page_token: None,
+            page_size: None,
+        };
+
+        remarks::search_remarks(parameters, repository).await
+    };
+
+    let hit_ids = |page: &Page<RemarkSearchHit>| -> Vec<Uuid> {
+        page.items.iter().map(|hit| hit.remark().id()).collect()
+    };
+
+    let page = search("rust").await.unwrap();
+
+    assert_eq!(page.total_size, Some(2));
+    assert_eq!(hit_ids(&page), [frequent.id(), remark.id()]);
+    assert!(page.items[1].snippet().contains("<b>rust</b>"));
+
+    let page = search("pasta OR ownership").await.unwrap();
+
+    assert_eq!(
+        hit_ids(&page).into_iter().collect::<BTreeSet<Uuid>>(),
+        BTreeSet::from([remark.id(), pasta.id()])
+    );
+
+    let page = search("rust -ownership").await.unwrap();
+
+    assert_eq!(hit_ids(&page), [frequent.id()]);
+
+    assert!(matches!(
+        search("  ").await,
+        Err(ApplicationError::InvalidArgument(_))
+    ));
+}
+
+async fn list_remarks(repository: &impl Backend, parameters: RemarksPageParameters) -> Vec<Remark> {
+    remarks::list_remarks(parameters, repository)
+        .await
+        .unwrap()
+        .items
+}
+
+async fn import_remark(
+    repository: &impl Backend,
+    essence: &str,
+    created_at: DateTime<Utc>,
+) -> Remark {
+    let id = Uuid::new_v4();
+
+    let attributes = ImportedRemarkAttributes {
+        id,
+        essence: essence.to_string(),
+        tags: vec![],
+        created_at,
+        updated_at: created_at,
+        deleted_at: None,
+        kind: Default::default(),
+    };
+
+    remarks::import_remark(attributes, repository)
+        .await
+        .unwrap();
+
+    remarks::get_remark(id, repository).await.unwrap()
+}
+
+fn ids(remarks: &[Remark]) -> Vec<Uuid> {
+    remarks.iter().map(Remark::id).collect()
+}
+
+fn tag_set(titles: &[&str]) -> BTreeSet<TagTitle> {
+    titles
+        .iter()
+        .map(|title| TagTitle::new(title.to_string()).unwrap())
+        .collect()
+}
+
+fn titles(remark: &Remark) -> Vec<String> {
+    remark.tags().iter().map(ToString::to_string).collect()
+}
@@ -0,0 +1,330 @@
+mod backends;
+
+use backends::{Backend, backend_tests, create_remark, list_all_tags, tag_titles};
+use canopus_definitions::{ApplicationError, PageSize, Tag};
+use canopus_operations::{
+    remarks::{self, RemarkChanges},
+    tags::{self, TagChanges, TagsOrderBy, TagsPageParameters},
+};
+use uuid::Uuid;
+
+backend_tests!(
+    test_list_tags_with_usage_counts,
+    test_list_tags_paginates,
+    test_list_tags_pages_a_snapshot,
+    test_suggest_tags,
+    test_list_tag_children,
+    test_merge_tags,
+    test_update_tag_renames_descendants,
+    test_update_tag_detects_conflicts,
+    test_get_missing_tag,
+);
+
+async fn test_list_tags_with_usage_counts(repository: &impl Backend) {
+    create_remark(repository, "First", &["rust", "web"]).await;
+    create_remark(repository, "Second", &["rust"]).await;
+
+    let trashed = create_remark(repository, "Trashed", &["rust", "old"]).await;
+    remarks::delete_remark(trashed.id(), None, repository)
+        .await
+        .unwrap();
+
+    let parameters = TagsPageParameters {
+        order_by: TagsOrderBy::UsageCount,
+        with_usage_count: true,
+        ..Default::default()
+    };
+
+    let page = tags::list_tags(parameters, repository).await.unwrap();
+
+    assert_eq!(
+        usage_counts(&page.items),
+        [
+            ("rust".to_string(), Some(2)),
+            ("web".to_string(), Some(1)),
+            ("old".to_string(), Some(0)),
+        ]
+    );
+
+    let page = tags::list_tags(TagsPageParameters::default(), repository)
+        .await
+        .unwrap();
+
+    assert!(page.items.iter().all(|tag| tag.usage_count().is_none()));
+}
+
+async fn test_list_tags_paginates(repository: &impl Backend) {
+    create_remark(repository, "Remark", &["web", "old", "rust"]).await;
+
+    let mut listed = Vec::new();
+    let mut page_token = None;
+
+    loop {
+        let parameters = TagsPageParameters {
+            page_token,
+            page_size: Some(PageSize::new(1).unwrap()),
+            order_by: TagsOrderBy::Title,
+            ..Default::default()
+        };
+
+        let page = tags::list_tags(parameters, repository).await.unwrap();
+
+        assert_eq!(page.total_size, Some(3));
+
+        listed.extend(page.items.iter().map(|tag| tag.title().to_string()));
+
+        match page.next_page_token {
+            Some(token) => page_token = Some(token),
+            None => break,
+        }
+    }
+
+    assert_eq!(listed, tag_titles(repository).await);
+    assert_eq!(listed, ["old", "rust", "web"]);
+}
+
+async fn test_list_tags_pages_a_snapshot(repository: &impl Backend) {
+    create_remark(repository, "First", &["a", "b"]).await;
+    create_remark(repository, "Second", &["c"]).await;
+
+    let edited = create_remark(repository, "Third", &["a"]).await;
+
+    let mut listed = Vec::new();
+    let mut page_token = None;
+
+    loop {
+        let parameters = TagsPageParameters {
+            page_token,
+            page_size: Some(PageSize::new(1).unwrap()),
+            order_by: TagsOrderBy::UsageCount,
+            with_usage_count: true,
+        };
+
+        let page = tags::list_tags(parameters, repository).await.unwrap();
+
+        listed.extend(usage_counts(&page.items));
+
+        if listed.len() == 1 {
+            let changes = RemarkChanges {
+                essence: None,
+                tags: Some(vec!["a".to_string(), "b".to_string(), "c".to_string()]),
+                kind: None,
+                expected_updated_at: None,
+            };
+
+            remarks::update_remark(edited.id(), changes, repository)
+                .await
+                .unwrap();
+        }
+
+        match page.next_page_token {
+            Some(token) => page_token = Some(token),
+            None => break,
+        }
+    }
+
+    listed.sort();
+
+    assert_eq!(
+        listed,
+        [
+            ("a".to_string(), Some(2)),
+            ("b".to_string(), Some(1)),
+            ("c".to_string(), Some(1)),
+        ]
+    );
+}
+
+async fn test_suggest_tags(repository: &impl Backend) {
+    create_remark(repository, "First", &["work/canopus", "work"]).await;
+    create_remark(repository, "Second", &["work/canopus"]).await;
+    create_remark(repository, "Third", &["web"]).await;
+
+    let suggest = async |prefix: &str, limit: Option<PageSize>| {
+        tags::suggest_tags(prefix.to_string(), limit, repository)
+            .await
+            .unwrap()
+            .items
+    };
+
+    assert_eq!(
+        usage_counts(&suggest(" W", None).await),
+        [
+            ("work/canopus".to_string(), Some(2)),
+            ("web".to_string(), Some(1)),
+            ("work".to_string(), Some(1)),
+        ]
+    );
+
+    let titles = |tags: Vec<Tag>| {
+        tags.iter()
+            .map(|tag| tag.title().to_string())
+            .collect::<Vec<String>>()
+    };
+
+    assert_eq!(titles(suggest("wor", None).await), ["work/canopus", "work"]);
+    assert_eq!(
+        titles(suggest("w", Some(PageSize::new(1).unwrap())).await),
+        ["work/canopus"]
+    );
+    assert!(suggest("work_", None).await.is_empty());
+}
+
+async fn test_list_tag_children(repository: &impl Backend) {
+    create_remark(repository, "Remark", &["a/b/c", "a/d"]).await;
+
+    let parent = find_tag(repository, "a").await;
+
+    let children = tags::list_tag_children(parent.id(), repository)
+        .await
+        .unwrap();
+
+    let titles: Vec<String> = children
+        .items
+        .iter()
+        .map(|tag| tag.title().to_string())
+        .collect();
+
+    assert_eq!(titles, ["a/b", "a/d"]);
+
+    let leaf = find_tag(repository, "a/b/c").await;
+
+    let children = tags::list_tag_children(leaf.id(), repository)
+        .await
+        .unwrap();
+
+    assert!(children.items.is_empty());
+    assert!(matches!(
+        tags::list_tag_children(Uuid::new_v4(), repository).await,
+        Err(ApplicationError::NotFound)
+    ));
+}
+
+async fn test_merge_tags(repository: &impl Backend) {
+    let remark = create_remark(repository, "First", &["js"]).await;
+    create_remark(repository, "Second", &["javascript"]).await;
+    let both = create_remark(repository, "Both", &["js", "javascript"]).await;
+
+    let source = find_tag(repository, "js").await;
+    let target = find_tag(repository, "javascript").await;
+
+    let merged = tags::merge_tags(source.id(), target.id(), repository)
+        .await
+        .unwrap();
+
+    assert_eq!(merged.title().as_str(), "javascript");
+    assert_eq!(tag_titles(repository).await, ["javascript"]);
+
+    let remark = remarks::get_remark(remark.id(), repository).await.unwrap();
+
+    assert_eq!(remark.tags()[0].as_str(), "javascript");
+
+    let both = remarks::get_remark(both.id(), repository).await.unwrap();
+
+    assert_eq!(both.tags().len(), 1);
+    assert_eq!(both.tags()[0].as_str(), "javascript");
+
+    let aliased = create_remark(repository, "Third", &["js"]).await;
+
+    assert_eq!(aliased.tags()[0].as_str(), "javascript");
+    assert_eq!(tag_titles(repository).await, ["javascript"]);
+
+    assert!(matches!(
+        tags::merge_tags(target.id(), target.id(), repository).await,
+        Err(ApplicationError::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        tags::merge_tags(source.id(), target.id(), repository).await,
+        Err(ApplicationError::NotFound)
+    ));
+
+    create_remark(repository, "Fourth", &["lang/rust"]).await;
+
+    let parent = find_tag(repository, "lang").await;
+
+    assert!(matches!(
+        tags::merge_tags(parent.id(), target.id(), repository).await,
+        Err(ApplicationError::InvalidArgument(_))
+    ));
+}
+
+async fn test_update_tag_renames_descendants(repository: &impl Backend) {
+    create_remark(repository, "First", &["lang/rust", "lang/go"]).await;
+    create_remark(repository, "Second", &["misc"]).await;
+
+    let lang = find_tag(repository, "lang").await;
+
+    let changes = TagChanges {
+        title: "Languages".to_string(),
+        expected_updated_at: Some(lang.updated_at()),
+    };
+
+    let renamed = tags::update_tag(lang.id(), changes, repository)
+        .await
+        .unwrap();
+
+    assert_eq!(renamed.title().as_str(), "languages");
+    assert_eq!(
+        tag_titles(repository).await,
+        ["languages", "languages/go", "languages/rust", "misc"]
+    );
+
+    let misc = find_tag(repository, "misc").await;
+
+    let changes = TagChanges {
+        title: "languages".to_string(),
+        expected_updated_at: None,
+    };
+
+    assert!(matches!(
+        tags::update_tag(misc.id(), changes, repository).await,
+        Err(ApplicationError::InvalidArgument(_))
+    ));
+}
+
+async fn test_update_tag_detects_conflicts(repository: &impl Backend) {
+    create_remark(repository, "Remark", &["draft"]).await;
+
+    let stale = find_tag(repository, "draft").await;
+
+    let changes = TagChanges {
+        title: "drafts".to_string(),
+        expected_updated_at: Some(stale.updated_at()),
+    };
+
+    tags::update_tag(stale.id(), changes, repository)
+        .await
+        .unwrap();
+
+    let changes = TagChanges {
+        title: "notes".to_string(),
+        expected_updated_at: Some(stale.updated_at()),
+    };
+
+    assert!(matches!(
+        tags::update_tag(stale.id(), changes, repository).await,
+        Err(ApplicationError::Conflict)
+    ));
+    assert_eq!(tag_titles(repository).await, ["drafts"]);
+}
+
+async fn test_get_missing_tag(repository: &impl Backend) {
+    assert!(matches!(
+        tags::get_tag(Uuid::new_v4(), repository).await,
+        Err(ApplicationError::NotFound)
+    ));
+}
+
+async fn find_tag(repository: &impl Backend, title: &str) -> Tag {
+    list_all_tags(repository)
+        .await
+        .into_iter()
+        .find(|tag| tag.title().as_str() == title)
+        .expect("tag should exist")
+}
+
+fn usage_counts(tags: &[Tag]) -> Vec<(String, Option<i64>)> {
+    tags.iter()
+        .map(|tag| (tag.title().to_string(), tag.usage_count()))
+        .collect()
+}
@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP INDEX remarks_essence_search_index;
+
+ALTER TABLE remarks DROP COLUMN essence_search;
@@ -0,0 +1,7 @@
+-- Add up migration script here
+
+ALTER TABLE remarks
+ADD COLUMN essence_search tsvector
+GENERATED ALWAYS AS (to_tsvector('english', essence)) STORED;
+
+CREATE INDEX remarks_essence_search_index ON remarks USING GIN (essence_search);
@@ -0,0 +1,23 @@
+-- Add up migration script here
+
+CREATE TABLE remark_revisions (
+    remark_id uuid NOT NULL,
+    number integer NOT NULL,
+    essence text NOT NULL,
+    tags text[] NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX remark_revisions_remark_id_number_index ON remark_revisions (remark_id, number);
+
+INSERT INTO remark_revisions ( remark_id, number, essence, tags, created_at )
+SELECT
+    remarks.id,
+    1,
+    remarks.essence,
+    COALESCE(array_agg(tags.title ORDER BY tags.title) FILTER (WHERE tags.title IS NOT NULL), '{}'),
+    remarks.updated_at
+FROM remarks
+LEFT JOIN remarks_tags ON remarks_tags.remark_id = remarks.id
+LEFT JOIN tags ON tags.id = remarks_tags.tag_id
+GROUP BY remarks.id;
@@ -0,0 +1,3 @@
+-- Add down migration script here
+
+ALTER TABLE remarks DROP COLUMN deleted_at
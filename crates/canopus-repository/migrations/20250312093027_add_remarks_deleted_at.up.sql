@@ -0,0 +1,3 @@
+-- Add up migration script here
+
+ALTER TABLE remarks ADD COLUMN deleted_at timestamptz;
@@ -0,0 +1,3 @@
+-- Add down migration script here
+
+-- Normalized titles and merged tags can't be told apart from the original ones
@@ -0,0 +1,47 @@
+-- Add up migration script here
+
+-- Titles are trimmed per segment and blank segments dropped, like TagTitle does
+CREATE TEMPORARY TABLE normalized_tags ON COMMIT DROP AS
+SELECT
+    id,
+    created_at,
+    array_to_string(
+        ARRAY(
+            SELECT trim(segments.segment)
+            FROM unnest(string_to_array(tags.title, '/')) WITH ORDINALITY AS segments(segment, position)
+            WHERE trim(segments.segment) <> ''
+            ORDER BY segments.position
+        ),
+        '/'
+    ) AS title
+FROM tags;
+
+DELETE FROM remarks_tags
+WHERE tag_id IN (SELECT id FROM normalized_tags WHERE title = '');
+
+DELETE FROM tags
+WHERE id IN (SELECT id FROM normalized_tags WHERE title = '');
+
+CREATE TEMPORARY TABLE duplicate_tags ON COMMIT DROP AS
+SELECT id, kept_id
+FROM (
+    SELECT id, first_value(id) OVER (PARTITION BY title ORDER BY created_at, id) AS kept_id
+    FROM normalized_tags
+    WHERE title <> ''
+) AS ranked_tags
+WHERE id <> kept_id;
+
+INSERT INTO remarks_tags ( remark_id, tag_id )
+SELECT remarks_tags.remark_id, duplicate_tags.kept_id
+FROM remarks_tags
+JOIN duplicate_tags ON duplicate_tags.id = remarks_tags.tag_id
+ON CONFLICT DO NOTHING;
+
+DELETE FROM remarks_tags WHERE tag_id IN (SELECT id FROM duplicate_tags);
+
+DELETE FROM tags WHERE id IN (SELECT id FROM duplicate_tags);
+
+UPDATE tags
+SET title = normalized_tags.title, updated_at = now()
+FROM normalized_tags
+WHERE normalized_tags.id = tags.id AND normalized_tags.title <> tags.title;
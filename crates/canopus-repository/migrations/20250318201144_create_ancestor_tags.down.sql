@@ -0,0 +1,11 @@
+-- Add down migration script here
+
+-- Only the ancestors inserted by the up migration and still unused are removed
+DELETE FROM tags
+WHERE id IN (SELECT tag_id FROM migrated_ancestor_tags)
+AND NOT EXISTS (
+    SELECT 1 FROM remarks_tags
+    WHERE remarks_tags.tag_id = tags.id
+);
+
+DROP TABLE migrated_ancestor_tags;
@@ -0,0 +1,23 @@
+-- Add up migration script here
+
+CREATE TABLE migrated_ancestor_tags (
+    tag_id uuid PRIMARY KEY
+);
+
+WITH inserted_tags AS (
+    INSERT INTO tags ( title )
+    SELECT DISTINCT array_to_string(ancestors.segments[1:ancestors.depth], '/')
+    FROM (
+        SELECT
+            string_to_array(title, '/') AS segments,
+            generate_series(1, cardinality(string_to_array(title, '/')) - 1) AS depth
+        FROM tags
+    ) AS ancestors
+    WHERE NOT EXISTS (
+        SELECT 1 FROM tags
+        WHERE tags.title = array_to_string(ancestors.segments[1:ancestors.depth], '/')
+    )
+    RETURNING id
+)
+INSERT INTO migrated_ancestor_tags ( tag_id )
+SELECT id FROM inserted_tags;
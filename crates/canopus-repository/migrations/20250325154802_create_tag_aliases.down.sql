@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP TABLE tag_aliases;
+
+DROP INDEX tags_title_index;
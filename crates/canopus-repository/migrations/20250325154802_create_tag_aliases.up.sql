@@ -0,0 +1,29 @@
+-- Add up migration script here
+
+CREATE TEMPORARY TABLE duplicate_tags ON COMMIT DROP AS
+SELECT id, kept_id
+FROM (
+    SELECT id, first_value(id) OVER (PARTITION BY title ORDER BY created_at, id) AS kept_id
+    FROM tags
+) AS ranked_tags
+WHERE id <> kept_id;
+
+INSERT INTO remarks_tags ( remark_id, tag_id )
+SELECT remarks_tags.remark_id, duplicate_tags.kept_id
+FROM remarks_tags
+JOIN duplicate_tags ON duplicate_tags.id = remarks_tags.tag_id
+ON CONFLICT DO NOTHING;
+
+DELETE FROM remarks_tags WHERE tag_id IN (SELECT id FROM duplicate_tags);
+
+DELETE FROM tags WHERE id IN (SELECT id FROM duplicate_tags);
+
+CREATE UNIQUE INDEX tags_title_index ON tags (title);
+
+CREATE TABLE tag_aliases (
+    title text PRIMARY KEY,
+    tag_id uuid NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX tag_aliases_tag_id_index ON tag_aliases (tag_id);
@@ -0,0 +1,20 @@
+-- Add up migration script here
+
+CREATE TABLE remark_links (
+    source_id uuid NOT NULL,
+    target_id uuid NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX remark_links_source_id_target_id_index ON remark_links (source_id, target_id);
+CREATE INDEX remark_links_target_id_index ON remark_links (target_id);
+
+INSERT INTO remark_links ( source_id, target_id )
+SELECT DISTINCT remarks.id, CAST(lower(matches[1]) AS uuid)
+FROM remarks
+CROSS JOIN LATERAL regexp_matches(
+    remarks.essence,
+    '\[\[\[*\s*([0-9A-Fa-f]{8}-[0-9A-Fa-f]{4}-[0-9A-Fa-f]{4}-[0-9A-Fa-f]{4}-[0-9A-Fa-f]{12})\s*\]\]',
+    'g'
+) AS matches
+WHERE CAST(lower(matches[1]) AS uuid) <> remarks.id;
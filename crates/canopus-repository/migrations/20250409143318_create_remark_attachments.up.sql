@@ -0,0 +1,14 @@
+-- Add up migration script here
+
+CREATE TABLE remark_attachments (
+    id uuid DEFAULT gen_random_uuid() PRIMARY KEY,
+    remark_id uuid NOT NULL,
+    file_name text NOT NULL,
+    media_type text NOT NULL,
+    size bigint NOT NULL,
+    checksum text NOT NULL,
+    content bytea,
+    created_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX remark_attachments_remark_id_index ON remark_attachments (remark_id);
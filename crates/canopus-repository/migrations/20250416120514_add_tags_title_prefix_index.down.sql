@@ -0,0 +1,3 @@
+-- Add down migration script here
+
+DROP INDEX tags_title_prefix_index;
@@ -0,0 +1,3 @@
+-- Add up migration script here
+
+CREATE INDEX tags_title_prefix_index ON tags (title text_pattern_ops);
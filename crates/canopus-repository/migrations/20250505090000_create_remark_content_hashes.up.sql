@@ -0,0 +1,9 @@
+-- Add up migration script here
+
+CREATE TABLE remark_content_hashes (
+    content_hash text PRIMARY KEY,
+    remark_id uuid NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX remark_content_hashes_remark_id_index ON remark_content_hashes (remark_id);
@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP INDEX remarks_updated_at_index;
+
+DROP INDEX remarks_created_at_index;
@@ -0,0 +1,5 @@
+-- Add up migration script here
+
+CREATE INDEX remarks_created_at_index ON remarks (created_at, id);
+
+CREATE INDEX remarks_updated_at_index ON remarks (updated_at, id);
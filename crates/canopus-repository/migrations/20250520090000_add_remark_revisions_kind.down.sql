@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+ALTER TABLE remark_revisions DROP COLUMN payload;
+ALTER TABLE remark_revisions DROP COLUMN kind
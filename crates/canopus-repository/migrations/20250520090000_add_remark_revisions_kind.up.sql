@@ -0,0 +1,10 @@
+-- Add up migration script here
+
+ALTER TABLE remark_revisions ADD COLUMN kind text NOT NULL DEFAULT 'note';
+ALTER TABLE remark_revisions ADD COLUMN payload jsonb;
+
+-- Earlier revisions didn't record a kind, restoring them keeps the current one
+UPDATE remark_revisions
+SET kind = remarks.kind, payload = remarks.payload
+FROM remarks
+WHERE remarks.id = remark_revisions.remark_id;
@@ -1,21 +1,29 @@
+mod remark_attachments;
+mod remark_content_hashes;
+mod remark_idempotency_keys;
+mod remark_links;
+mod remark_revisions;
 mod remarks;
+mod remarks_search;
 mod remarks_tags;
 mod tags;
 
+pub use remark_attachments::AttachmentsStorage;
+
 use base64::{
     alphabet,
     engine::{GeneralPurpose, general_purpose},
 };
-use canopus_definitions::{ApplicationError, ApplicationResult};
+use canopus_definitions::{ApplicationError, ApplicationResult, PageSize};
 use sqlx::PgTransaction;
 use tags::{TagRow, TagTitleRow};
 
-const DEFAULT_PAGE_SIZE: i64 = 3;
 const URL_SAFE_NO_PAD_ENGINE: GeneralPurpose =
     GeneralPurpose::new(&alphabet::URL_SAFE, general_purpose::NO_PAD);
 
 pub struct Repository {
     pub pool: sqlx::PgPool,
+    pub attachments_storage: AttachmentsStorage,
 }
 
 impl Repository {
@@ -34,3 +42,14 @@ fn from_sqlx_err(err: sqlx::Error) -> ApplicationError {
         other => ApplicationError::Repository(other.to_string()),
     }
 }
+
+fn resolve_page_size(
+    page_size: Option<PageSize>,
+    token_page_size: Option<i64>,
+) -> ApplicationResult<PageSize> {
+    match (page_size, token_page_size) {
+        (Some(page_size), _) => Ok(page_size),
+        (None, Some(page_size)) => PageSize::new(page_size),
+        (None, None) => Ok(PageSize::default()),
+    }
+}
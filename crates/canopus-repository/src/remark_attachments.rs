@@ -0,0 +1,245 @@
+use crate::{Repository, commit_transaction, from_sqlx_err};
+use canopus_definitions::{
+    ApplicationError, ApplicationResult, RemarkAttachment, RemarkAttachmentAttributes,
+};
+use canopus_operations::remarks::{
+    DeleteRemarkAttachment, GetRemarkAttachment, GetRemarkAttachmentContent,
+    InsertRemarkAttachment, ListRemarkAttachments, NewRemarkAttachment,
+};
+use chrono::{DateTime, Utc};
+use sqlx::PgTransaction;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+pub enum AttachmentsStorage {
+    Database,
+    Directory(PathBuf),
+}
+
+pub struct RemarkAttachmentRow {
+    pub id: Uuid,
+    pub remark_id: Uuid,
+    pub file_name: String,
+    pub media_type: String,
+    pub size: i64,
+    pub checksum: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DeleteRemarkAttachment for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn delete_remark_attachment(
+        &self,
+        attachment: &RemarkAttachment,
+    ) -> ApplicationResult<()> {
+        let rec = sqlx::query!(
+            r#"DELETE FROM remark_attachments WHERE id = $1"#,
+            attachment.id()
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        if rec.rows_affected() == 0 {
+            return Err(ApplicationError::NotFound);
+        }
+
+        self.attachments_storage
+            .remove_files(&[attachment.id()])
+            .await
+    }
+}
+
+impl GetRemarkAttachment for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn get_remark_attachment(
+        &self,
+        remark_id: Uuid,
+        id: Uuid,
+    ) -> ApplicationResult<RemarkAttachment> {
+        let row = sqlx::query_as!(
+            RemarkAttachmentRow,
+            r#"
+SELECT id, remark_id, file_name, media_type, size, checksum, created_at
+FROM remark_attachments
+WHERE remark_id = $1 AND id = $2
+            "#,
+            remark_id,
+            id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        Ok(row.into())
+    }
+}
+
+impl GetRemarkAttachmentContent for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn get_remark_attachment_content(
+        &self,
+        attachment: &RemarkAttachment,
+    ) -> ApplicationResult<Vec<u8>> {
+        let content = sqlx::query_scalar!(
+            r#"SELECT content FROM remark_attachments WHERE id = $1"#,
+            attachment.id()
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        if let Some(content) = content {
+            return Ok(content);
+        }
+
+        let AttachmentsStorage::Directory(directory) = &self.attachments_storage else {
+            return Err(ApplicationError::msg(
+                "attachment is stored in a directory, set CANOPUS_ATTACHMENTS_DIR to read it",
+            ));
+        };
+
+        tokio::fs::read(directory.join(attachment.id().to_string()))
+            .await
+            .map_err(|err| ApplicationError::internal("failed to read attachment", err))
+    }
+}
+
+impl InsertRemarkAttachment for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn insert_remark_attachment(
+        &self,
+        attachment: NewRemarkAttachment,
+    ) -> ApplicationResult<RemarkAttachment> {
+        let NewRemarkAttachment {
+            remark_id,
+            file_name,
+            media_type,
+            checksum,
+            content,
+        } = attachment;
+
+        let mut tx = self.begin_transaction().await?;
+
+        let stored_content = match self.attachments_storage {
+            AttachmentsStorage::Database => Some(content.as_slice()),
+            AttachmentsStorage::Directory(_) => None,
+        };
+
+        let row = sqlx::query_as!(
+            RemarkAttachmentRow,
+            r#"
+INSERT INTO remark_attachments ( remark_id, file_name, media_type, size, checksum, content )
+VALUES ( $1, $2, $3, $4, $5, $6 )
+RETURNING id, remark_id, file_name, media_type, size, checksum, created_at
+            "#,
+            remark_id,
+            file_name,
+            media_type,
+            content.len() as i64,
+            checksum,
+            stored_content
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        if let AttachmentsStorage::Directory(directory) = &self.attachments_storage {
+            write_file(directory, row.id, &content).await?;
+        }
+
+        commit_transaction(tx).await?;
+
+        Ok(row.into())
+    }
+}
+
+impl ListRemarkAttachments for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn list_remark_attachments(
+        &self,
+        remark_id: Uuid,
+    ) -> ApplicationResult<Vec<RemarkAttachment>> {
+        let rows = sqlx::query_as!(
+            RemarkAttachmentRow,
+            r#"
+SELECT id, remark_id, file_name, media_type, size, checksum, created_at
+FROM remark_attachments
+WHERE remark_id = $1
+ORDER BY created_at ASC, id ASC
+            "#,
+            remark_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
+
+impl AttachmentsStorage {
+    pub async fn remove_files(&self, ids: &[Uuid]) -> ApplicationResult<()> {
+        let AttachmentsStorage::Directory(directory) = self else {
+            return Ok(());
+        };
+
+        for id in ids {
+            match tokio::fs::remove_file(directory.join(id.to_string())).await {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+                    return Err(ApplicationError::internal(
+                        "failed to remove attachment",
+                        err,
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub async fn delete(tx: &mut PgTransaction<'_>, remark_id: Uuid) -> ApplicationResult<Vec<Uuid>> {
+    sqlx::query_scalar!(
+        r#"DELETE FROM remark_attachments WHERE remark_id = $1 RETURNING id"#,
+        remark_id
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)
+}
+
+async fn write_file(directory: &Path, id: Uuid, content: &[u8]) -> ApplicationResult<()> {
+    tokio::fs::create_dir_all(directory)
+        .await
+        .map_err(|err| ApplicationError::internal("failed to create attachments directory", err))?;
+
+    tokio::fs::write(directory.join(id.to_string()), content)
+        .await
+        .map_err(|err| ApplicationError::internal("failed to write attachment", err))
+}
+
+impl From<RemarkAttachmentRow> for RemarkAttachment {
+    fn from(value: RemarkAttachmentRow) -> Self {
+        let RemarkAttachmentRow {
+            id,
+            remark_id,
+            file_name,
+            media_type,
+            size,
+            checksum,
+            created_at,
+        } = value;
+
+        Self::new(RemarkAttachmentAttributes {
+            id,
+            remark_id,
+            file_name,
+            media_type,
+            size,
+            checksum,
+            created_at,
+        })
+    }
+}
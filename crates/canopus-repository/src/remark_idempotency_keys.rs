@@ -0,0 +1,121 @@
+use crate::{Repository, commit_transaction, from_sqlx_err, remarks};
+use canopus_definitions::{ApplicationError, ApplicationResult, Remark};
+use canopus_operations::remarks::{InsertIdempotentRemark, NewRemark, RemarkIdempotencyKey};
+use sqlx::{PgExecutor, PgTransaction};
+use uuid::Uuid;
+
+struct RemarkIdempotencyKeyRow {
+    request_hash: String,
+    remark_id: Uuid,
+    trashed: bool,
+}
+
+impl InsertIdempotentRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn insert_idempotent_remark(
+        &self,
+        new_remark: NewRemark,
+        key: RemarkIdempotencyKey,
+    ) -> ApplicationResult<Remark> {
+        let RemarkIdempotencyKey {
+            key,
+            request_hash,
+            created_after,
+        } = key;
+
+        let mut tx = self.begin_transaction().await?;
+
+        sqlx::query!(
+            "DELETE FROM remark_idempotency_keys WHERE created_at < $1",
+            created_after
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        if let Some(row) = find(&mut *tx, &key).await? {
+            commit_transaction(tx).await?;
+
+            return self.find_stored_remark(row, &request_hash).await;
+        }
+
+        let remark = remarks::insert_remark(&mut tx, new_remark).await?;
+
+        let claimed = sqlx::query_scalar!(
+            r#"
+INSERT INTO remark_idempotency_keys ( key, request_hash, remark_id )
+VALUES ( $1, $2, $3 )
+ON CONFLICT (key) DO NOTHING
+RETURNING key
+            "#,
+            key,
+            request_hash,
+            remark.id()
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        if claimed.is_some() {
+            commit_transaction(tx).await?;
+
+            return Ok(remark);
+        }
+
+        tx.rollback().await.map_err(from_sqlx_err)?;
+
+        let row = find(&self.pool, &key)
+            .await?
+            .ok_or(ApplicationError::Conflict)?;
+
+        self.find_stored_remark(row, &request_hash).await
+    }
+}
+
+impl Repository {
+    async fn find_stored_remark(
+        &self,
+        row: RemarkIdempotencyKeyRow,
+        request_hash: &str,
+    ) -> ApplicationResult<Remark> {
+        if row.request_hash != request_hash {
+            return Err(ApplicationError::Conflict);
+        }
+
+        remarks::find_remark(&self.pool, row.remark_id, row.trashed).await
+    }
+}
+
+pub async fn delete(tx: &mut PgTransaction<'_>, remark_id: Uuid) -> ApplicationResult<()> {
+    sqlx::query!(
+        "DELETE FROM remark_idempotency_keys WHERE remark_id = $1",
+        remark_id
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    Ok(())
+}
+
+async fn find(
+    executor: impl PgExecutor<'_>,
+    key: &str,
+) -> ApplicationResult<Option<RemarkIdempotencyKeyRow>> {
+    sqlx::query_as!(
+        RemarkIdempotencyKeyRow,
+        r#"
+SELECT
+    remark_idempotency_keys.request_hash,
+    remark_idempotency_keys.remark_id,
+    remarks.deleted_at IS NOT NULL AS "trashed!"
+FROM remark_idempotency_keys
+JOIN remarks ON remarks.id = remark_idempotency_keys.remark_id
+WHERE remark_idempotency_keys.key = $1
+        "#,
+        key
+    )
+    .fetch_optional(executor)
+    .await
+    .map_err(from_sqlx_err)
+}
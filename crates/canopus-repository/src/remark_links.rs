@@ -0,0 +1,113 @@
+use crate::{Repository, from_sqlx_err};
+use canopus_definitions::{ApplicationResult, Remark, RemarkLink, RemarkLinkAttributes};
+use canopus_operations::remarks::{ListRemarkBacklinks, ListRemarkLinks};
+use sqlx::PgTransaction;
+use uuid::Uuid;
+
+pub struct RemarkLinkRow {
+    pub source_id: Uuid,
+    pub target_id: Uuid,
+    pub dangling: bool,
+}
+
+impl ListRemarkBacklinks for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn list_remark_backlinks(&self, remark_id: Uuid) -> ApplicationResult<Vec<RemarkLink>> {
+        let rows = sqlx::query_as!(
+            RemarkLinkRow,
+            r#"
+SELECT remark_links.source_id, remark_links.target_id, FALSE AS "dangling!"
+FROM remark_links
+JOIN remarks ON remarks.id = remark_links.source_id AND remarks.deleted_at IS NULL
+WHERE remark_links.target_id = $1
+ORDER BY remarks.created_at DESC, remarks.id DESC
+            "#,
+            remark_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
+
+impl ListRemarkLinks for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn list_remark_links(&self, remark_id: Uuid) -> ApplicationResult<Vec<RemarkLink>> {
+        let rows = sqlx::query_as!(
+            RemarkLinkRow,
+            r#"
+SELECT remark_links.source_id, remark_links.target_id, remarks.id IS NULL AS "dangling!"
+FROM remark_links
+LEFT JOIN remarks ON remarks.id = remark_links.target_id AND remarks.deleted_at IS NULL
+WHERE remark_links.source_id = $1
+ORDER BY remark_links.target_id ASC
+            "#,
+            remark_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
+
+pub async fn delete(tx: &mut PgTransaction<'_>, remark_id: Uuid) -> ApplicationResult<()> {
+    sqlx::query!(
+        r#"DELETE FROM remark_links WHERE source_id = $1"#,
+        remark_id
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    Ok(())
+}
+
+pub async fn save(tx: &mut PgTransaction<'_>, remark: &Remark) -> ApplicationResult<()> {
+    delete(tx, remark.id()).await?;
+
+    let target_ids: Vec<Uuid> = remark
+        .essence()
+        .linked_remark_ids()
+        .into_iter()
+        .filter(|id| *id != remark.id())
+        .collect();
+
+    if target_ids.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query!(
+        r#"
+INSERT INTO remark_links ( source_id, target_id )
+SELECT $1, target_id
+FROM unnest($2::uuid[]) AS target_id
+        "#,
+        remark.id(),
+        &target_ids
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    Ok(())
+}
+
+impl From<RemarkLinkRow> for RemarkLink {
+    fn from(value: RemarkLinkRow) -> Self {
+        let RemarkLinkRow {
+            source_id,
+            target_id,
+            dangling,
+        } = value;
+
+        Self::new(RemarkLinkAttributes {
+            source_id,
+            target_id,
+            dangling,
+        })
+    }
+}
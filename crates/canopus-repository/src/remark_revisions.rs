@@ -0,0 +1,153 @@
+use crate::{Repository, commit_transaction, from_sqlx_err, remarks};
+use canopus_definitions::{
+    ApplicationError, ApplicationResult, Remark, RemarkEssence, RemarkKind, RemarkRevision,
+    RemarkRevisionAttributes, TagTitle,
+};
+use canopus_operations::remarks::{ListRemarkRevisions, RestoreRemarkRevision};
+use chrono::{DateTime, Utc};
+use sqlx::PgTransaction;
+use uuid::Uuid;
+
+pub struct RemarkRevisionRow {
+    pub remark_id: Uuid,
+    pub number: i32,
+    pub essence: String,
+    pub tags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub kind: String,
+    pub payload: Option<String>,
+}
+
+impl ListRemarkRevisions for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn list_remark_revisions(
+        &self,
+        remark_id: Uuid,
+    ) -> ApplicationResult<Vec<RemarkRevision>> {
+        sqlx::query_as!(
+            RemarkRevisionRow,
+            r#"
+SELECT remark_id, number, essence, tags, created_at, kind, payload::text AS "payload?"
+FROM remark_revisions
+WHERE remark_id = $1
+ORDER BY number ASC
+            "#,
+            remark_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?
+        .into_iter()
+        .map(TryInto::try_into)
+        .collect()
+    }
+}
+
+impl RestoreRemarkRevision for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn restore_remark_revision(
+        &self,
+        remark: &mut Remark,
+        number: i32,
+    ) -> ApplicationResult<()> {
+        let mut tx = self.begin_transaction().await?;
+
+        let RemarkRevisionRow {
+            essence,
+            tags,
+            kind,
+            payload,
+            ..
+        } = sqlx::query_as!(
+            RemarkRevisionRow,
+            r#"
+SELECT remark_id, number, essence, tags, created_at, kind, payload::text AS "payload?"
+FROM remark_revisions
+WHERE remark_id = $1 AND number = $2
+            "#,
+            remark.id(),
+            number
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        remark.set_essence(RemarkEssence::new(essence)?);
+        remark.set_tags(
+            tags.into_iter()
+                .map(TagTitle::new)
+                .collect::<ApplicationResult<Vec<TagTitle>>>()?,
+        );
+        remark.set_kind(RemarkKind::from_parts(&kind, payload.as_deref())?);
+
+        remarks::save_remark(&mut tx, remark).await?;
+
+        commit_transaction(tx).await
+    }
+}
+
+pub async fn create(tx: &mut PgTransaction<'_>, remark: &Remark) -> ApplicationResult<()> {
+    let tags: Vec<String> = remark.tags().iter().map(ToString::to_string).collect();
+
+    sqlx::query!(
+        r#"
+INSERT INTO remark_revisions ( remark_id, number, essence, tags, created_at, kind, payload )
+SELECT $1, COALESCE(MAX(number), 0) + 1, $2, $3, $4, $5, $6::text::jsonb
+FROM remark_revisions
+WHERE remark_id = $1
+        "#,
+        remark.id(),
+        remark.essence().as_str(),
+        &tags,
+        remark.updated_at(),
+        remark.kind().name().as_str(),
+        remark.kind().payload(),
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    Ok(())
+}
+
+pub async fn delete(tx: &mut PgTransaction<'_>, remark_id: Uuid) -> ApplicationResult<()> {
+    sqlx::query!(
+        r#"DELETE FROM remark_revisions WHERE remark_id = $1"#,
+        remark_id
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    Ok(())
+}
+
+impl TryFrom<RemarkRevisionRow> for RemarkRevision {
+    type Error = ApplicationError;
+
+    fn try_from(value: RemarkRevisionRow) -> ApplicationResult<Self> {
+        let RemarkRevisionRow {
+            remark_id,
+            number,
+            essence,
+            tags,
+            created_at,
+            kind,
+            payload,
+        } = value;
+
+        let revision = Self::new(RemarkRevisionAttributes {
+            remark_id,
+            number,
+            essence: RemarkEssence::new(essence)?,
+            tags: tags
+                .into_iter()
+                .map(TagTitle::new)
+                .collect::<ApplicationResult<Vec<TagTitle>>>()?,
+            created_at,
+            kind: RemarkKind::from_parts(&kind, payload.as_deref())?,
+        });
+
+        Ok(revision)
+    }
+}
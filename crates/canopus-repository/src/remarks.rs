@@ -1,20 +1,25 @@
 use crate::{
-    DEFAULT_PAGE_SIZE, Repository, TagRow, TagTitleRow, URL_SAFE_NO_PAD_ENGINE, commit_transaction,
-    from_sqlx_err, remarks_tags,
+    Repository, TagRow, TagTitleRow, URL_SAFE_NO_PAD_ENGINE, commit_transaction, from_sqlx_err,
+    remark_attachments, remark_content_hashes, remark_idempotency_keys, remark_links,
+    remark_revisions, remarks_tags, resolve_page_size,
 };
 use canopus_definitions::{
-    ApplicationError, ApplicationResult, Page, Remark, RemarkAttributes, RemarkEssence, Tag,
-    TagAttributes, TagTitle,
+    ApplicationError, ApplicationResult, Page, PageSize, Remark, RemarkAttributes, RemarkEssence,
+    RemarkKind, RemarkKindName, Tag, TagAttributes, TagTitle,
 };
 use canopus_operations::remarks::{
-    DeleteRemark, GetRemark, InsertRemark, ListRemarks, NewRemark, RemarksPageParameters,
-    UpdateRemark,
+    AddRemarkTags, DeleteRemark, GetRemark, GetTrashedRemark, ImportRemark, InsertRemark,
+    ListRemarks, NewRemark, RemarksDatesFilter, RemarksOrderBy, RemarksPageParameters,
+    RemarksTagsFilter, RemoveRemarkTags, RestoreRemark, TrashRemark, UpdateRemark,
 };
 use chrono::{DateTime, Utc};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use sqlx::{PgPool, PgTransaction};
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    collections::{BTreeSet, HashMap},
+    str::FromStr,
+};
 use uuid::Uuid;
 
 pub struct RemarkRow {
@@ -22,13 +27,117 @@ pub struct RemarkRow {
     pub essence: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub kind: String,
+    pub payload: Option<String>,
+}
+
+struct RemarkListingRow {
+    id: Uuid,
+    essence: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    deleted_at: Option<DateTime<Utc>>,
+    kind: String,
+    payload: Option<String>,
+    position: DateTime<Utc>,
+}
+
+// Remarks edited after the listing snapshot keep the position of their last revision before it.
+// The filter is spliced between the query parts so keyset predicates and ordering stay on the
+// remarks table and can use its order indexes.
+macro_rules! matching_remarks_query {
+    ($query:ident $(, $record:ident)?; [$first:literal $(, $part:literal)* $(,)?], $($args:expr),*) => {
+        sqlx::$query!(
+            $($record,)?
+            $first
+            $(+ r#"
+FROM remarks
+CROSS JOIN LATERAL (
+    SELECT CASE
+        WHEN remarks.updated_at <= $1 THEN remarks.updated_at
+        ELSE (
+            SELECT MAX(remark_revisions.created_at)
+            FROM remark_revisions
+            WHERE remark_revisions.remark_id = remarks.id AND remark_revisions.created_at <= $1
+        )
+    END AS updated_at
+) AS snapshot
+WHERE snapshot.updated_at IS NOT NULL
+AND remarks.created_at <= $1
+AND (remarks.deleted_at IS NOT NULL) = $2
+AND ($3::timestamptz IS NULL OR remarks.created_at >= $3)
+AND ($4::timestamptz IS NULL OR remarks.created_at < $4)
+AND ($5::timestamptz IS NULL OR snapshot.updated_at >= $5)
+AND ($6::timestamptz IS NULL OR snapshot.updated_at < $6)
+AND ($7::text IS NULL OR remarks.kind = $7)
+AND NOT EXISTS (
+    SELECT 1
+    FROM unnest($8::text[]) AS wanted(title)
+    WHERE NOT EXISTS (
+        SELECT 1
+        FROM remarks_tags
+        JOIN tags ON tags.id = remarks_tags.tag_id
+        WHERE remarks_tags.remark_id = remarks.id
+        AND (tags.title = wanted.title OR starts_with(tags.title, wanted.title || '/'))
+    )
+)
+AND (
+    cardinality($9::text[]) = 0
+    OR EXISTS (
+        SELECT 1
+        FROM remarks_tags
+        JOIN tags ON tags.id = remarks_tags.tag_id
+        JOIN unnest($9::text[]) AS wanted(title)
+        ON tags.title = wanted.title OR starts_with(tags.title, wanted.title || '/')
+        WHERE remarks_tags.remark_id = remarks.id
+    )
+)
+AND NOT EXISTS (
+    SELECT 1
+    FROM remarks_tags
+    JOIN tags ON tags.id = remarks_tags.tag_id
+    JOIN unnest($10::text[]) AS unwanted(title)
+    ON tags.title = unwanted.title OR starts_with(tags.title, unwanted.title || '/')
+    WHERE remarks_tags.remark_id = remarks.id
+)
+"# + $part)*,
+            $($args),*
+        )
+    };
+}
+
+impl AddRemarkTags for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn add_remark_tags(
+        &self,
+        remark: &mut Remark,
+        tags: &[TagTitle],
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
+        let mut tx = self.begin_transaction().await?;
+
+        refresh_remark(&mut tx, remark, expected_updated_at).await?;
+
+        let titles: Vec<TagTitle> = remark.tags().into_iter().chain(tags).cloned().collect();
+
+        save_remark_tags(&mut tx, remark, titles).await?;
+
+        commit_transaction(tx).await
+    }
 }
 
 impl DeleteRemark for Repository {
     #[tracing::instrument(skip_all)]
-    async fn delete_remark(&self, remark: &Remark) -> ApplicationResult<()> {
+    async fn delete_remark(
+        &self,
+        remark: &Remark,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
         let mut tx = self.begin_transaction().await?;
 
+        check_updated_at(&mut tx, remark.id(), expected_updated_at).await?;
+
         let rec = sqlx::query!("DELETE FROM remarks WHERE id = $1", remark.id())
             .execute(&mut *tx)
             .await
@@ -39,86 +148,82 @@ impl DeleteRemark for Repository {
         }
 
         remarks_tags::delete_unused_remarks_tags(&mut tx).await?;
+        remark_revisions::delete(&mut tx, remark.id()).await?;
+        remark_links::delete(&mut tx, remark.id()).await?;
+        remark_idempotency_keys::delete(&mut tx, remark.id()).await?;
+        remark_content_hashes::delete(&mut tx, remark.id()).await?;
+        let attachment_ids = remark_attachments::delete(&mut tx, remark.id()).await?;
         delete_unused_tags(&mut tx).await?;
 
-        commit_transaction(tx).await
+        commit_transaction(tx).await?;
+
+        self.attachments_storage.remove_files(&attachment_ids).await
     }
 }
 
 impl GetRemark for Repository {
     #[tracing::instrument(skip_all)]
     async fn get_remark(&self, id: Uuid) -> ApplicationResult<Remark> {
-        let RemarkRow {
-            id,
-            essence,
-            created_at,
-            updated_at,
-        } = sqlx::query_as!(RemarkRow, "SELECT * FROM remarks WHERE id = $1", id)
-            .fetch_one(&self.pool)
-            .await
-            .map_err(from_sqlx_err)?;
+        find_remark(&self.pool, id, false).await
+    }
+}
+
+impl GetTrashedRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn get_trashed_remark(&self, id: Uuid) -> ApplicationResult<Remark> {
+        find_remark(&self.pool, id, true).await
+    }
+}
 
-        let tag_titles = sqlx::query_as!(
-            TagTitleRow,
+impl ImportRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn import_remark(&self, remark: &mut Remark) -> ApplicationResult<bool> {
+        let mut tx = self.begin_transaction().await?;
+
+        let imported = sqlx::query_scalar!(
             r#"
-SELECT tags.title FROM tags
-JOIN remarks_tags ON remarks_tags.tag_id = tags.id AND remarks_tags.remark_id = $1
-ORDER BY tags.title DESC
+INSERT INTO remarks ( id, essence, created_at, updated_at, deleted_at, kind, payload )
+VALUES ( $1, $2, $3, $4, $5, $6, $7::text::jsonb )
+ON CONFLICT (id) DO NOTHING
+RETURNING id
             "#,
-            id
+            remark.id(),
+            remark.essence().as_str(),
+            remark.created_at(),
+            remark.updated_at(),
+            remark.deleted_at(),
+            remark.kind().name().as_str(),
+            remark.kind().payload()
         )
-        .fetch_all(&self.pool)
+        .fetch_optional(&mut *tx)
         .await
         .map_err(from_sqlx_err)?;
 
-        let tags = tag_titles
-            .into_iter()
-            .map(TryInto::<TagTitle>::try_into)
-            .collect::<ApplicationResult<Vec<TagTitle>>>()?;
+        if imported.is_none() {
+            return Ok(false);
+        }
 
-        let remark = Remark::new(RemarkAttributes {
-            id,
-            essence: RemarkEssence::new(essence)?,
-            tags,
-            created_at,
-            updated_at,
-        });
+        let tags = link_tags(&mut tx, remark.id(), remark.tags()).await?;
+        remark.set_tags(tags);
 
-        Ok(remark)
+        remark_revisions::create(&mut tx, remark).await?;
+        remark_links::save(&mut tx, remark).await?;
+
+        commit_transaction(tx).await?;
+
+        Ok(true)
     }
 }
 
 impl InsertRemark for Repository {
     #[tracing::instrument(skip_all)]
     async fn insert_remark(&self, new_remark: NewRemark) -> Result<Remark, ApplicationError> {
-        let NewRemark { essence, tags } = new_remark;
-
         let mut tx = self.begin_transaction().await?;
 
-        let rec = sqlx::query!(
-            r#"
-INSERT INTO remarks ( essence )
-VALUES ( $1 )
-RETURNING id, created_at, updated_at
-            "#,
-            essence.as_str()
-        )
-        .fetch_one(&mut *tx)
-        .await
-        .map_err(from_sqlx_err)?;
-
-        link_tags(&mut tx, rec.id, tags.iter().collect()).await?;
+        let remark = insert_remark(&mut tx, new_remark).await?;
 
         commit_transaction(tx).await?;
 
-        let remark = Remark::new(RemarkAttributes {
-            id: rec.id,
-            essence,
-            tags,
-            created_at: rec.created_at,
-            updated_at: rec.updated_at,
-        });
-
         Ok(remark)
     }
 }
@@ -129,36 +234,268 @@ impl ListRemarks for Repository {
         &self,
         parameters: RemarksPageParameters,
     ) -> ApplicationResult<Page<Remark>> {
-        let RemarksPageParameters { page_token } = parameters;
+        let RemarksPageParameters {
+            page_token,
+            page_size,
+            order_by,
+            dates_filter,
+            tags_filter,
+            kind,
+            trashed,
+        } = parameters;
 
         let page_token = page_token.map(TryInto::<PageToken>::try_into).transpose()?;
 
+        let page_size = resolve_page_size(
+            page_size,
+            page_token.as_ref().and_then(|token| token.page_size),
+        )?;
+
+        let order_by = match &page_token {
+            Some(token) => token.order_by()?,
+            None => order_by,
+        };
+
         let last_id = page_token
             .as_ref()
             .map(|token| token.id)
             .unwrap_or(Uuid::nil());
 
-        let last_created_at = page_token
-            .map(|token| token.created_at)
-            .unwrap_or(Utc::now());
-
-        let rows = sqlx::query_as!(
-            RemarkRow,
-            r#"
-SELECT * FROM remarks
-WHERE created_at < $1 OR (created_at = $1 AND id > $2)
-ORDER BY created_at DESC, id ASC
-LIMIT $3
-            "#,
-            last_created_at,
-            last_id,
-            DEFAULT_PAGE_SIZE,
-        )
-        .fetch_all(&self.pool)
-        .await
+        let last_position = page_token.as_ref().map(|token| token.position);
+
+        let (dates_filter, tags_filter, kind, as_of, total_size) = match page_token {
+            Some(token) => (
+                token.dates_filter,
+                token.tags_filter,
+                token.kind,
+                token.as_of,
+                token.total_size,
+            ),
+            None => (dates_filter.into(), tags_filter.into(), kind, None, None),
+        };
+
+        let as_of = match as_of {
+            Some(as_of) => as_of,
+            None => sqlx::query_scalar!(r#"SELECT now() AS "now!""#)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(from_sqlx_err)?,
+        };
+
+        let kind_name = kind.map(|kind| kind.as_str());
+
+        let total_size = match total_size {
+            Some(total_size) => total_size,
+            None => matching_remarks_query!(
+                query_scalar;
+                [r#"SELECT COUNT(*) AS "count!""#, ""],
+                as_of,
+                trashed,
+                dates_filter.created_after,
+                dates_filter.created_before,
+                dates_filter.updated_after,
+                dates_filter.updated_before,
+                kind_name,
+                &tags_filter.include_all,
+                &tags_filter.include_any,
+                &tags_filter.exclude
+            )
+            .fetch_one(&self.pool)
+            .await
+            .map_err(from_sqlx_err)?,
+        };
+
+        let rows = match order_by {
+            RemarksOrderBy::CreatedAtAsc => {
+                matching_remarks_query!(
+                    query_as, RemarkListingRow;
+                    [
+                        r#"
+SELECT
+    remarks.id AS "id!", remarks.essence AS "essence!", remarks.created_at AS "created_at!",
+    remarks.updated_at AS "updated_at!", remarks.deleted_at, remarks.kind AS "kind!",
+    remarks.payload::text AS "payload?", remarks.created_at AS "position!"
+                        "#,
+                        r#"
+AND (remarks.created_at, remarks.id) > (COALESCE($11::timestamptz, '-infinity'), $12)
+ORDER BY remarks.created_at ASC, remarks.id ASC
+LIMIT $13
+                        "#,
+                    ],
+                    as_of,
+                    trashed,
+                    dates_filter.created_after,
+                    dates_filter.created_before,
+                    dates_filter.updated_after,
+                    dates_filter.updated_before,
+                    kind_name,
+                    &tags_filter.include_all,
+                    &tags_filter.include_any,
+                    &tags_filter.exclude,
+                    last_position,
+                    last_id,
+                    *page_size
+                )
+                .fetch_all(&self.pool)
+                .await
+            }
+            RemarksOrderBy::CreatedAtDesc => {
+                matching_remarks_query!(
+                    query_as, RemarkListingRow;
+                    [
+                        r#"
+SELECT
+    remarks.id AS "id!", remarks.essence AS "essence!", remarks.created_at AS "created_at!",
+    remarks.updated_at AS "updated_at!", remarks.deleted_at, remarks.kind AS "kind!",
+    remarks.payload::text AS "payload?", remarks.created_at AS "position!"
+                        "#,
+                        r#"
+AND (remarks.created_at, remarks.id) < (COALESCE($11::timestamptz, 'infinity'), $12)
+ORDER BY remarks.created_at DESC, remarks.id DESC
+LIMIT $13
+                        "#,
+                    ],
+                    as_of,
+                    trashed,
+                    dates_filter.created_after,
+                    dates_filter.created_before,
+                    dates_filter.updated_after,
+                    dates_filter.updated_before,
+                    kind_name,
+                    &tags_filter.include_all,
+                    &tags_filter.include_any,
+                    &tags_filter.exclude,
+                    last_position,
+                    last_id,
+                    *page_size
+                )
+                .fetch_all(&self.pool)
+                .await
+            }
+            RemarksOrderBy::UpdatedAtAsc => {
+                matching_remarks_query!(
+                    query_as, RemarkListingRow;
+                    [
+                        r#"
+SELECT
+    id AS "id!", essence AS "essence!", created_at AS "created_at!", updated_at AS "updated_at!",
+    deleted_at, kind AS "kind!", payload AS "payload?", position AS "position!"
+FROM (
+SELECT * FROM (
+SELECT
+    remarks.id, remarks.essence, remarks.created_at, remarks.updated_at, remarks.deleted_at,
+    remarks.kind, remarks.payload::text AS payload, remarks.updated_at AS position
+                        "#,
+                        r#"
+AND remarks.updated_at <= $1
+AND (remarks.updated_at, remarks.id) > (COALESCE($11::timestamptz, '-infinity'), $12)
+ORDER BY remarks.updated_at ASC, remarks.id ASC
+LIMIT $13
+) AS unchanged
+UNION ALL
+SELECT * FROM (
+SELECT
+    remarks.id, remarks.essence, remarks.created_at, remarks.updated_at, remarks.deleted_at,
+    remarks.kind, remarks.payload::text AS payload, snapshot.updated_at AS position
+                        "#,
+                        r#"
+AND remarks.updated_at > $1
+AND (snapshot.updated_at, remarks.id) > (COALESCE($11::timestamptz, '-infinity'), $12)
+ORDER BY snapshot.updated_at ASC, remarks.id ASC
+LIMIT $13
+) AS edited
+) AS page
+ORDER BY position ASC, id ASC
+LIMIT $13
+                        "#,
+                    ],
+                    as_of,
+                    trashed,
+                    dates_filter.created_after,
+                    dates_filter.created_before,
+                    dates_filter.updated_after,
+                    dates_filter.updated_before,
+                    kind_name,
+                    &tags_filter.include_all,
+                    &tags_filter.include_any,
+                    &tags_filter.exclude,
+                    last_position,
+                    last_id,
+                    *page_size
+                )
+                .fetch_all(&self.pool)
+                .await
+            }
+            RemarksOrderBy::UpdatedAtDesc => {
+                matching_remarks_query!(
+                    query_as, RemarkListingRow;
+                    [
+                        r#"
+SELECT
+    id AS "id!", essence AS "essence!", created_at AS "created_at!", updated_at AS "updated_at!",
+    deleted_at, kind AS "kind!", payload AS "payload?", position AS "position!"
+FROM (
+SELECT * FROM (
+SELECT
+    remarks.id, remarks.essence, remarks.created_at, remarks.updated_at, remarks.deleted_at,
+    remarks.kind, remarks.payload::text AS payload, remarks.updated_at AS position
+                        "#,
+                        r#"
+AND remarks.updated_at <= $1
+AND (remarks.updated_at, remarks.id) < (COALESCE($11::timestamptz, 'infinity'), $12)
+ORDER BY remarks.updated_at DESC, remarks.id DESC
+LIMIT $13
+) AS unchanged
+UNION ALL
+SELECT * FROM (
+SELECT
+    remarks.id, remarks.essence, remarks.created_at, remarks.updated_at, remarks.deleted_at,
+    remarks.kind, remarks.payload::text AS payload, snapshot.updated_at AS position
+                        "#,
+                        r#"
+AND remarks.updated_at > $1
+AND (snapshot.updated_at, remarks.id) < (COALESCE($11::timestamptz, 'infinity'), $12)
+ORDER BY snapshot.updated_at DESC, remarks.id DESC
+LIMIT $13
+) AS edited
+) AS page
+ORDER BY position DESC, id DESC
+LIMIT $13
+                        "#,
+                    ],
+                    as_of,
+                    trashed,
+                    dates_filter.created_after,
+                    dates_filter.created_before,
+                    dates_filter.updated_after,
+                    dates_filter.updated_before,
+                    kind_name,
+                    &tags_filter.include_all,
+                    &tags_filter.include_any,
+                    &tags_filter.exclude,
+                    last_position,
+                    last_id,
+                    *page_size
+                )
+                .fetch_all(&self.pool)
+                .await
+            }
+        }
         .map_err(from_sqlx_err)?;
 
-        let next_page_token = PageToken::from_rows(&rows).map(Into::into);
+        let next_page_token = PageToken {
+            id: Uuid::nil(),
+            position: as_of,
+            order_by: Some(order_by.as_str().to_string()),
+            page_size: Some(*page_size),
+            dates_filter,
+            tags_filter,
+            kind,
+            as_of: Some(as_of),
+            total_size: Some(total_size),
+        }
+        .next(&rows, page_size)
+        .map(Into::into);
         let remark_ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
         let remarks_tags = preload_tags(&self.pool, &remark_ids)
             .await
@@ -169,11 +506,15 @@ LIMIT $3
         let items: Vec<Remark> = rows
             .into_iter()
             .map(|row| {
-                let RemarkRow {
+                let RemarkListingRow {
                     id,
                     essence,
                     created_at,
                     updated_at,
+                    deleted_at,
+                    kind,
+                    payload,
+                    position: _,
                 } = row;
 
                 let tags = grouped_tags
@@ -189,6 +530,8 @@ LIMIT $3
                     tags,
                     created_at,
                     updated_at,
+                    deleted_at,
+                    kind: RemarkKind::from_parts(&kind, payload.as_deref())?,
                 });
 
                 Ok(remark)
@@ -198,34 +541,105 @@ LIMIT $3
         Ok(Page {
             next_page_token,
             items,
+            total_size: Some(total_size),
         })
     }
 }
 
-impl UpdateRemark for Repository {
+impl RemoveRemarkTags for Repository {
     #[tracing::instrument(skip_all)]
-    async fn update_remark(&self, remark: &mut Remark) -> Result<(), ApplicationError> {
+    async fn remove_remark_tags(
+        &self,
+        remark: &mut Remark,
+        tags: &[TagTitle],
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
+        let mut tx = self.begin_transaction().await?;
+
+        refresh_remark(&mut tx, remark, expected_updated_at).await?;
+
+        let titles: Vec<TagTitle> = remark
+            .tags()
+            .into_iter()
+            .filter(|title| !tags.contains(title))
+            .cloned()
+            .collect();
+
+        save_remark_tags(&mut tx, remark, titles).await?;
+
+        commit_transaction(tx).await
+    }
+}
+
+impl RestoreRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn restore_remark(
+        &self,
+        remark: &mut Remark,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
         let mut tx = self.begin_transaction().await?;
 
+        check_updated_at(&mut tx, remark.id(), expected_updated_at).await?;
+
+        let rec = sqlx::query!(
+            "UPDATE remarks SET deleted_at = NULL WHERE id = $1 AND deleted_at IS NOT NULL",
+            remark.id()
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        if rec.rows_affected() == 0 {
+            return Err(ApplicationError::NotFound);
+        }
+
+        commit_transaction(tx).await?;
+
+        remark.set_deleted_at(None);
+
+        Ok(())
+    }
+}
+
+impl TrashRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn trash_remark(
+        &self,
+        remark: &mut Remark,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
+        let mut tx = self.begin_transaction().await?;
+
+        check_updated_at(&mut tx, remark.id(), expected_updated_at).await?;
+
         let rec = sqlx::query!(
             r#"
 UPDATE remarks
-SET essence = $2, updated_at = DEFAULT
-WHERE id = $1
-RETURNING updated_at
+SET deleted_at = now()
+WHERE id = $1 AND deleted_at IS NULL
+RETURNING deleted_at AS "deleted_at!"
             "#,
-            remark.id(),
-            remark.essence().as_str()
+            remark.id()
         )
         .fetch_one(&mut *tx)
         .await
         .map_err(from_sqlx_err)?;
 
-        remark.set_updated_at(rec.updated_at)?;
+        commit_transaction(tx).await?;
+
+        remark.set_deleted_at(Some(rec.deleted_at));
 
-        unlink_tags(&mut tx, remark.id()).await?;
-        link_tags(&mut tx, remark.id(), remark.tags()).await?;
-        delete_unused_tags(&mut tx).await?;
+        Ok(())
+    }
+}
+
+impl UpdateRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn update_remark(&self, remark: &mut Remark) -> Result<(), ApplicationError> {
+        let mut tx = self.begin_transaction().await?;
+
+        save_remark(&mut tx, remark).await?;
 
         commit_transaction(tx).await?;
 
@@ -236,20 +650,116 @@ RETURNING updated_at
 #[derive(Serialize, Deserialize)]
 struct PageToken {
     id: Uuid,
-    created_at: DateTime<Utc>,
+
+    #[serde(alias = "created_at")]
+    position: DateTime<Utc>,
+
+    #[serde(default)]
+    order_by: Option<String>,
+
+    #[serde(default)]
+    page_size: Option<i64>,
+
+    #[serde(default)]
+    dates_filter: DatesFilter,
+
+    #[serde(default)]
+    tags_filter: TagsFilter,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kind: Option<RemarkKindName>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    as_of: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    total_size: Option<i64>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct DatesFilter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_after: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_before: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_after: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_before: Option<DateTime<Utc>>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct TagsFilter {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    include_all: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    include_any: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    exclude: Vec<String>,
 }
 
 impl PageToken {
-    fn from_rows(rows: &[RemarkRow]) -> Option<Self> {
-        if rows.len() < DEFAULT_PAGE_SIZE as usize {
+    fn next(self, rows: &[RemarkListingRow], page_size: PageSize) -> Option<Self> {
+        if rows.len() < *page_size as usize {
             return None;
         }
 
         rows.last().map(|row| PageToken {
             id: row.id,
-            created_at: row.created_at,
+            position: row.position,
+            ..self
         })
     }
+
+    fn order_by(&self) -> ApplicationResult<RemarksOrderBy> {
+        self.order_by
+            .as_deref()
+            .map(str::parse)
+            .unwrap_or(Ok(RemarksOrderBy::default()))
+            .map_err(|_err| ApplicationError::invalid_argument("malformed remarks page token"))
+    }
+}
+
+impl From<RemarksDatesFilter> for DatesFilter {
+    fn from(value: RemarksDatesFilter) -> Self {
+        let RemarksDatesFilter {
+            created_after,
+            created_before,
+            updated_after,
+            updated_before,
+        } = value;
+
+        DatesFilter {
+            created_after,
+            created_before,
+            updated_after,
+            updated_before,
+        }
+    }
+}
+
+impl From<RemarksTagsFilter> for TagsFilter {
+    fn from(value: RemarksTagsFilter) -> Self {
+        let RemarksTagsFilter {
+            include_all,
+            include_any,
+            exclude,
+        } = value;
+
+        let into_strings =
+            |titles: BTreeSet<TagTitle>| titles.iter().map(ToString::to_string).collect();
+
+        TagsFilter {
+            include_all: into_strings(include_all),
+            include_any: into_strings(include_any),
+            exclude: into_strings(exclude),
+        }
+    }
 }
 
 impl FromStr for PageToken {
@@ -271,10 +781,60 @@ impl TryFrom<canopus_definitions::PageToken> for PageToken {
     fn try_from(value: canopus_definitions::PageToken) -> ApplicationResult<Self> {
         value
             .parse()
-            .map_err(|_err| ApplicationError::invalid_argument("malformed tags page token"))
+            .map_err(|_err| ApplicationError::invalid_argument("malformed remarks page token"))
     }
 }
 
+pub async fn insert_remark(
+    tx: &mut PgTransaction<'_>,
+    new_remark: NewRemark,
+) -> ApplicationResult<Remark> {
+    let NewRemark {
+        essence,
+        tags,
+        created_at,
+        kind,
+    } = new_remark;
+
+    let rec = sqlx::query!(
+        r#"
+INSERT INTO remarks ( essence, created_at, updated_at, kind, payload )
+VALUES (
+    $1,
+    COALESCE($2::timestamptz, now()),
+    COALESCE($2::timestamptz, now()),
+    $3,
+    $4::text::jsonb
+)
+RETURNING id, created_at, updated_at
+        "#,
+        essence.as_str(),
+        created_at,
+        kind.name().as_str(),
+        kind.payload()
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    let tags = link_tags(tx, rec.id, tags.iter().collect()).await?;
+
+    let remark = Remark::new(RemarkAttributes {
+        id: rec.id,
+        essence,
+        tags,
+        created_at: rec.created_at,
+        updated_at: rec.updated_at,
+        deleted_at: None,
+        kind,
+    });
+
+    remark_revisions::create(tx, &remark).await?;
+    remark_links::save(tx, &remark).await?;
+
+    Ok(remark)
+}
+
 async fn insert_tag(tx: &mut PgTransaction<'_>, title: TagTitle) -> ApplicationResult<Tag> {
     let rec = sqlx::query!(
         r#"
@@ -293,6 +853,7 @@ RETURNING id, created_at, updated_at
         title,
         created_at: rec.created_at,
         updated_at: rec.updated_at,
+        usage_count: None,
     });
 
     Ok(tag)
@@ -302,13 +863,16 @@ async fn link_tags(
     tx: &mut PgTransaction<'_>,
     remark_id: Uuid,
     tags: Vec<&TagTitle>,
-) -> ApplicationResult<()> {
+) -> ApplicationResult<Vec<TagTitle>> {
+    let mut linked_titles = Vec::with_capacity(tags.len());
+
     for title in tags {
         let tag = find_or_create_tag(tx, title.clone()).await?;
         assign_remark_tags(tx, remark_id, &tag).await?;
+        linked_titles.push(tag.title().clone());
     }
 
-    Ok(())
+    Ok(linked_titles)
 }
 
 async fn assign_remark_tags(
@@ -328,25 +892,110 @@ async fn assign_remark_tags(
     Ok(())
 }
 
+pub async fn find_remark(pool: &PgPool, id: Uuid, trashed: bool) -> ApplicationResult<Remark> {
+    let RemarkRow {
+        id,
+        essence,
+        created_at,
+        updated_at,
+        deleted_at,
+        kind,
+        payload,
+    } = sqlx::query_as!(
+        RemarkRow,
+        r#"
+SELECT id, essence, created_at, updated_at, deleted_at, kind, payload::text AS "payload?"
+FROM remarks
+WHERE id = $1 AND (deleted_at IS NOT NULL) = $2
+        "#,
+        id,
+        trashed
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    let tag_titles = sqlx::query_as!(
+        TagTitleRow,
+        r#"
+SELECT tags.title FROM tags
+JOIN remarks_tags ON remarks_tags.tag_id = tags.id AND remarks_tags.remark_id = $1
+ORDER BY tags.title DESC
+        "#,
+        id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    let tags = tag_titles
+        .into_iter()
+        .map(TryInto::<TagTitle>::try_into)
+        .collect::<ApplicationResult<Vec<TagTitle>>>()?;
+
+    let remark = Remark::new(RemarkAttributes {
+        id,
+        essence: RemarkEssence::new(essence)?,
+        tags,
+        created_at,
+        updated_at,
+        deleted_at,
+        kind: RemarkKind::from_parts(&kind, payload.as_deref())?,
+    });
+
+    Ok(remark)
+}
+
 async fn find_or_create_tag(tx: &mut PgTransaction<'_>, title: TagTitle) -> ApplicationResult<Tag> {
-    let Some(tag) = find_tag_by_title(tx, &title).await? else {
-        return insert_tag(tx, title).await;
-    };
+    if let Some(tag) = find_tag_by_title(tx, &title).await? {
+        return Ok(tag);
+    }
 
-    Ok(tag)
+    if let Some(tag) = find_tag_by_alias(tx, &title).await? {
+        return Ok(tag);
+    }
+
+    create_ancestor_tags(tx, &title).await?;
+
+    insert_tag(tx, title).await
+}
+
+pub async fn create_ancestor_tags(
+    tx: &mut PgTransaction<'_>,
+    title: &TagTitle,
+) -> ApplicationResult<()> {
+    for ancestor in title.ancestors() {
+        if find_tag_by_title(tx, &ancestor).await?.is_none() {
+            insert_tag(tx, ancestor).await?;
+        }
+    }
+
+    Ok(())
 }
 
 pub async fn delete_unused_tags(tx: &mut PgTransaction<'_>) -> ApplicationResult<()> {
     sqlx::query!(
         r#"
-WITH unused_tags AS (
-    SELECT id
+WITH used_tags AS (
+    SELECT DISTINCT tags.title
     FROM tags
-    LEFT JOIN remarks_tags ON remarks_tags.tag_id = tags.id
-    WHERE remarks_tags.tag_id IS NULL
+    JOIN remarks_tags ON remarks_tags.tag_id = tags.id
 )
 DELETE FROM tags
-WHERE tags.id IN (SELECT unused_tags.id FROM unused_tags)
+WHERE NOT EXISTS (
+    SELECT 1 FROM used_tags
+    WHERE used_tags.title = tags.title OR starts_with(used_tags.title, tags.title || '/')
+)
+        "#
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    sqlx::query!(
+        r#"
+DELETE FROM tag_aliases
+WHERE NOT EXISTS (SELECT 1 FROM tags WHERE tags.id = tag_aliases.tag_id)
         "#
     )
     .execute(&mut **tx)
@@ -356,6 +1005,26 @@ WHERE tags.id IN (SELECT unused_tags.id FROM unused_tags)
     Ok(())
 }
 
+async fn find_tag_by_alias(
+    tx: &mut PgTransaction<'_>,
+    title: &TagTitle,
+) -> ApplicationResult<Option<Tag>> {
+    let tag = sqlx::query_as!(
+        TagRow,
+        r#"
+SELECT tags.* FROM tags
+JOIN tag_aliases ON tag_aliases.tag_id = tags.id
+WHERE tag_aliases.title = $1
+        "#,
+        title.as_str()
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    tag.map(TryInto::try_into).transpose()
+}
+
 async fn find_tag_by_title(
     tx: &mut PgTransaction<'_>,
     title: &TagTitle,
@@ -372,7 +1041,7 @@ async fn find_tag_by_title(
     tag.map(TryInto::try_into).transpose()
 }
 
-async fn preload_tags(pool: &PgPool, remark_ids: &[Uuid]) -> sqlx::Result<Vec<(Uuid, TagRow)>> {
+pub async fn preload_tags(pool: &PgPool, remark_ids: &[Uuid]) -> sqlx::Result<Vec<(Uuid, TagRow)>> {
     use sqlx::Row;
 
     let rows = sqlx::query(
@@ -406,6 +1075,133 @@ ORDER BY tags.title DESC
     Ok(tags)
 }
 
+async fn check_updated_at(
+    tx: &mut PgTransaction<'_>,
+    id: Uuid,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<()> {
+    let Some(expected_updated_at) = expected_updated_at else {
+        return Ok(());
+    };
+
+    let updated_at = sqlx::query_scalar!(
+        "SELECT updated_at FROM remarks WHERE id = $1 FOR UPDATE",
+        id
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    if updated_at != expected_updated_at {
+        return Err(ApplicationError::Conflict);
+    }
+
+    Ok(())
+}
+
+async fn refresh_remark(
+    tx: &mut PgTransaction<'_>,
+    remark: &mut Remark,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<()> {
+    let rec = sqlx::query!(
+        r#"
+SELECT essence, updated_at, kind, payload::text AS "payload?" FROM remarks
+WHERE id = $1 AND deleted_at IS NULL
+FOR UPDATE
+        "#,
+        remark.id()
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    if expected_updated_at.is_some_and(|updated_at| updated_at != rec.updated_at) {
+        return Err(ApplicationError::Conflict);
+    }
+
+    let tag_titles = sqlx::query_as!(
+        TagTitleRow,
+        r#"
+SELECT tags.title FROM tags
+JOIN remarks_tags ON remarks_tags.tag_id = tags.id AND remarks_tags.remark_id = $1
+ORDER BY tags.title DESC
+        "#,
+        remark.id()
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    let tags = tag_titles
+        .into_iter()
+        .map(TryInto::<TagTitle>::try_into)
+        .collect::<ApplicationResult<Vec<TagTitle>>>()?;
+
+    remark.set_essence(RemarkEssence::new(rec.essence)?);
+    remark.set_kind(RemarkKind::from_parts(&rec.kind, rec.payload.as_deref())?);
+    remark.set_tags(tags);
+    remark.set_updated_at(rec.updated_at)
+}
+
+async fn save_remark_tags(
+    tx: &mut PgTransaction<'_>,
+    remark: &mut Remark,
+    titles: Vec<TagTitle>,
+) -> ApplicationResult<()> {
+    let titles = BTreeSet::from_iter(titles);
+
+    if titles.iter().eq(remark.tags()) {
+        return Ok(());
+    }
+
+    remark.set_tags(titles.into_iter().collect());
+
+    save_remark(tx, remark).await
+}
+
+pub async fn save_remark(tx: &mut PgTransaction<'_>, remark: &mut Remark) -> ApplicationResult<()> {
+    let previous_updated_at = sqlx::query_scalar!(
+        "SELECT updated_at FROM remarks WHERE id = $1 FOR UPDATE",
+        remark.id()
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    if previous_updated_at != remark.updated_at() {
+        return Err(ApplicationError::Conflict);
+    }
+
+    let rec = sqlx::query!(
+        r#"
+UPDATE remarks
+SET essence = $2, kind = $3, payload = $4::text::jsonb, updated_at = DEFAULT
+WHERE id = $1
+RETURNING updated_at
+        "#,
+        remark.id(),
+        remark.essence().as_str(),
+        remark.kind().name().as_str(),
+        remark.kind().payload()
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    remark.set_updated_at(rec.updated_at)?;
+
+    unlink_tags(tx, remark.id()).await?;
+    let tags = link_tags(tx, remark.id(), remark.tags()).await?;
+    delete_unused_tags(tx).await?;
+    remark.set_tags(tags);
+
+    remark_revisions::create(tx, remark).await?;
+    remark_links::save(tx, remark).await?;
+
+    Ok(())
+}
+
 async fn unlink_tags(tx: &mut PgTransaction<'_>, remark_id: Uuid) -> ApplicationResult<()> {
     sqlx::query!(
         r#"DELETE FROM remarks_tags WHERE remark_id = $1"#,
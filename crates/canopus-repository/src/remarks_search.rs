@@ -0,0 +1,201 @@
+use crate::{
+    Repository, TagRow, URL_SAFE_NO_PAD_ENGINE, from_sqlx_err, remarks::preload_tags,
+    resolve_page_size,
+};
+use canopus_definitions::{
+    ApplicationError, ApplicationResult, Page, PageSize, Remark, RemarkAttributes, RemarkEssence,
+    RemarkKind, RemarkSearchHit, RemarkSearchHitAttributes, TagTitle,
+};
+use canopus_operations::remarks::{RemarksSearchParameters, SearchRemarks};
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, str::FromStr};
+use uuid::Uuid;
+
+struct RemarkSearchRow {
+    id: Uuid,
+    essence: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    kind: String,
+    payload: Option<String>,
+    rank: f32,
+    snippet: String,
+}
+
+impl SearchRemarks for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn search_remarks(
+        &self,
+        parameters: RemarksSearchParameters,
+    ) -> ApplicationResult<Page<RemarkSearchHit>> {
+        let RemarksSearchParameters {
+            query,
+            page_token,
+            page_size,
+        } = parameters;
+
+        let page_token = page_token.map(TryInto::<PageToken>::try_into).transpose()?;
+
+        let page_size = resolve_page_size(
+            page_size,
+            page_token.as_ref().and_then(|token| token.page_size),
+        )?;
+
+        let offset = page_token.map(|token| token.offset).unwrap_or_default();
+
+        let rows = sqlx::query_as!(
+            RemarkSearchRow,
+            r#"
+SELECT
+    remarks.id,
+    remarks.essence,
+    remarks.created_at,
+    remarks.updated_at,
+    remarks.kind,
+    remarks.payload::text AS "payload?",
+    ts_rank(remarks.essence_search, query) AS "rank!",
+    ts_headline('english', remarks.essence, query) AS "snippet!"
+FROM remarks, websearch_to_tsquery('english', $1) AS query
+WHERE remarks.essence_search @@ query AND remarks.deleted_at IS NULL
+ORDER BY ts_rank(remarks.essence_search, query) DESC, remarks.id ASC
+LIMIT $2
+OFFSET $3
+            "#,
+            query,
+            *page_size,
+            offset,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        let total_size = sqlx::query_scalar!(
+            r#"
+SELECT COUNT(*) AS "count!"
+FROM remarks, websearch_to_tsquery('english', $1) AS query
+WHERE remarks.essence_search @@ query AND remarks.deleted_at IS NULL
+            "#,
+            query,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        let next_page_token = PageToken::from_rows(&rows, page_size, offset).map(Into::into);
+        let remark_ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+        let remarks_tags = preload_tags(&self.pool, &remark_ids)
+            .await
+            .map_err(from_sqlx_err)?;
+        let mut grouped_tags: HashMap<Uuid, Vec<TagRow>> =
+            remarks_tags.into_iter().into_group_map();
+
+        let items = rows
+            .into_iter()
+            .map(|row| {
+                let RemarkSearchRow {
+                    id,
+                    essence,
+                    created_at,
+                    updated_at,
+                    kind,
+                    payload,
+                    rank,
+                    snippet,
+                } = row;
+
+                let tags = grouped_tags
+                    .remove(&id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|row| TagTitle::new(row.title))
+                    .collect::<ApplicationResult<Vec<TagTitle>>>()?;
+
+                let remark = Remark::new(RemarkAttributes {
+                    id,
+                    essence: RemarkEssence::new(essence)?,
+                    tags,
+                    created_at,
+                    updated_at,
+                    deleted_at: None,
+                    kind: RemarkKind::from_parts(&kind, payload.as_deref())?,
+                });
+
+                Ok(RemarkSearchHit::new(RemarkSearchHitAttributes {
+                    remark,
+                    rank,
+                    snippet,
+                }))
+            })
+            .collect::<ApplicationResult<Vec<RemarkSearchHit>>>()?;
+
+        Ok(Page {
+            next_page_token,
+            items,
+            total_size: Some(total_size),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PageToken {
+    offset: i64,
+
+    #[serde(default)]
+    page_size: Option<i64>,
+}
+
+impl PageToken {
+    fn from_rows(rows: &[RemarkSearchRow], page_size: PageSize, offset: i64) -> Option<Self> {
+        if rows.len() < *page_size as usize {
+            return None;
+        }
+
+        Some(PageToken {
+            offset: offset + rows.len() as i64,
+            page_size: Some(*page_size),
+        })
+    }
+}
+
+impl FromStr for PageToken {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> eyre::Result<Self> {
+        use base64::Engine;
+
+        let json = URL_SAFE_NO_PAD_ENGINE.decode(s)?;
+        let token = serde_json::from_slice(&json)?;
+
+        Ok(token)
+    }
+}
+
+impl TryFrom<canopus_definitions::PageToken> for PageToken {
+    type Error = ApplicationError;
+
+    fn try_from(value: canopus_definitions::PageToken) -> ApplicationResult<Self> {
+        value
+            .parse()
+            .map_err(|_err| ApplicationError::invalid_argument("malformed search page token"))
+    }
+}
+
+impl std::fmt::Display for PageToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use base64::Engine;
+
+        let json = serde_json::to_string(&self).map_err(|_err| std::fmt::Error)?;
+
+        let encoded_json = URL_SAFE_NO_PAD_ENGINE.encode(json);
+
+        f.write_str(&encoded_json)
+    }
+}
+
+impl From<PageToken> for canopus_definitions::PageToken {
+    fn from(value: PageToken) -> Self {
+        value.to_string().into()
+    }
+}
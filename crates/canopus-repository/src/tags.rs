@@ -1,10 +1,14 @@
 use crate::{
-    DEFAULT_PAGE_SIZE, Repository, URL_SAFE_NO_PAD_ENGINE, commit_transaction, from_sqlx_err,
+    Repository, URL_SAFE_NO_PAD_ENGINE, commit_transaction, from_sqlx_err, remarks,
+    resolve_page_size,
 };
 use canopus_definitions::{
-    ApplicationError, ApplicationResult, Page, Tag, TagAttributes, TagTitle,
+    ApplicationError, ApplicationResult, Page, PageSize, Tag, TagAttributes, TagTitle,
+};
+use canopus_operations::tags::{
+    GetTag, ListTagChildren, ListTags, MergeTags, SuggestTags, TagsOrderBy, TagsPageParameters,
+    UpdateTag,
 };
-use canopus_operations::tags::{GetTag, ListTags, TagsPageParameters, UpdateTag};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -17,6 +21,58 @@ pub struct TagRow {
     pub updated_at: DateTime<Utc>,
 }
 
+struct TagListingRow {
+    id: Uuid,
+    title: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    usage_count: i64,
+}
+
+// Usage counts are taken as of the listing snapshot, remarks edited after it count their last
+// revision before it
+macro_rules! listed_tags_query {
+    ($select:literal, $($args:expr),*) => {
+        sqlx::query_as!(
+            TagListingRow,
+            r#"
+WITH listed AS (
+    SELECT tags.id, tags.title, tags.created_at, tags.updated_at, usage.count AS usage_count
+    FROM tags
+    CROSS JOIN LATERAL (
+        SELECT COUNT(*)
+        FROM (
+            SELECT remarks.id
+            FROM remarks_tags
+            JOIN remarks ON remarks.id = remarks_tags.remark_id
+            WHERE remarks_tags.tag_id = tags.id
+            AND remarks.updated_at <= $1
+            AND (remarks.deleted_at IS NULL OR remarks.deleted_at > $1)
+            UNION ALL
+            SELECT remarks.id
+            FROM remarks
+            CROSS JOIN LATERAL (
+                SELECT remark_revisions.tags
+                FROM remark_revisions
+                WHERE remark_revisions.remark_id = remarks.id
+                AND remark_revisions.created_at <= $1
+                ORDER BY remark_revisions.number DESC
+                LIMIT 1
+            ) AS revision
+            WHERE remarks.updated_at > $1
+            AND remarks.created_at <= $1
+            AND (remarks.deleted_at IS NULL OR remarks.deleted_at > $1)
+            AND tags.title = ANY(revision.tags)
+        ) AS snapshot_remarks
+    ) AS usage(count)
+    WHERE tags.created_at <= $1
+)
+"# + $select,
+            $($args),*
+        )
+    };
+}
+
 pub struct TagTitleRow {
     pub title: String,
 }
@@ -32,56 +88,326 @@ impl GetTag for Repository {
     }
 }
 
+impl ListTagChildren for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn list_tag_children(&self, tag: &Tag) -> ApplicationResult<Vec<Tag>> {
+        sqlx::query_as!(
+            TagRow,
+            r#"
+SELECT * FROM tags
+WHERE starts_with(title, $1 || '/') AND strpos(substr(title, length($1) + 2), '/') = 0
+ORDER BY title ASC
+            "#,
+            tag.title().as_str()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?
+        .into_iter()
+        .map(TryInto::try_into)
+        .collect()
+    }
+}
+
 impl ListTags for Repository {
     #[tracing::instrument(skip_all)]
     async fn list_tags(&self, parameters: TagsPageParameters) -> ApplicationResult<Page<Tag>> {
-        let TagsPageParameters { page_token } = parameters;
+        let TagsPageParameters {
+            page_token,
+            page_size,
+            order_by,
+            with_usage_count,
+        } = parameters;
 
         let page_token = page_token.map(TryInto::<PageToken>::try_into).transpose()?;
 
+        let page_size = resolve_page_size(
+            page_size,
+            page_token.as_ref().and_then(|token| token.page_size),
+        )?;
+
+        let order_by = match &page_token {
+            Some(token) => token.order_by()?,
+            None => order_by,
+        };
+
         let last_id = page_token
             .as_ref()
             .map(|token| token.id)
             .unwrap_or(Uuid::nil());
 
-        let last_created_at = page_token
-            .map(|token| token.created_at)
-            .unwrap_or(Utc::now());
-
-        let rows = sqlx::query_as!(
-            TagRow,
-            r#"
-SELECT * FROM tags
-WHERE created_at < $1 OR (created_at = $1 AND id > $2)
+        let last_created_at = page_token.as_ref().and_then(|token| token.created_at);
+        let last_title = page_token.as_ref().and_then(|token| token.title.clone());
+        let last_usage_count = page_token.as_ref().and_then(|token| token.usage_count);
+
+        let as_of = match page_token.as_ref().and_then(|token| token.as_of) {
+            Some(as_of) => as_of,
+            None => sqlx::query_scalar!(r#"SELECT now() AS "now!""#)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(from_sqlx_err)?,
+        };
+
+        let total_size = match page_token.as_ref().and_then(|token| token.total_size) {
+            Some(total_size) => total_size,
+            None => sqlx::query_scalar!(
+                r#"SELECT COUNT(*) AS "count!" FROM tags WHERE created_at <= $1"#,
+                as_of
+            )
+            .fetch_one(&self.pool)
+            .await
+            .map_err(from_sqlx_err)?,
+        };
+
+        let rows = match order_by {
+            TagsOrderBy::Title => {
+                listed_tags_query!(
+                    r#"
+SELECT id AS "id!", title AS "title!", created_at AS "created_at!", updated_at AS "updated_at!",
+    usage_count AS "usage_count!"
+FROM listed
+WHERE $2::text IS NULL OR title > $2 OR (title = $2 AND id > $3)
+ORDER BY title ASC, id ASC
+LIMIT $4
+                "#,
+                    as_of,
+                    last_title,
+                    last_id,
+                    *page_size
+                )
+                .fetch_all(&self.pool)
+                .await
+            }
+            TagsOrderBy::UsageCount => {
+                listed_tags_query!(
+                    r#"
+SELECT id AS "id!", title AS "title!", created_at AS "created_at!", updated_at AS "updated_at!",
+    usage_count AS "usage_count!"
+FROM listed
+WHERE $2::bigint IS NULL OR usage_count < $2 OR (usage_count = $2 AND id > $3)
+ORDER BY usage_count DESC, id ASC
+LIMIT $4
+                "#,
+                    as_of,
+                    last_usage_count,
+                    last_id,
+                    *page_size
+                )
+                .fetch_all(&self.pool)
+                .await
+            }
+            TagsOrderBy::CreatedAtDesc => {
+                listed_tags_query!(
+                    r#"
+SELECT id AS "id!", title AS "title!", created_at AS "created_at!", updated_at AS "updated_at!",
+    usage_count AS "usage_count!"
+FROM listed
+WHERE $2::timestamptz IS NULL OR created_at < $2 OR (created_at = $2 AND id > $3)
 ORDER BY created_at DESC, id ASC
-LIMIT $3
-            "#,
-            last_created_at,
-            last_id,
-            DEFAULT_PAGE_SIZE,
-        )
-        .fetch_all(&self.pool)
-        .await
+LIMIT $4
+                "#,
+                    as_of,
+                    last_created_at,
+                    last_id,
+                    *page_size
+                )
+                .fetch_all(&self.pool)
+                .await
+            }
+        }
         .map_err(from_sqlx_err)?;
 
-        let next_page_token = PageToken::from_rows(&rows).map(Into::into);
+        let next_page_token =
+            PageToken::from_rows(&rows, page_size, order_by, as_of, total_size).map(Into::into);
         let items = rows
             .into_iter()
-            .map(TryInto::<Tag>::try_into)
+            .map(|row| row.into_tag(with_usage_count))
             .collect::<ApplicationResult<Vec<Tag>>>()?;
 
         Ok(Page {
             next_page_token,
             items,
+            total_size: Some(total_size),
         })
     }
 }
 
+impl MergeTags for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn merge_tags(&self, source: &Tag, target: &mut Tag) -> ApplicationResult<()> {
+        let mut tx = self.begin_transaction().await?;
+
+        let has_children = sqlx::query_scalar!(
+            r#"
+SELECT EXISTS(
+    SELECT 1 FROM tags WHERE starts_with(title, $1 || '/')
+) AS "exists!"
+            "#,
+            source.title().as_str()
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        if has_children {
+            return Err(ApplicationError::invalid_argument(
+                "can't merge a tag that has child tags",
+            ));
+        }
+
+        sqlx::query!(
+            r#"
+INSERT INTO remarks_tags ( remark_id, tag_id )
+SELECT remark_id, $2 FROM remarks_tags WHERE tag_id = $1
+ON CONFLICT DO NOTHING
+            "#,
+            source.id(),
+            target.id()
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        sqlx::query!("DELETE FROM remarks_tags WHERE tag_id = $1", source.id())
+            .execute(&mut *tx)
+            .await
+            .map_err(from_sqlx_err)?;
+
+        sqlx::query!(
+            "UPDATE tag_aliases SET tag_id = $2 WHERE tag_id = $1",
+            source.id(),
+            target.id()
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        sqlx::query!(
+            r#"
+INSERT INTO tag_aliases ( title, tag_id )
+VALUES ( $1, $2 )
+ON CONFLICT ( title ) DO UPDATE SET tag_id = EXCLUDED.tag_id
+            "#,
+            source.title().as_str(),
+            target.id()
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        sqlx::query!("DELETE FROM tags WHERE id = $1", source.id())
+            .execute(&mut *tx)
+            .await
+            .map_err(from_sqlx_err)?;
+
+        let rec = sqlx::query!(
+            r#"
+UPDATE tags
+SET updated_at = DEFAULT
+WHERE id = $1
+RETURNING updated_at
+            "#,
+            target.id()
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        target.set_updated_at(rec.updated_at)?;
+
+        remarks::delete_unused_tags(&mut tx).await?;
+
+        commit_transaction(tx).await?;
+
+        Ok(())
+    }
+}
+
+impl SuggestTags for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn suggest_tags(&self, prefix: &str, limit: PageSize) -> ApplicationResult<Vec<Tag>> {
+        let pattern = format!(
+            "{}%",
+            prefix
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+
+        sqlx::query_as!(
+            TagListingRow,
+            r#"
+SELECT tags.id, tags.title, tags.created_at, tags.updated_at, usage.count AS "usage_count!"
+FROM tags
+CROSS JOIN LATERAL (
+    SELECT COUNT(*)
+    FROM remarks_tags
+    JOIN remarks ON remarks.id = remarks_tags.remark_id AND remarks.deleted_at IS NULL
+    WHERE remarks_tags.tag_id = tags.id
+) AS usage(count)
+WHERE tags.title LIKE $1
+ORDER BY usage.count DESC, tags.title ASC
+LIMIT $2
+            "#,
+            pattern,
+            *limit,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?
+        .into_iter()
+        .map(|row| row.into_tag(true))
+        .collect()
+    }
+}
+
 impl UpdateTag for Repository {
     #[tracing::instrument(skip_all)]
     async fn update_tag(&self, tag: &mut Tag) -> Result<(), ApplicationError> {
         let mut tx = self.begin_transaction().await?;
 
+        let previous = sqlx::query!(
+            "SELECT title, updated_at FROM tags WHERE id = $1 FOR UPDATE",
+            tag.id()
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        if previous.updated_at != tag.updated_at() {
+            return Err(ApplicationError::Conflict);
+        }
+
+        let previous_title = previous.title;
+
+        let title_taken = sqlx::query_scalar!(
+            r#"
+SELECT EXISTS(
+    SELECT 1 FROM tags
+    WHERE id <> $3 AND (
+        title = $2
+        OR title IN (
+            SELECT $2 || substr(descendants.title, length($1) + 1)
+            FROM tags AS descendants
+            WHERE starts_with(descendants.title, $1 || '/')
+        )
+    )
+) AS "exists!"
+            "#,
+            previous_title,
+            tag.title().as_str(),
+            tag.id()
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        if title_taken {
+            return Err(ApplicationError::invalid_argument(
+                "tag title is already taken, merge the tags instead",
+            ));
+        }
+
         let rec = sqlx::query!(
             r#"
 UPDATE tags
@@ -98,6 +424,32 @@ RETURNING updated_at
 
         tag.set_updated_at(rec.updated_at)?;
 
+        sqlx::query!(
+            r#"
+UPDATE tags
+SET title = $2 || substr(title, length($1) + 1), updated_at = DEFAULT
+WHERE starts_with(title, $1 || '/') AND id <> $3
+            "#,
+            previous_title,
+            tag.title().as_str(),
+            tag.id()
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        sqlx::query!(
+            "DELETE FROM tag_aliases WHERE title = $1",
+            tag.title().as_str()
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        remarks::create_ancestor_tags(&mut tx, tag.title()).await?;
+
+        remarks::delete_unused_tags(&mut tx).await?;
+
         commit_transaction(tx).await?;
 
         Ok(())
@@ -107,20 +459,82 @@ RETURNING updated_at
 #[derive(Serialize, Deserialize)]
 struct PageToken {
     id: Uuid,
-    created_at: DateTime<Utc>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_at: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    usage_count: Option<i64>,
+
+    #[serde(default)]
+    order_by: Option<String>,
+
+    #[serde(default)]
+    page_size: Option<i64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    as_of: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    total_size: Option<i64>,
+}
+
+impl TagListingRow {
+    fn into_tag(self, with_usage_count: bool) -> ApplicationResult<Tag> {
+        let TagListingRow {
+            id,
+            title,
+            created_at,
+            updated_at,
+            usage_count,
+        } = self;
+
+        let tag = Tag::new(TagAttributes {
+            id,
+            title: TagTitle::new(title)?,
+            created_at,
+            updated_at,
+            usage_count: with_usage_count.then_some(usage_count),
+        });
+
+        Ok(tag)
+    }
 }
 
 impl PageToken {
-    fn from_rows(rows: &[TagRow]) -> Option<Self> {
-        if rows.len() < DEFAULT_PAGE_SIZE as usize {
+    fn from_rows(
+        rows: &[TagListingRow],
+        page_size: PageSize,
+        order_by: TagsOrderBy,
+        as_of: DateTime<Utc>,
+        total_size: i64,
+    ) -> Option<Self> {
+        if rows.len() < *page_size as usize {
             return None;
         }
 
         rows.last().map(|row| PageToken {
             id: row.id,
-            created_at: row.created_at,
+            created_at: (order_by == TagsOrderBy::CreatedAtDesc).then_some(row.created_at),
+            title: (order_by == TagsOrderBy::Title).then(|| row.title.clone()),
+            usage_count: (order_by == TagsOrderBy::UsageCount).then_some(row.usage_count),
+            order_by: Some(order_by.as_str().to_string()),
+            page_size: Some(*page_size),
+            as_of: Some(as_of),
+            total_size: Some(total_size),
         })
     }
+
+    fn order_by(&self) -> ApplicationResult<TagsOrderBy> {
+        self.order_by
+            .as_deref()
+            .map(str::parse)
+            .unwrap_or(Ok(TagsOrderBy::default()))
+            .map_err(|_err| ApplicationError::invalid_argument("malformed tags page token"))
+    }
 }
 
 impl FromStr for PageToken {
@@ -180,6 +594,7 @@ impl TryFrom<TagRow> for Tag {
             title: TagTitle::new(title)?,
             created_at,
             updated_at,
+            usage_count: None,
         });
 
         Ok(tag)
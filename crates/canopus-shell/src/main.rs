@@ -42,7 +42,7 @@ async fn start_tcp_repl(mut stream: TcpStream) -> JoinHandle<Result<()>> {
         loop {
             let mut buf = vec![0; 1024];
             match stream.read(&mut buf).await {
-                Ok(n) if n == 0 => {
+                Ok(0) => {
                     println!("Connection closed.");
                     break;
                 }
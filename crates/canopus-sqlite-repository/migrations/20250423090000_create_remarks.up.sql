@@ -0,0 +1,12 @@
+-- Add up migration script here
+
+CREATE TABLE remarks (
+    id blob NOT NULL PRIMARY KEY,
+    essence text NOT NULL,
+    created_at text NOT NULL,
+    updated_at text NOT NULL,
+    deleted_at text
+);
+
+CREATE INDEX remarks_created_at_index ON remarks (created_at, id);
+CREATE INDEX remarks_updated_at_index ON remarks (updated_at, id);
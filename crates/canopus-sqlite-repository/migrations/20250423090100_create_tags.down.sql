@@ -0,0 +1,7 @@
+-- Add down migration script here
+
+DROP TABLE tag_aliases;
+
+DROP TABLE remarks_tags;
+
+DROP TABLE tags;
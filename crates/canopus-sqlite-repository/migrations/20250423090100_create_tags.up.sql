@@ -0,0 +1,24 @@
+-- Add up migration script here
+
+CREATE TABLE tags (
+    id blob NOT NULL PRIMARY KEY,
+    title text NOT NULL UNIQUE,
+    created_at text NOT NULL,
+    updated_at text NOT NULL
+);
+
+CREATE TABLE remarks_tags (
+    remark_id blob NOT NULL,
+    tag_id blob NOT NULL,
+    PRIMARY KEY (remark_id, tag_id)
+);
+
+CREATE INDEX remarks_tags_tag_id_index ON remarks_tags (tag_id);
+
+CREATE TABLE tag_aliases (
+    title text NOT NULL PRIMARY KEY,
+    tag_id blob NOT NULL,
+    created_at text NOT NULL
+);
+
+CREATE INDEX tag_aliases_tag_id_index ON tag_aliases (tag_id);
@@ -0,0 +1,10 @@
+-- Add up migration script here
+
+CREATE TABLE remark_revisions (
+    remark_id blob NOT NULL,
+    number integer NOT NULL,
+    essence text NOT NULL,
+    tags text NOT NULL,
+    created_at text NOT NULL,
+    PRIMARY KEY (remark_id, number)
+);
@@ -0,0 +1,10 @@
+-- Add up migration script here
+
+CREATE TABLE remark_links (
+    source_id blob NOT NULL,
+    target_id blob NOT NULL,
+    created_at text NOT NULL,
+    PRIMARY KEY (source_id, target_id)
+);
+
+CREATE INDEX remark_links_target_id_index ON remark_links (target_id);
@@ -0,0 +1,14 @@
+-- Add up migration script here
+
+CREATE TABLE remark_attachments (
+    id blob NOT NULL PRIMARY KEY,
+    remark_id blob NOT NULL,
+    file_name text NOT NULL,
+    media_type text NOT NULL,
+    size integer NOT NULL,
+    checksum text NOT NULL,
+    content blob NOT NULL,
+    created_at text NOT NULL
+);
+
+CREATE INDEX remark_attachments_remark_id_index ON remark_attachments (remark_id);
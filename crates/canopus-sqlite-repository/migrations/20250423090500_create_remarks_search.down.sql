@@ -0,0 +1,9 @@
+-- Add down migration script here
+
+DROP TRIGGER remarks_search_update;
+
+DROP TRIGGER remarks_search_delete;
+
+DROP TRIGGER remarks_search_insert;
+
+DROP TABLE remarks_search;
@@ -0,0 +1,19 @@
+-- Add up migration script here
+
+CREATE VIRTUAL TABLE remarks_search USING fts5 (
+    remark_id UNINDEXED,
+    essence,
+    tokenize = 'porter unicode61'
+);
+
+CREATE TRIGGER remarks_search_insert AFTER INSERT ON remarks BEGIN
+    INSERT INTO remarks_search ( remark_id, essence ) VALUES ( new.id, new.essence );
+END;
+
+CREATE TRIGGER remarks_search_delete AFTER DELETE ON remarks BEGIN
+    DELETE FROM remarks_search WHERE remark_id = old.id;
+END;
+
+CREATE TRIGGER remarks_search_update AFTER UPDATE OF essence ON remarks BEGIN
+    UPDATE remarks_search SET essence = new.essence WHERE remark_id = old.id;
+END;
@@ -0,0 +1,11 @@
+-- Add up migration script here
+
+CREATE TABLE remark_idempotency_keys (
+    key text NOT NULL PRIMARY KEY,
+    request_hash text NOT NULL,
+    remark_id blob NOT NULL,
+    created_at text NOT NULL
+);
+
+CREATE INDEX remark_idempotency_keys_remark_id_index ON remark_idempotency_keys (remark_id);
+CREATE INDEX remark_idempotency_keys_created_at_index ON remark_idempotency_keys (created_at);
@@ -0,0 +1,9 @@
+-- Add up migration script here
+
+CREATE TABLE remark_content_hashes (
+    content_hash text NOT NULL PRIMARY KEY,
+    remark_id blob NOT NULL,
+    created_at text NOT NULL
+);
+
+CREATE INDEX remark_content_hashes_remark_id_index ON remark_content_hashes (remark_id);
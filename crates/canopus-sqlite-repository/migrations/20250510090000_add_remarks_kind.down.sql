@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP INDEX remarks_kind_index;
+ALTER TABLE remarks DROP COLUMN payload;
+ALTER TABLE remarks DROP COLUMN kind;
@@ -0,0 +1,6 @@
+-- Add up migration script here
+
+ALTER TABLE remarks ADD COLUMN kind text NOT NULL DEFAULT 'note';
+ALTER TABLE remarks ADD COLUMN payload text;
+
+CREATE INDEX remarks_kind_index ON remarks (kind);
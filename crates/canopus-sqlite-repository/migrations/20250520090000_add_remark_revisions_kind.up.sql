@@ -0,0 +1,11 @@
+-- Add up migration script here
+
+ALTER TABLE remark_revisions ADD COLUMN kind text NOT NULL DEFAULT 'note';
+ALTER TABLE remark_revisions ADD COLUMN payload text;
+
+-- Earlier revisions didn't record a kind, restoring them keeps the current one
+UPDATE remark_revisions
+SET
+    kind = (SELECT remarks.kind FROM remarks WHERE remarks.id = remark_revisions.remark_id),
+    payload = (SELECT remarks.payload FROM remarks WHERE remarks.id = remark_revisions.remark_id)
+WHERE EXISTS (SELECT 1 FROM remarks WHERE remarks.id = remark_revisions.remark_id);
@@ -0,0 +1,59 @@
+mod remark_attachments;
+mod remark_content_hashes;
+mod remark_idempotency_keys;
+mod remark_links;
+mod remark_revisions;
+mod remarks;
+mod remarks_search;
+mod remarks_tags;
+mod tags;
+
+use base64::{
+    alphabet,
+    engine::{GeneralPurpose, general_purpose},
+};
+use canopus_definitions::{ApplicationError, ApplicationResult, PageSize};
+use sqlx::SqliteTransaction;
+use tags::{TagRow, TagTitleRow};
+
+const URL_SAFE_NO_PAD_ENGINE: GeneralPurpose =
+    GeneralPurpose::new(&alphabet::URL_SAFE, general_purpose::NO_PAD);
+
+pub struct Repository {
+    pub pool: sqlx::SqlitePool,
+}
+
+impl Repository {
+    pub async fn migrate(&self) -> ApplicationResult<()> {
+        sqlx::migrate!()
+            .run(&self.pool)
+            .await
+            .map_err(|err| ApplicationError::internal("failed to run migrations", err))
+    }
+
+    async fn begin_transaction(&self) -> ApplicationResult<SqliteTransaction<'static>> {
+        self.pool.begin().await.map_err(from_sqlx_err)
+    }
+}
+
+async fn commit_transaction(tx: SqliteTransaction<'_>) -> ApplicationResult<()> {
+    tx.commit().await.map_err(from_sqlx_err)
+}
+
+fn from_sqlx_err(err: sqlx::Error) -> ApplicationError {
+    match err {
+        sqlx::Error::RowNotFound => ApplicationError::NotFound,
+        other => ApplicationError::Repository(other.to_string()),
+    }
+}
+
+fn resolve_page_size(
+    page_size: Option<PageSize>,
+    token_page_size: Option<i64>,
+) -> ApplicationResult<PageSize> {
+    match (page_size, token_page_size) {
+        (Some(page_size), _) => Ok(page_size),
+        (None, Some(page_size)) => PageSize::new(page_size),
+        (None, None) => Ok(PageSize::default()),
+    }
+}
@@ -0,0 +1,211 @@
+use crate::{Repository, from_sqlx_err};
+use canopus_definitions::{
+    ApplicationError, ApplicationResult, RemarkAttachment, RemarkAttachmentAttributes,
+};
+use canopus_operations::remarks::{
+    DeleteRemarkAttachment, GetRemarkAttachment, GetRemarkAttachmentContent,
+    InsertRemarkAttachment, ListRemarkAttachments, NewRemarkAttachment,
+};
+use chrono::{DateTime, Utc};
+use sqlx::SqliteTransaction;
+use uuid::Uuid;
+
+pub struct RemarkAttachmentRow {
+    pub id: Uuid,
+    pub remark_id: Uuid,
+    pub file_name: String,
+    pub media_type: String,
+    pub size: i64,
+    pub checksum: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DeleteRemarkAttachment for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn delete_remark_attachment(
+        &self,
+        attachment: &RemarkAttachment,
+    ) -> ApplicationResult<()> {
+        let id = attachment.id();
+
+        let rec = sqlx::query!(r#"DELETE FROM remark_attachments WHERE id = $1"#, id)
+            .execute(&self.pool)
+            .await
+            .map_err(from_sqlx_err)?;
+
+        if rec.rows_affected() == 0 {
+            return Err(ApplicationError::NotFound);
+        }
+
+        Ok(())
+    }
+}
+
+impl GetRemarkAttachment for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn get_remark_attachment(
+        &self,
+        remark_id: Uuid,
+        id: Uuid,
+    ) -> ApplicationResult<RemarkAttachment> {
+        let row = sqlx::query_as!(
+            RemarkAttachmentRow,
+            r#"
+SELECT
+    id AS "id!: Uuid",
+    remark_id AS "remark_id!: Uuid",
+    file_name,
+    media_type,
+    size,
+    checksum,
+    created_at AS "created_at!: DateTime<Utc>"
+FROM remark_attachments
+WHERE remark_id = $1 AND id = $2
+            "#,
+            remark_id,
+            id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        Ok(row.into())
+    }
+}
+
+impl GetRemarkAttachmentContent for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn get_remark_attachment_content(
+        &self,
+        attachment: &RemarkAttachment,
+    ) -> ApplicationResult<Vec<u8>> {
+        let id = attachment.id();
+
+        sqlx::query_scalar!(
+            r#"SELECT content FROM remark_attachments WHERE id = $1"#,
+            id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(from_sqlx_err)
+    }
+}
+
+impl InsertRemarkAttachment for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn insert_remark_attachment(
+        &self,
+        attachment: NewRemarkAttachment,
+    ) -> ApplicationResult<RemarkAttachment> {
+        let NewRemarkAttachment {
+            remark_id,
+            file_name,
+            media_type,
+            checksum,
+            content,
+        } = attachment;
+
+        let id = Uuid::new_v4();
+        let size = content.len() as i64;
+        let created_at = Utc::now();
+
+        sqlx::query!(
+            r#"
+INSERT INTO remark_attachments (
+    id, remark_id, file_name, media_type, size, checksum, content, created_at
+)
+VALUES ( $1, $2, $3, $4, $5, $6, $7, $8 )
+            "#,
+            id,
+            remark_id,
+            file_name,
+            media_type,
+            size,
+            checksum,
+            content,
+            created_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        let row = RemarkAttachmentRow {
+            id,
+            remark_id,
+            file_name,
+            media_type,
+            size,
+            checksum,
+            created_at,
+        };
+
+        Ok(row.into())
+    }
+}
+
+impl ListRemarkAttachments for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn list_remark_attachments(
+        &self,
+        remark_id: Uuid,
+    ) -> ApplicationResult<Vec<RemarkAttachment>> {
+        let rows = sqlx::query_as!(
+            RemarkAttachmentRow,
+            r#"
+SELECT
+    id AS "id!: Uuid",
+    remark_id AS "remark_id!: Uuid",
+    file_name,
+    media_type,
+    size,
+    checksum,
+    created_at AS "created_at!: DateTime<Utc>"
+FROM remark_attachments
+WHERE remark_id = $1
+ORDER BY created_at ASC, id ASC
+            "#,
+            remark_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
+
+pub async fn delete(tx: &mut SqliteTransaction<'_>, remark_id: Uuid) -> ApplicationResult<()> {
+    sqlx::query!(
+        r#"DELETE FROM remark_attachments WHERE remark_id = $1"#,
+        remark_id
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    Ok(())
+}
+
+impl From<RemarkAttachmentRow> for RemarkAttachment {
+    fn from(value: RemarkAttachmentRow) -> Self {
+        let RemarkAttachmentRow {
+            id,
+            remark_id,
+            file_name,
+            media_type,
+            size,
+            checksum,
+            created_at,
+        } = value;
+
+        Self::new(RemarkAttachmentAttributes {
+            id,
+            remark_id,
+            file_name,
+            media_type,
+            size,
+            checksum,
+            created_at,
+        })
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{Repository, commit_transaction, from_sqlx_err, remarks};
+use canopus_definitions::{ApplicationResult, RemarkBulkOutcome, RemarkBulkOutcomeAttributes};
+use canopus_operations::remarks::{BulkNewRemark, InsertRemarks};
+use chrono::Utc;
+use sqlx::SqliteTransaction;
+use uuid::Uuid;
+
+impl InsertRemarks for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn insert_remarks(
+        &self,
+        remarks: Vec<BulkNewRemark>,
+        dry_run: bool,
+    ) -> ApplicationResult<Vec<RemarkBulkOutcome>> {
+        let mut tx = self.begin_transaction().await?;
+        let mut outcomes = Vec::with_capacity(remarks.len());
+
+        for BulkNewRemark {
+            remark,
+            content_hash,
+        } in remarks
+        {
+            if let Some(content_hash) = &content_hash
+                && let Some(remark_id) = find(&mut tx, content_hash).await?
+            {
+                outcomes.push(RemarkBulkOutcome::new(RemarkBulkOutcomeAttributes {
+                    remark_id: Some(remark_id),
+                    skipped: true,
+                }));
+
+                continue;
+            }
+
+            if dry_run {
+                outcomes.push(RemarkBulkOutcome::new(RemarkBulkOutcomeAttributes {
+                    remark_id: None,
+                    skipped: false,
+                }));
+
+                continue;
+            }
+
+            let remark = remarks::insert_remark(&mut tx, remark).await?;
+
+            if let Some(content_hash) = &content_hash {
+                create(&mut tx, content_hash, remark.id()).await?;
+            }
+
+            outcomes.push(RemarkBulkOutcome::new(RemarkBulkOutcomeAttributes {
+                remark_id: Some(remark.id()),
+                skipped: false,
+            }));
+        }
+
+        commit_transaction(tx).await?;
+
+        Ok(outcomes)
+    }
+}
+
+pub async fn delete(tx: &mut SqliteTransaction<'_>, remark_id: Uuid) -> ApplicationResult<()> {
+    sqlx::query!(
+        "DELETE FROM remark_content_hashes WHERE remark_id = $1",
+        remark_id
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    Ok(())
+}
+
+async fn create(
+    tx: &mut SqliteTransaction<'_>,
+    content_hash: &str,
+    remark_id: Uuid,
+) -> ApplicationResult<()> {
+    let created_at = Utc::now();
+
+    sqlx::query!(
+        r#"
+INSERT INTO remark_content_hashes ( content_hash, remark_id, created_at )
+VALUES ( $1, $2, $3 )
+        "#,
+        content_hash,
+        remark_id,
+        created_at
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    Ok(())
+}
+
+async fn find(
+    tx: &mut SqliteTransaction<'_>,
+    content_hash: &str,
+) -> ApplicationResult<Option<Uuid>> {
+    sqlx::query_scalar!(
+        r#"SELECT remark_id AS "remark_id!: Uuid" FROM remark_content_hashes WHERE content_hash = $1"#,
+        content_hash
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)
+}
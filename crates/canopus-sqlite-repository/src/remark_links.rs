@@ -0,0 +1,119 @@
+use crate::{Repository, from_sqlx_err};
+use canopus_definitions::{ApplicationResult, Remark, RemarkLink, RemarkLinkAttributes};
+use canopus_operations::remarks::{ListRemarkBacklinks, ListRemarkLinks};
+use chrono::Utc;
+use sqlx::SqliteTransaction;
+use uuid::Uuid;
+
+pub struct RemarkLinkRow {
+    pub source_id: Uuid,
+    pub target_id: Uuid,
+    pub dangling: bool,
+}
+
+impl ListRemarkBacklinks for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn list_remark_backlinks(&self, remark_id: Uuid) -> ApplicationResult<Vec<RemarkLink>> {
+        let rows = sqlx::query_as!(
+            RemarkLinkRow,
+            r#"
+SELECT
+    remark_links.source_id AS "source_id!: Uuid",
+    remark_links.target_id AS "target_id!: Uuid",
+    FALSE AS "dangling!: bool"
+FROM remark_links
+JOIN remarks ON remarks.id = remark_links.source_id AND remarks.deleted_at IS NULL
+WHERE remark_links.target_id = $1
+ORDER BY remarks.created_at DESC, remarks.id DESC
+            "#,
+            remark_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
+
+impl ListRemarkLinks for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn list_remark_links(&self, remark_id: Uuid) -> ApplicationResult<Vec<RemarkLink>> {
+        let rows = sqlx::query_as!(
+            RemarkLinkRow,
+            r#"
+SELECT
+    remark_links.source_id AS "source_id!: Uuid",
+    remark_links.target_id AS "target_id!: Uuid",
+    remarks.id IS NULL AS "dangling!: bool"
+FROM remark_links
+LEFT JOIN remarks ON remarks.id = remark_links.target_id AND remarks.deleted_at IS NULL
+WHERE remark_links.source_id = $1
+ORDER BY remark_links.target_id ASC
+            "#,
+            remark_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
+
+pub async fn delete(tx: &mut SqliteTransaction<'_>, remark_id: Uuid) -> ApplicationResult<()> {
+    sqlx::query!(
+        r#"DELETE FROM remark_links WHERE source_id = $1"#,
+        remark_id
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    Ok(())
+}
+
+pub async fn save(tx: &mut SqliteTransaction<'_>, remark: &Remark) -> ApplicationResult<()> {
+    let source_id = remark.id();
+
+    delete(tx, source_id).await?;
+
+    let created_at = Utc::now();
+
+    for target_id in remark.essence().linked_remark_ids() {
+        if target_id == source_id {
+            continue;
+        }
+
+        sqlx::query!(
+            r#"
+INSERT INTO remark_links ( source_id, target_id, created_at )
+VALUES ( $1, $2, $3 )
+            "#,
+            source_id,
+            target_id,
+            created_at
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(from_sqlx_err)?;
+    }
+
+    Ok(())
+}
+
+impl From<RemarkLinkRow> for RemarkLink {
+    fn from(value: RemarkLinkRow) -> Self {
+        let RemarkLinkRow {
+            source_id,
+            target_id,
+            dangling,
+        } = value;
+
+        Self::new(RemarkLinkAttributes {
+            source_id,
+            target_id,
+            dangling,
+        })
+    }
+}
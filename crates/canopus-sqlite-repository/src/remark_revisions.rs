@@ -0,0 +1,182 @@
+use crate::{Repository, commit_transaction, from_sqlx_err, remarks};
+use canopus_definitions::{
+    ApplicationError, ApplicationResult, Remark, RemarkEssence, RemarkKind, RemarkRevision,
+    RemarkRevisionAttributes, TagTitle,
+};
+use canopus_operations::remarks::{ListRemarkRevisions, RestoreRemarkRevision};
+use chrono::{DateTime, Utc};
+use sqlx::{SqliteTransaction, types::Json};
+use uuid::Uuid;
+
+pub struct RemarkRevisionRow {
+    pub remark_id: Uuid,
+    pub number: i32,
+    pub essence: String,
+    pub tags: Json<Vec<String>>,
+    pub created_at: DateTime<Utc>,
+    pub kind: String,
+    pub payload: Option<String>,
+}
+
+impl ListRemarkRevisions for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn list_remark_revisions(
+        &self,
+        remark_id: Uuid,
+    ) -> ApplicationResult<Vec<RemarkRevision>> {
+        sqlx::query_as!(
+            RemarkRevisionRow,
+            r#"
+SELECT
+    remark_id AS "remark_id!: Uuid",
+    number AS "number!: i32",
+    essence,
+    tags AS "tags!: Json<Vec<String>>",
+    created_at AS "created_at!: DateTime<Utc>",
+    kind,
+    payload
+FROM remark_revisions
+WHERE remark_id = $1
+ORDER BY number ASC
+            "#,
+            remark_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?
+        .into_iter()
+        .map(TryInto::try_into)
+        .collect()
+    }
+}
+
+impl RestoreRemarkRevision for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn restore_remark_revision(
+        &self,
+        remark: &mut Remark,
+        number: i32,
+    ) -> ApplicationResult<()> {
+        let remark_id = remark.id();
+
+        let mut tx = self.begin_transaction().await?;
+
+        let RemarkRevisionRow {
+            essence,
+            tags,
+            kind,
+            payload,
+            ..
+        } = sqlx::query_as!(
+            RemarkRevisionRow,
+            r#"
+SELECT
+    remark_id AS "remark_id!: Uuid",
+    number AS "number!: i32",
+    essence,
+    tags AS "tags!: Json<Vec<String>>",
+    created_at AS "created_at!: DateTime<Utc>",
+    kind,
+    payload
+FROM remark_revisions
+WHERE remark_id = $1 AND number = $2
+            "#,
+            remark_id,
+            number
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        remark.set_essence(RemarkEssence::new(essence)?);
+        remark.set_tags(
+            tags.0
+                .into_iter()
+                .map(TagTitle::new)
+                .collect::<ApplicationResult<Vec<TagTitle>>>()?,
+        );
+        remark.set_kind(RemarkKind::from_parts(&kind, payload.as_deref())?);
+
+        remarks::save_remark(&mut tx, remark).await?;
+
+        commit_transaction(tx).await
+    }
+}
+
+pub async fn create(tx: &mut SqliteTransaction<'_>, remark: &Remark) -> ApplicationResult<()> {
+    let remark_id = remark.id();
+    let essence = remark.essence().as_str();
+    let tags = Json(
+        remark
+            .tags()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>(),
+    );
+    let created_at = remark.updated_at();
+    let kind = remark.kind().name().as_str();
+    let payload = remark.kind().payload();
+
+    sqlx::query!(
+        r#"
+INSERT INTO remark_revisions ( remark_id, number, essence, tags, created_at, kind, payload )
+SELECT $1, COALESCE(MAX(number), 0) + 1, $2, $3, $4, $5, $6
+FROM remark_revisions
+WHERE remark_id = $1
+        "#,
+        remark_id,
+        essence,
+        tags,
+        created_at,
+        kind,
+        payload,
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    Ok(())
+}
+
+pub async fn delete(tx: &mut SqliteTransaction<'_>, remark_id: Uuid) -> ApplicationResult<()> {
+    sqlx::query!(
+        r#"DELETE FROM remark_revisions WHERE remark_id = $1"#,
+        remark_id
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    Ok(())
+}
+
+impl TryFrom<RemarkRevisionRow> for RemarkRevision {
+    type Error = ApplicationError;
+
+    fn try_from(value: RemarkRevisionRow) -> ApplicationResult<Self> {
+        let RemarkRevisionRow {
+            remark_id,
+            number,
+            essence,
+            tags,
+            created_at,
+            kind,
+            payload,
+        } = value;
+
+        let revision = Self::new(RemarkRevisionAttributes {
+            remark_id,
+            number,
+            essence: RemarkEssence::new(essence)?,
+            tags: tags
+                .0
+                .into_iter()
+                .map(TagTitle::new)
+                .collect::<ApplicationResult<Vec<TagTitle>>>()?,
+            created_at,
+            kind: RemarkKind::from_parts(&kind, payload.as_deref())?,
+        });
+
+        Ok(revision)
+    }
+}
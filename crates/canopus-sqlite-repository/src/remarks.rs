@@ -0,0 +1,1322 @@
+use crate::{
+    Repository, TagRow, TagTitleRow, URL_SAFE_NO_PAD_ENGINE, commit_transaction, from_sqlx_err,
+    remark_attachments, remark_content_hashes, remark_idempotency_keys, remark_links,
+    remark_revisions, remarks_tags, resolve_page_size,
+};
+use canopus_definitions::{
+    ApplicationError, ApplicationResult, Page, PageSize, Remark, RemarkAttributes, RemarkEssence,
+    RemarkKind, RemarkKindName, Tag, TagAttributes, TagTitle,
+};
+use canopus_operations::remarks::{
+    AddRemarkTags, DeleteRemark, GetRemark, GetTrashedRemark, ImportRemark, InsertRemark,
+    ListRemarks, NewRemark, RemarksDatesFilter, RemarksOrderBy, RemarksPageParameters,
+    RemarksTagsFilter, RemoveRemarkTags, RestoreRemark, TrashRemark, UpdateRemark,
+};
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use sqlx::{QueryBuilder, Sqlite, SqlitePool, SqliteTransaction, types::Json};
+use std::{
+    collections::{BTreeSet, HashMap},
+    str::FromStr,
+};
+use uuid::Uuid;
+
+pub struct RemarkRow {
+    pub id: Uuid,
+    pub essence: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub kind: String,
+    pub payload: Option<String>,
+}
+
+struct RemarkListingRow {
+    id: Uuid,
+    essence: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    deleted_at: Option<DateTime<Utc>>,
+    kind: String,
+    payload: Option<String>,
+    position: DateTime<Utc>,
+}
+
+// Remarks edited after the listing snapshot keep the position of their last revision before it.
+// The filter is spliced between the query parts so keyset predicates and ordering stay on the
+// remarks table and can use its order indexes.
+macro_rules! matching_remarks_query {
+    ($query:ident $(, $record:ident)?; [$first:literal $(, $part:literal)* $(,)?], $($args:expr),*) => {
+        sqlx::$query!(
+            $($record,)?
+            $first
+            $(+ r#"
+FROM remarks
+LEFT JOIN (
+    SELECT remark_revisions.remark_id, MAX(remark_revisions.created_at) AS updated_at
+    FROM remarks AS edited
+    JOIN remark_revisions ON remark_revisions.remark_id = edited.id
+    WHERE edited.updated_at > $1 AND remark_revisions.created_at <= $1
+    GROUP BY remark_revisions.remark_id
+) AS snapshot ON snapshot.remark_id = remarks.id
+WHERE (remarks.updated_at <= $1 OR snapshot.updated_at IS NOT NULL)
+AND remarks.created_at <= $1
+AND (remarks.deleted_at IS NOT NULL) = $2
+AND ($3 IS NULL OR remarks.created_at >= $3)
+AND ($4 IS NULL OR remarks.created_at < $4)
+AND ($5 IS NULL OR COALESCE(snapshot.updated_at, remarks.updated_at) >= $5)
+AND ($6 IS NULL OR COALESCE(snapshot.updated_at, remarks.updated_at) < $6)
+AND ($7 IS NULL OR remarks.kind = $7)
+AND NOT EXISTS (
+    SELECT 1
+    FROM json_each($8) AS wanted
+    WHERE NOT EXISTS (
+        SELECT 1
+        FROM remarks_tags
+        JOIN tags ON tags.id = remarks_tags.tag_id
+        WHERE remarks_tags.remark_id = remarks.id
+        AND (
+            tags.title = wanted.value
+            OR substr(tags.title, 1, length(wanted.value) + 1) = wanted.value || '/'
+        )
+    )
+)
+AND (
+    json_array_length($9) = 0
+    OR EXISTS (
+        SELECT 1
+        FROM remarks_tags
+        JOIN tags ON tags.id = remarks_tags.tag_id
+        JOIN json_each($9) AS wanted
+        ON tags.title = wanted.value
+        OR substr(tags.title, 1, length(wanted.value) + 1) = wanted.value || '/'
+        WHERE remarks_tags.remark_id = remarks.id
+    )
+)
+AND NOT EXISTS (
+    SELECT 1
+    FROM remarks_tags
+    JOIN tags ON tags.id = remarks_tags.tag_id
+    JOIN json_each($10) AS unwanted
+    ON tags.title = unwanted.value
+    OR substr(tags.title, 1, length(unwanted.value) + 1) = unwanted.value || '/'
+    WHERE remarks_tags.remark_id = remarks.id
+)
+"# + $part)*,
+            $($args),*
+        )
+    };
+}
+
+impl AddRemarkTags for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn add_remark_tags(
+        &self,
+        remark: &mut Remark,
+        tags: &[TagTitle],
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
+        let mut tx = self.begin_transaction().await?;
+
+        refresh_remark(&mut tx, remark, expected_updated_at).await?;
+
+        let titles: Vec<TagTitle> = remark.tags().into_iter().chain(tags).cloned().collect();
+
+        save_remark_tags(&mut tx, remark, titles).await?;
+
+        commit_transaction(tx).await
+    }
+}
+
+impl DeleteRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn delete_remark(
+        &self,
+        remark: &Remark,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
+        let mut tx = self.begin_transaction().await?;
+
+        let id = remark.id();
+
+        check_updated_at(&mut tx, id, expected_updated_at).await?;
+
+        let rec = sqlx::query!("DELETE FROM remarks WHERE id = $1", id)
+            .execute(&mut *tx)
+            .await
+            .map_err(from_sqlx_err)?;
+
+        if rec.rows_affected() == 0 {
+            return Err(ApplicationError::NotFound);
+        }
+
+        remarks_tags::delete_unused_remarks_tags(&mut tx).await?;
+        remark_revisions::delete(&mut tx, id).await?;
+        remark_links::delete(&mut tx, id).await?;
+        remark_idempotency_keys::delete(&mut tx, id).await?;
+        remark_content_hashes::delete(&mut tx, id).await?;
+        remark_attachments::delete(&mut tx, id).await?;
+        delete_unused_tags(&mut tx).await?;
+
+        commit_transaction(tx).await
+    }
+}
+
+impl GetRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn get_remark(&self, id: Uuid) -> ApplicationResult<Remark> {
+        find_remark(&self.pool, id, false).await
+    }
+}
+
+impl GetTrashedRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn get_trashed_remark(&self, id: Uuid) -> ApplicationResult<Remark> {
+        find_remark(&self.pool, id, true).await
+    }
+}
+
+impl ImportRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn import_remark(&self, remark: &mut Remark) -> ApplicationResult<bool> {
+        let mut tx = self.begin_transaction().await?;
+
+        let id = remark.id();
+        let essence = remark.essence().as_str();
+        let created_at = remark.created_at();
+        let updated_at = remark.updated_at();
+        let deleted_at = remark.deleted_at();
+        let kind = remark.kind().name().as_str();
+        let payload = remark.kind().payload();
+
+        let rec = sqlx::query!(
+            r#"
+INSERT INTO remarks ( id, essence, created_at, updated_at, deleted_at, kind, payload )
+VALUES ( $1, $2, $3, $4, $5, $6, $7 )
+ON CONFLICT (id) DO NOTHING
+            "#,
+            id,
+            essence,
+            created_at,
+            updated_at,
+            deleted_at,
+            kind,
+            payload
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        if rec.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        let tags = link_tags(&mut tx, remark.id(), remark.tags()).await?;
+        remark.set_tags(tags);
+
+        remark_revisions::create(&mut tx, remark).await?;
+        remark_links::save(&mut tx, remark).await?;
+
+        commit_transaction(tx).await?;
+
+        Ok(true)
+    }
+}
+
+impl InsertRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn insert_remark(&self, new_remark: NewRemark) -> Result<Remark, ApplicationError> {
+        let mut tx = self.begin_transaction().await?;
+
+        let remark = insert_remark(&mut tx, new_remark).await?;
+
+        commit_transaction(tx).await?;
+
+        Ok(remark)
+    }
+}
+
+impl ListRemarks for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn list_remarks(
+        &self,
+        parameters: RemarksPageParameters,
+    ) -> ApplicationResult<Page<Remark>> {
+        let RemarksPageParameters {
+            page_token,
+            page_size,
+            order_by,
+            dates_filter,
+            tags_filter,
+            kind,
+            trashed,
+        } = parameters;
+
+        let page_token = page_token.map(TryInto::<PageToken>::try_into).transpose()?;
+
+        let page_size = resolve_page_size(
+            page_size,
+            page_token.as_ref().and_then(|token| token.page_size),
+        )?;
+
+        let order_by = match &page_token {
+            Some(token) => token.order_by()?,
+            None => order_by,
+        };
+
+        let last_id = page_token
+            .as_ref()
+            .map(|token| token.id)
+            .unwrap_or(Uuid::nil());
+
+        let last_position = page_token.as_ref().map(|token| token.position);
+
+        let (dates_filter, tags_filter, kind, as_of, total_size) = match page_token {
+            Some(token) => (
+                token.dates_filter,
+                token.tags_filter,
+                token.kind,
+                token.as_of,
+                token.total_size,
+            ),
+            None => (dates_filter.into(), tags_filter.into(), kind, None, None),
+        };
+
+        let as_of = as_of.unwrap_or_else(Utc::now);
+        let limit = *page_size;
+        let kind_name = kind.map(|kind| kind.as_str());
+        let include_all = Json(&tags_filter.include_all);
+        let include_any = Json(&tags_filter.include_any);
+        let exclude = Json(&tags_filter.exclude);
+
+        let total_size = match total_size {
+            Some(total_size) => total_size,
+            None => matching_remarks_query!(
+                query_scalar;
+                [r#"SELECT COUNT(*) AS "count!: i64""#, ""],
+                as_of,
+                trashed,
+                dates_filter.created_after,
+                dates_filter.created_before,
+                dates_filter.updated_after,
+                dates_filter.updated_before,
+                kind_name,
+                include_all,
+                include_any,
+                exclude
+            )
+            .fetch_one(&self.pool)
+            .await
+            .map_err(from_sqlx_err)?,
+        };
+
+        let rows = match order_by {
+            RemarksOrderBy::CreatedAtAsc => {
+                matching_remarks_query!(
+                    query_as, RemarkListingRow;
+                    [
+                        r#"
+SELECT
+    remarks.id AS "id!: Uuid",
+    remarks.essence AS "essence!",
+    remarks.created_at AS "created_at!: DateTime<Utc>",
+    remarks.updated_at AS "updated_at!: DateTime<Utc>",
+    remarks.deleted_at AS "deleted_at?: DateTime<Utc>",
+    remarks.kind AS "kind!",
+    remarks.payload AS "payload?",
+    remarks.created_at AS "position!: DateTime<Utc>"
+                        "#,
+                        r#"
+AND (remarks.created_at, remarks.id) > (COALESCE($11, '-infinity'), $12)
+ORDER BY remarks.created_at ASC, remarks.id ASC
+LIMIT $13
+                        "#,
+                    ],
+                    as_of,
+                    trashed,
+                    dates_filter.created_after,
+                    dates_filter.created_before,
+                    dates_filter.updated_after,
+                    dates_filter.updated_before,
+                    kind_name,
+                    include_all,
+                    include_any,
+                    exclude,
+                    last_position,
+                    last_id,
+                    limit
+                )
+                .fetch_all(&self.pool)
+                .await
+            }
+            RemarksOrderBy::CreatedAtDesc => {
+                matching_remarks_query!(
+                    query_as, RemarkListingRow;
+                    [
+                        r#"
+SELECT
+    remarks.id AS "id!: Uuid",
+    remarks.essence AS "essence!",
+    remarks.created_at AS "created_at!: DateTime<Utc>",
+    remarks.updated_at AS "updated_at!: DateTime<Utc>",
+    remarks.deleted_at AS "deleted_at?: DateTime<Utc>",
+    remarks.kind AS "kind!",
+    remarks.payload AS "payload?",
+    remarks.created_at AS "position!: DateTime<Utc>"
+                        "#,
+                        r#"
+AND (remarks.created_at, remarks.id) < (COALESCE($11, 'infinity'), $12)
+ORDER BY remarks.created_at DESC, remarks.id DESC
+LIMIT $13
+                        "#,
+                    ],
+                    as_of,
+                    trashed,
+                    dates_filter.created_after,
+                    dates_filter.created_before,
+                    dates_filter.updated_after,
+                    dates_filter.updated_before,
+                    kind_name,
+                    include_all,
+                    include_any,
+                    exclude,
+                    last_position,
+                    last_id,
+                    limit
+                )
+                .fetch_all(&self.pool)
+                .await
+            }
+            RemarksOrderBy::UpdatedAtAsc => {
+                matching_remarks_query!(
+                    query_as, RemarkListingRow;
+                    [
+                        r#"
+SELECT
+    id AS "id!: Uuid",
+    essence AS "essence!",
+    created_at AS "created_at!: DateTime<Utc>",
+    updated_at AS "updated_at!: DateTime<Utc>",
+    deleted_at AS "deleted_at?: DateTime<Utc>",
+    kind AS "kind!",
+    payload AS "payload?",
+    position AS "position!: DateTime<Utc>"
+FROM (
+SELECT * FROM (
+SELECT
+    remarks.id, remarks.essence, remarks.created_at, remarks.updated_at, remarks.deleted_at,
+    remarks.kind, remarks.payload, remarks.updated_at AS position
+                        "#,
+                        r#"
+AND remarks.updated_at <= $1
+AND (remarks.updated_at, remarks.id) > (COALESCE($11, '-infinity'), $12)
+ORDER BY remarks.updated_at ASC, remarks.id ASC
+LIMIT $13
+) AS unchanged
+UNION ALL
+SELECT * FROM (
+SELECT
+    remarks.id, remarks.essence, remarks.created_at, remarks.updated_at, remarks.deleted_at,
+    remarks.kind, remarks.payload, snapshot.updated_at AS position
+                        "#,
+                        r#"
+AND remarks.updated_at > $1
+AND (snapshot.updated_at, remarks.id) > (COALESCE($11, '-infinity'), $12)
+ORDER BY snapshot.updated_at ASC, remarks.id ASC
+LIMIT $13
+) AS edited
+) AS page
+ORDER BY position ASC, id ASC
+LIMIT $13
+                        "#,
+                    ],
+                    as_of,
+                    trashed,
+                    dates_filter.created_after,
+                    dates_filter.created_before,
+                    dates_filter.updated_after,
+                    dates_filter.updated_before,
+                    kind_name,
+                    include_all,
+                    include_any,
+                    exclude,
+                    last_position,
+                    last_id,
+                    limit
+                )
+                .fetch_all(&self.pool)
+                .await
+            }
+            RemarksOrderBy::UpdatedAtDesc => {
+                matching_remarks_query!(
+                    query_as, RemarkListingRow;
+                    [
+                        r#"
+SELECT
+    id AS "id!: Uuid",
+    essence AS "essence!",
+    created_at AS "created_at!: DateTime<Utc>",
+    updated_at AS "updated_at!: DateTime<Utc>",
+    deleted_at AS "deleted_at?: DateTime<Utc>",
+    kind AS "kind!",
+    payload AS "payload?",
+    position AS "position!: DateTime<Utc>"
+FROM (
+SELECT * FROM (
+SELECT
+    remarks.id, remarks.essence, remarks.created_at, remarks.updated_at, remarks.deleted_at,
+    remarks.kind, remarks.payload, remarks.updated_at AS position
+                        "#,
+                        r#"
+AND remarks.updated_at <= $1
+AND (remarks.updated_at, remarks.id) < (COALESCE($11, 'infinity'), $12)
+ORDER BY remarks.updated_at DESC, remarks.id DESC
+LIMIT $13
+) AS unchanged
+UNION ALL
+SELECT * FROM (
+SELECT
+    remarks.id, remarks.essence, remarks.created_at, remarks.updated_at, remarks.deleted_at,
+    remarks.kind, remarks.payload, snapshot.updated_at AS position
+                        "#,
+                        r#"
+AND remarks.updated_at > $1
+AND (snapshot.updated_at, remarks.id) < (COALESCE($11, 'infinity'), $12)
+ORDER BY snapshot.updated_at DESC, remarks.id DESC
+LIMIT $13
+) AS edited
+) AS page
+ORDER BY position DESC, id DESC
+LIMIT $13
+                        "#,
+                    ],
+                    as_of,
+                    trashed,
+                    dates_filter.created_after,
+                    dates_filter.created_before,
+                    dates_filter.updated_after,
+                    dates_filter.updated_before,
+                    kind_name,
+                    include_all,
+                    include_any,
+                    exclude,
+                    last_position,
+                    last_id,
+                    limit
+                )
+                .fetch_all(&self.pool)
+                .await
+            }
+        }
+        .map_err(from_sqlx_err)?;
+
+        let next_page_token = PageToken {
+            id: Uuid::nil(),
+            position: as_of,
+            order_by: Some(order_by.as_str().to_string()),
+            page_size: Some(*page_size),
+            dates_filter,
+            tags_filter,
+            kind,
+            as_of: Some(as_of),
+            total_size: Some(total_size),
+        }
+        .next(&rows, page_size)
+        .map(Into::into);
+        let remark_ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+        let remarks_tags = preload_tags(&self.pool, &remark_ids)
+            .await
+            .map_err(from_sqlx_err)?;
+        let mut grouped_tags: HashMap<Uuid, Vec<TagRow>> =
+            remarks_tags.into_iter().into_group_map();
+
+        let items: Vec<Remark> = rows
+            .into_iter()
+            .map(|row| {
+                let RemarkListingRow {
+                    id,
+                    essence,
+                    created_at,
+                    updated_at,
+                    deleted_at,
+                    kind,
+                    payload,
+                    position: _,
+                } = row;
+
+                let tags = grouped_tags
+                    .remove(&row.id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|row| TagTitle::new(row.title))
+                    .collect::<ApplicationResult<Vec<TagTitle>>>()?;
+
+                let remark = Remark::new(RemarkAttributes {
+                    id,
+                    essence: RemarkEssence::new(essence)?,
+                    tags,
+                    created_at,
+                    updated_at,
+                    deleted_at,
+                    kind: RemarkKind::from_parts(&kind, payload.as_deref())?,
+                });
+
+                Ok(remark)
+            })
+            .collect::<ApplicationResult<Vec<Remark>>>()?;
+
+        Ok(Page {
+            next_page_token,
+            items,
+            total_size: Some(total_size),
+        })
+    }
+}
+
+impl RemoveRemarkTags for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn remove_remark_tags(
+        &self,
+        remark: &mut Remark,
+        tags: &[TagTitle],
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
+        let mut tx = self.begin_transaction().await?;
+
+        refresh_remark(&mut tx, remark, expected_updated_at).await?;
+
+        let titles: Vec<TagTitle> = remark
+            .tags()
+            .into_iter()
+            .filter(|title| !tags.contains(title))
+            .cloned()
+            .collect();
+
+        save_remark_tags(&mut tx, remark, titles).await?;
+
+        commit_transaction(tx).await
+    }
+}
+
+impl RestoreRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn restore_remark(
+        &self,
+        remark: &mut Remark,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
+        let mut tx = self.begin_transaction().await?;
+
+        let id = remark.id();
+
+        check_updated_at(&mut tx, id, expected_updated_at).await?;
+
+        let rec = sqlx::query!(
+            "UPDATE remarks SET deleted_at = NULL WHERE id = $1 AND deleted_at IS NOT NULL",
+            id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        if rec.rows_affected() == 0 {
+            return Err(ApplicationError::NotFound);
+        }
+
+        commit_transaction(tx).await?;
+
+        remark.set_deleted_at(None);
+
+        Ok(())
+    }
+}
+
+impl TrashRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn trash_remark(
+        &self,
+        remark: &mut Remark,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> ApplicationResult<()> {
+        let mut tx = self.begin_transaction().await?;
+
+        let id = remark.id();
+        let deleted_at = Utc::now();
+
+        check_updated_at(&mut tx, id, expected_updated_at).await?;
+
+        let rec = sqlx::query!(
+            "UPDATE remarks SET deleted_at = $2 WHERE id = $1 AND deleted_at IS NULL",
+            id,
+            deleted_at
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        if rec.rows_affected() == 0 {
+            return Err(ApplicationError::NotFound);
+        }
+
+        commit_transaction(tx).await?;
+
+        remark.set_deleted_at(Some(deleted_at));
+
+        Ok(())
+    }
+}
+
+impl UpdateRemark for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn update_remark(&self, remark: &mut Remark) -> Result<(), ApplicationError> {
+        let mut tx = self.begin_transaction().await?;
+
+        save_remark(&mut tx, remark).await?;
+
+        commit_transaction(tx).await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PageToken {
+    id: Uuid,
+
+    #[serde(alias = "created_at")]
+    position: DateTime<Utc>,
+
+    #[serde(default)]
+    order_by: Option<String>,
+
+    #[serde(default)]
+    page_size: Option<i64>,
+
+    #[serde(default)]
+    dates_filter: DatesFilter,
+
+    #[serde(default)]
+    tags_filter: TagsFilter,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kind: Option<RemarkKindName>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    as_of: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    total_size: Option<i64>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct DatesFilter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_after: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_before: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_after: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_before: Option<DateTime<Utc>>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct TagsFilter {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    include_all: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    include_any: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    exclude: Vec<String>,
+}
+
+impl PageToken {
+    fn next(self, rows: &[RemarkListingRow], page_size: PageSize) -> Option<Self> {
+        if rows.len() < *page_size as usize {
+            return None;
+        }
+
+        rows.last().map(|row| PageToken {
+            id: row.id,
+            position: row.position,
+            ..self
+        })
+    }
+
+    fn order_by(&self) -> ApplicationResult<RemarksOrderBy> {
+        self.order_by
+            .as_deref()
+            .map(str::parse)
+            .unwrap_or(Ok(RemarksOrderBy::default()))
+            .map_err(|_err| ApplicationError::invalid_argument("malformed remarks page token"))
+    }
+}
+
+impl From<RemarksDatesFilter> for DatesFilter {
+    fn from(value: RemarksDatesFilter) -> Self {
+        let RemarksDatesFilter {
+            created_after,
+            created_before,
+            updated_after,
+            updated_before,
+        } = value;
+
+        DatesFilter {
+            created_after,
+            created_before,
+            updated_after,
+            updated_before,
+        }
+    }
+}
+
+impl From<RemarksTagsFilter> for TagsFilter {
+    fn from(value: RemarksTagsFilter) -> Self {
+        let RemarksTagsFilter {
+            include_all,
+            include_any,
+            exclude,
+        } = value;
+
+        let into_strings =
+            |titles: BTreeSet<TagTitle>| titles.iter().map(ToString::to_string).collect();
+
+        TagsFilter {
+            include_all: into_strings(include_all),
+            include_any: into_strings(include_any),
+            exclude: into_strings(exclude),
+        }
+    }
+}
+
+impl FromStr for PageToken {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> eyre::Result<Self> {
+        use base64::Engine;
+
+        let json = URL_SAFE_NO_PAD_ENGINE.decode(s)?;
+        let token = serde_json::from_slice(&json)?;
+
+        Ok(token)
+    }
+}
+
+impl TryFrom<canopus_definitions::PageToken> for PageToken {
+    type Error = ApplicationError;
+
+    fn try_from(value: canopus_definitions::PageToken) -> ApplicationResult<Self> {
+        value
+            .parse()
+            .map_err(|_err| ApplicationError::invalid_argument("malformed remarks page token"))
+    }
+}
+
+pub async fn insert_remark(
+    tx: &mut SqliteTransaction<'_>,
+    new_remark: NewRemark,
+) -> ApplicationResult<Remark> {
+    let NewRemark {
+        essence,
+        tags,
+        created_at,
+        kind,
+    } = new_remark;
+
+    let id = Uuid::new_v4();
+    let created_at = created_at.unwrap_or_else(Utc::now);
+    let essence_text = essence.as_str();
+    let kind_name = kind.name().as_str();
+    let payload = kind.payload();
+
+    sqlx::query!(
+        r#"
+INSERT INTO remarks ( id, essence, created_at, updated_at, kind, payload )
+VALUES ( $1, $2, $3, $3, $4, $5 )
+        "#,
+        id,
+        essence_text,
+        created_at,
+        kind_name,
+        payload
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    let tags = link_tags(tx, id, tags.iter().collect()).await?;
+
+    let remark = Remark::new(RemarkAttributes {
+        id,
+        essence,
+        tags,
+        created_at,
+        updated_at: created_at,
+        deleted_at: None,
+        kind,
+    });
+
+    remark_revisions::create(tx, &remark).await?;
+    remark_links::save(tx, &remark).await?;
+
+    Ok(remark)
+}
+
+async fn insert_tag(tx: &mut SqliteTransaction<'_>, title: TagTitle) -> ApplicationResult<Tag> {
+    let id = Uuid::new_v4();
+    let created_at = Utc::now();
+    let title_text = title.as_str();
+
+    sqlx::query!(
+        r#"
+INSERT INTO tags ( id, title, created_at, updated_at )
+VALUES ( $1, $2, $3, $3 )
+        "#,
+        id,
+        title_text,
+        created_at
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    let tag = Tag::new(TagAttributes {
+        id,
+        title,
+        created_at,
+        updated_at: created_at,
+        usage_count: None,
+    });
+
+    Ok(tag)
+}
+
+async fn link_tags(
+    tx: &mut SqliteTransaction<'_>,
+    remark_id: Uuid,
+    tags: Vec<&TagTitle>,
+) -> ApplicationResult<Vec<TagTitle>> {
+    let mut linked_titles = Vec::with_capacity(tags.len());
+
+    for title in tags {
+        let tag = find_or_create_tag(tx, title.clone()).await?;
+        assign_remark_tags(tx, remark_id, &tag).await?;
+        linked_titles.push(tag.title().clone());
+    }
+
+    Ok(linked_titles)
+}
+
+async fn assign_remark_tags(
+    tx: &mut SqliteTransaction<'_>,
+    remark_id: Uuid,
+    tag: &Tag,
+) -> ApplicationResult<()> {
+    if !remarks_tags::remark_tag_exists(tx, remark_id, tag.id())
+        .await
+        .map_err(from_sqlx_err)?
+    {
+        remarks_tags::create(tx, remark_id, tag.id())
+            .await
+            .map_err(from_sqlx_err)?;
+    }
+
+    Ok(())
+}
+
+pub async fn find_remark(pool: &SqlitePool, id: Uuid, trashed: bool) -> ApplicationResult<Remark> {
+    let RemarkRow {
+        id,
+        essence,
+        created_at,
+        updated_at,
+        deleted_at,
+        kind,
+        payload,
+    } = sqlx::query_as!(
+        RemarkRow,
+        r#"
+SELECT
+    id AS "id!: Uuid",
+    essence,
+    created_at AS "created_at!: DateTime<Utc>",
+    updated_at AS "updated_at!: DateTime<Utc>",
+    deleted_at AS "deleted_at: DateTime<Utc>",
+    kind,
+    payload
+FROM remarks
+WHERE id = $1 AND (deleted_at IS NOT NULL) = $2
+        "#,
+        id,
+        trashed
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    let tag_titles = sqlx::query_as!(
+        TagTitleRow,
+        r#"
+SELECT tags.title FROM tags
+JOIN remarks_tags ON remarks_tags.tag_id = tags.id AND remarks_tags.remark_id = $1
+ORDER BY tags.title DESC
+        "#,
+        id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    let tags = tag_titles
+        .into_iter()
+        .map(TryInto::<TagTitle>::try_into)
+        .collect::<ApplicationResult<Vec<TagTitle>>>()?;
+
+    let remark = Remark::new(RemarkAttributes {
+        id,
+        essence: RemarkEssence::new(essence)?,
+        tags,
+        created_at,
+        updated_at,
+        deleted_at,
+        kind: RemarkKind::from_parts(&kind, payload.as_deref())?,
+    });
+
+    Ok(remark)
+}
+
+async fn find_or_create_tag(
+    tx: &mut SqliteTransaction<'_>,
+    title: TagTitle,
+) -> ApplicationResult<Tag> {
+    if let Some(tag) = find_tag_by_title(tx, &title).await? {
+        return Ok(tag);
+    }
+
+    if let Some(tag) = find_tag_by_alias(tx, &title).await? {
+        return Ok(tag);
+    }
+
+    create_ancestor_tags(tx, &title).await?;
+
+    insert_tag(tx, title).await
+}
+
+pub async fn create_ancestor_tags(
+    tx: &mut SqliteTransaction<'_>,
+    title: &TagTitle,
+) -> ApplicationResult<()> {
+    for ancestor in title.ancestors() {
+        if find_tag_by_title(tx, &ancestor).await?.is_none() {
+            insert_tag(tx, ancestor).await?;
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn delete_unused_tags(tx: &mut SqliteTransaction<'_>) -> ApplicationResult<()> {
+    sqlx::query!(
+        r#"
+WITH used_tags AS (
+    SELECT DISTINCT tags.title
+    FROM tags
+    JOIN remarks_tags ON remarks_tags.tag_id = tags.id
+)
+DELETE FROM tags
+WHERE NOT EXISTS (
+    SELECT 1 FROM used_tags
+    WHERE used_tags.title = tags.title
+    OR substr(used_tags.title, 1, length(tags.title) + 1) = tags.title || '/'
+)
+        "#
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    sqlx::query!(
+        r#"
+DELETE FROM tag_aliases
+WHERE NOT EXISTS (SELECT 1 FROM tags WHERE tags.id = tag_aliases.tag_id)
+        "#
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    Ok(())
+}
+
+async fn find_tag_by_alias(
+    tx: &mut SqliteTransaction<'_>,
+    title: &TagTitle,
+) -> ApplicationResult<Option<Tag>> {
+    let title = title.as_str();
+
+    let tag = sqlx::query_as!(
+        TagRow,
+        r#"
+SELECT
+    tags.id AS "id!: Uuid",
+    tags.title,
+    tags.created_at AS "created_at!: DateTime<Utc>",
+    tags.updated_at AS "updated_at!: DateTime<Utc>"
+FROM tags
+JOIN tag_aliases ON tag_aliases.tag_id = tags.id
+WHERE tag_aliases.title = $1
+        "#,
+        title
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    tag.map(TryInto::try_into).transpose()
+}
+
+async fn find_tag_by_title(
+    tx: &mut SqliteTransaction<'_>,
+    title: &TagTitle,
+) -> ApplicationResult<Option<Tag>> {
+    let title = title.as_str();
+
+    let tag = sqlx::query_as!(
+        TagRow,
+        r#"
+SELECT
+    id AS "id!: Uuid",
+    title,
+    created_at AS "created_at!: DateTime<Utc>",
+    updated_at AS "updated_at!: DateTime<Utc>"
+FROM tags
+WHERE title = $1
+        "#,
+        title
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    tag.map(TryInto::try_into).transpose()
+}
+
+pub async fn preload_tags(
+    pool: &SqlitePool,
+    remark_ids: &[Uuid],
+) -> sqlx::Result<Vec<(Uuid, TagRow)>> {
+    use sqlx::Row;
+
+    if remark_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut query = QueryBuilder::<Sqlite>::new(
+        r#"
+SELECT remarks_tags.remark_id, tags.*
+FROM tags
+JOIN remarks_tags ON remarks_tags.tag_id = tags.id
+WHERE remarks_tags.remark_id IN (
+        "#,
+    );
+
+    let mut ids = query.separated(", ");
+
+    for id in remark_ids {
+        ids.push_bind(id);
+    }
+
+    query.push(") ORDER BY tags.title DESC");
+
+    let rows = query.build().fetch_all(pool).await?;
+
+    let tags = rows
+        .into_iter()
+        .map(|row| {
+            (
+                row.get("remark_id"),
+                TagRow {
+                    id: row.get("id"),
+                    title: row.get("title"),
+                    created_at: row.get("created_at"),
+                    updated_at: row.get("updated_at"),
+                },
+            )
+        })
+        .collect();
+
+    Ok(tags)
+}
+
+async fn check_updated_at(
+    tx: &mut SqliteTransaction<'_>,
+    id: Uuid,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<()> {
+    let Some(expected_updated_at) = expected_updated_at else {
+        return Ok(());
+    };
+
+    let updated_at = sqlx::query_scalar!(
+        r#"SELECT updated_at AS "updated_at!: DateTime<Utc>" FROM remarks WHERE id = $1"#,
+        id
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    if updated_at != expected_updated_at {
+        return Err(ApplicationError::Conflict);
+    }
+
+    Ok(())
+}
+
+async fn refresh_remark(
+    tx: &mut SqliteTransaction<'_>,
+    remark: &mut Remark,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> ApplicationResult<()> {
+    let id = remark.id();
+
+    let rec = sqlx::query!(
+        r#"
+SELECT essence, updated_at AS "updated_at!: DateTime<Utc>", kind, payload FROM remarks
+WHERE id = $1 AND deleted_at IS NULL
+        "#,
+        id
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    if expected_updated_at.is_some_and(|updated_at| updated_at != rec.updated_at) {
+        return Err(ApplicationError::Conflict);
+    }
+
+    let tag_titles = sqlx::query_as!(
+        TagTitleRow,
+        r#"
+SELECT tags.title FROM tags
+JOIN remarks_tags ON remarks_tags.tag_id = tags.id AND remarks_tags.remark_id = $1
+ORDER BY tags.title DESC
+        "#,
+        id
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    let tags = tag_titles
+        .into_iter()
+        .map(TryInto::<TagTitle>::try_into)
+        .collect::<ApplicationResult<Vec<TagTitle>>>()?;
+
+    remark.set_essence(RemarkEssence::new(rec.essence)?);
+    remark.set_kind(RemarkKind::from_parts(&rec.kind, rec.payload.as_deref())?);
+    remark.set_tags(tags);
+    remark.set_updated_at(rec.updated_at)
+}
+
+async fn save_remark_tags(
+    tx: &mut SqliteTransaction<'_>,
+    remark: &mut Remark,
+    titles: Vec<TagTitle>,
+) -> ApplicationResult<()> {
+    let titles = BTreeSet::from_iter(titles);
+
+    if titles.iter().eq(remark.tags()) {
+        return Ok(());
+    }
+
+    remark.set_tags(titles.into_iter().collect());
+
+    save_remark(tx, remark).await
+}
+
+pub async fn save_remark(
+    tx: &mut SqliteTransaction<'_>,
+    remark: &mut Remark,
+) -> ApplicationResult<()> {
+    let id = remark.id();
+    let essence = remark.essence().as_str();
+    let kind = remark.kind().name().as_str();
+    let payload = remark.kind().payload();
+    let updated_at = Utc::now();
+
+    let previous_updated_at = sqlx::query_scalar!(
+        r#"SELECT updated_at AS "updated_at!: DateTime<Utc>" FROM remarks WHERE id = $1"#,
+        id
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    if previous_updated_at != remark.updated_at() {
+        return Err(ApplicationError::Conflict);
+    }
+
+    sqlx::query!(
+        r#"
+UPDATE remarks
+SET essence = $2, kind = $3, payload = $4, updated_at = $5
+WHERE id = $1
+        "#,
+        id,
+        essence,
+        kind,
+        payload,
+        updated_at
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    remark.set_updated_at(updated_at)?;
+
+    unlink_tags(tx, id).await?;
+    let tags = link_tags(tx, id, remark.tags()).await?;
+    delete_unused_tags(tx).await?;
+    remark.set_tags(tags);
+
+    remark_revisions::create(tx, remark).await?;
+    remark_links::save(tx, remark).await?;
+
+    Ok(())
+}
+
+async fn unlink_tags(tx: &mut SqliteTransaction<'_>, remark_id: Uuid) -> ApplicationResult<()> {
+    sqlx::query!(
+        r#"DELETE FROM remarks_tags WHERE remark_id = $1"#,
+        remark_id
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    Ok(())
+}
+
+impl std::fmt::Display for PageToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use base64::Engine;
+
+        let json = serde_json::to_string(&self).map_err(|_err| std::fmt::Error)?;
+
+        let encoded_json = URL_SAFE_NO_PAD_ENGINE.encode(json);
+
+        f.write_str(&encoded_json)
+    }
+}
+
+impl From<PageToken> for canopus_definitions::PageToken {
+    fn from(value: PageToken) -> Self {
+        value.to_string().into()
+    }
+}
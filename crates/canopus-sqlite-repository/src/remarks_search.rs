@@ -0,0 +1,302 @@
+use crate::{
+    Repository, TagRow, URL_SAFE_NO_PAD_ENGINE, from_sqlx_err, remarks::preload_tags,
+    resolve_page_size,
+};
+use canopus_definitions::{
+    ApplicationError, ApplicationResult, Page, PageSize, Remark, RemarkAttributes, RemarkEssence,
+    RemarkKind, RemarkSearchHit, RemarkSearchHitAttributes, TagTitle,
+};
+use canopus_operations::remarks::{RemarksSearchParameters, SearchRemarks};
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, str::FromStr};
+use uuid::Uuid;
+
+struct RemarkSearchRow {
+    id: Uuid,
+    essence: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    kind: String,
+    payload: Option<String>,
+    rank: f64,
+    snippet: String,
+}
+
+impl SearchRemarks for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn search_remarks(
+        &self,
+        parameters: RemarksSearchParameters,
+    ) -> ApplicationResult<Page<RemarkSearchHit>> {
+        let RemarksSearchParameters {
+            query,
+            page_token,
+            page_size,
+        } = parameters;
+
+        let page_token = page_token.map(TryInto::<PageToken>::try_into).transpose()?;
+
+        let page_size = resolve_page_size(
+            page_size,
+            page_token.as_ref().and_then(|token| token.page_size),
+        )?;
+
+        let offset = page_token.map(|token| token.offset).unwrap_or_default();
+
+        let Some(match_query) = to_match_query(&query) else {
+            return Ok(Page {
+                next_page_token: None,
+                items: Vec::new(),
+                total_size: Some(0),
+            });
+        };
+
+        let limit = *page_size;
+
+        let rows = sqlx::query_as!(
+            RemarkSearchRow,
+            r#"
+SELECT
+    remarks.id AS "id!: Uuid",
+    remarks.essence AS "essence!",
+    remarks.created_at AS "created_at!: DateTime<Utc>",
+    remarks.updated_at AS "updated_at!: DateTime<Utc>",
+    remarks.kind AS "kind!",
+    remarks.payload,
+    -bm25(remarks_search) AS "rank!: f64",
+    snippet(remarks_search, 1, '<b>', '</b>', '...', 32) AS "snippet!: String"
+FROM remarks_search
+JOIN remarks ON remarks.id = remarks_search.remark_id
+WHERE remarks_search MATCH $1 AND remarks.deleted_at IS NULL
+ORDER BY bm25(remarks_search) ASC, remarks.id ASC
+LIMIT $2
+OFFSET $3
+            "#,
+            match_query,
+            limit,
+            offset,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        let total_size = sqlx::query_scalar!(
+            r#"
+SELECT COUNT(*) AS "count!: i64"
+FROM remarks_search
+JOIN remarks ON remarks.id = remarks_search.remark_id
+WHERE remarks_search MATCH $1 AND remarks.deleted_at IS NULL
+            "#,
+            match_query,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        let next_page_token = PageToken::from_rows(&rows, page_size, offset).map(Into::into);
+        let remark_ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+        let remarks_tags = preload_tags(&self.pool, &remark_ids)
+            .await
+            .map_err(from_sqlx_err)?;
+        let mut grouped_tags: HashMap<Uuid, Vec<TagRow>> =
+            remarks_tags.into_iter().into_group_map();
+
+        let items = rows
+            .into_iter()
+            .map(|row| {
+                let RemarkSearchRow {
+                    id,
+                    essence,
+                    created_at,
+                    updated_at,
+                    kind,
+                    payload,
+                    rank,
+                    snippet,
+                } = row;
+
+                let tags = grouped_tags
+                    .remove(&id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|row| TagTitle::new(row.title))
+                    .collect::<ApplicationResult<Vec<TagTitle>>>()?;
+
+                let remark = Remark::new(RemarkAttributes {
+                    id,
+                    essence: RemarkEssence::new(essence)?,
+                    tags,
+                    created_at,
+                    updated_at,
+                    deleted_at: None,
+                    kind: RemarkKind::from_parts(&kind, payload.as_deref())?,
+                });
+
+                Ok(RemarkSearchHit::new(RemarkSearchHitAttributes {
+                    remark,
+                    rank: rank as f32,
+                    snippet,
+                }))
+            })
+            .collect::<ApplicationResult<Vec<RemarkSearchHit>>>()?;
+
+        Ok(Page {
+            next_page_token,
+            items,
+            total_size: Some(total_size),
+        })
+    }
+}
+
+fn to_match_query(query: &str) -> Option<String> {
+    let mut included = String::new();
+    let mut excluded = Vec::new();
+    let mut any = false;
+
+    let mut quoted = false;
+
+    for part in query.split('"') {
+        let terms: Vec<(bool, String)> = if quoted {
+            vec![(false, part.to_string())]
+        } else {
+            part.split_whitespace()
+                .map(|term| match term.strip_prefix('-') {
+                    Some(term) => (true, term.to_string()),
+                    None => (false, term.to_string()),
+                })
+                .collect()
+        };
+
+        for (negated, term) in terms {
+            if !quoted && term.eq_ignore_ascii_case("or") {
+                any = !included.is_empty();
+                continue;
+            }
+
+            let words: Vec<&str> = term
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .collect();
+
+            if words.is_empty() {
+                continue;
+            }
+
+            let phrase = format!("\"{}\"", words.join(" "));
+
+            if negated {
+                excluded.push(phrase);
+                continue;
+            }
+
+            if !included.is_empty() {
+                included.push_str(if any { " OR " } else { " AND " });
+            }
+
+            any = false;
+            included.push_str(&phrase);
+        }
+
+        quoted = !quoted;
+    }
+
+    if included.is_empty() {
+        return None;
+    }
+
+    let mut match_query = format!("({included})");
+
+    for phrase in excluded {
+        match_query.push_str(" NOT ");
+        match_query.push_str(&phrase);
+    }
+
+    Some(match_query)
+}
+
+#[derive(Serialize, Deserialize)]
+struct PageToken {
+    offset: i64,
+
+    #[serde(default)]
+    page_size: Option<i64>,
+}
+
+impl PageToken {
+    fn from_rows(rows: &[RemarkSearchRow], page_size: PageSize, offset: i64) -> Option<Self> {
+        if rows.len() < *page_size as usize {
+            return None;
+        }
+
+        Some(PageToken {
+            offset: offset + rows.len() as i64,
+            page_size: Some(*page_size),
+        })
+    }
+}
+
+impl FromStr for PageToken {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> eyre::Result<Self> {
+        use base64::Engine;
+
+        let json = URL_SAFE_NO_PAD_ENGINE.decode(s)?;
+        let token = serde_json::from_slice(&json)?;
+
+        Ok(token)
+    }
+}
+
+impl TryFrom<canopus_definitions::PageToken> for PageToken {
+    type Error = ApplicationError;
+
+    fn try_from(value: canopus_definitions::PageToken) -> ApplicationResult<Self> {
+        value
+            .parse()
+            .map_err(|_err| ApplicationError::invalid_argument("malformed search page token"))
+    }
+}
+
+impl std::fmt::Display for PageToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use base64::Engine;
+
+        let json = serde_json::to_string(&self).map_err(|_err| std::fmt::Error)?;
+
+        let encoded_json = URL_SAFE_NO_PAD_ENGINE.encode(json);
+
+        f.write_str(&encoded_json)
+    }
+}
+
+impl From<PageToken> for canopus_definitions::PageToken {
+    fn from(value: PageToken) -> Self {
+        value.to_string().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_match_query() {
+        assert_eq!(
+            to_match_query("rust ownership").as_deref(),
+            Some(r#"("rust" AND "ownership")"#)
+        );
+        assert_eq!(
+            to_match_query(r#""borrow checker" or lifetimes -unsafe"#).as_deref(),
+            Some(r#"("borrow checker" OR "lifetimes") NOT "unsafe""#)
+        );
+        assert_eq!(
+            to_match_query("c++ AND*").as_deref(),
+            Some(r#"("c" AND "AND")"#)
+        );
+        assert_eq!(to_match_query("-unsafe"), None);
+        assert_eq!(to_match_query(" ?! "), None);
+    }
+}
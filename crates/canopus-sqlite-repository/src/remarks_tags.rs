@@ -0,0 +1,58 @@
+use canopus_definitions::ApplicationResult;
+use sqlx::SqliteTransaction;
+use uuid::Uuid;
+
+use crate::from_sqlx_err;
+
+pub async fn create(
+    tx: &mut SqliteTransaction<'_>,
+    remark_id: Uuid,
+    tag_id: Uuid,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        r#"
+INSERT INTO remarks_tags ( remark_id, tag_id )
+VALUES ( $1, $2 )
+        "#,
+        remark_id,
+        tag_id,
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn remark_tag_exists(
+    tx: &mut SqliteTransaction<'_>,
+    remark_id: Uuid,
+    tag_id: Uuid,
+) -> sqlx::Result<bool> {
+    let exists = sqlx::query_scalar!(
+        r#"
+SELECT EXISTS(
+    SELECT 1 FROM remarks_tags WHERE remark_id = $1 AND tag_id = $2
+) AS "exists!: bool"
+        "#,
+        remark_id,
+        tag_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(exists)
+}
+
+pub async fn delete_unused_remarks_tags(tx: &mut SqliteTransaction<'_>) -> ApplicationResult<()> {
+    sqlx::query!(
+        r#"
+DELETE FROM remarks_tags
+WHERE NOT EXISTS (SELECT 1 FROM remarks WHERE remarks.id = remarks_tags.remark_id)
+        "#,
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(from_sqlx_err)?;
+
+    Ok(())
+}
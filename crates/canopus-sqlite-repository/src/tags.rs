@@ -0,0 +1,649 @@
+use crate::{
+    Repository, URL_SAFE_NO_PAD_ENGINE, commit_transaction, from_sqlx_err, remarks,
+    resolve_page_size,
+};
+use canopus_definitions::{
+    ApplicationError, ApplicationResult, Page, PageSize, Tag, TagAttributes, TagTitle,
+};
+use canopus_operations::tags::{
+    GetTag, ListTagChildren, ListTags, MergeTags, SuggestTags, TagsOrderBy, TagsPageParameters,
+    UpdateTag,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use uuid::Uuid;
+
+pub struct TagRow {
+    pub id: Uuid,
+    pub title: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+struct TagListingRow {
+    id: Uuid,
+    title: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    usage_count: i64,
+}
+
+// Usage counts are taken as of the listing snapshot, remarks edited after it count their last
+// revision before it
+macro_rules! listed_tags_query {
+    ($select:literal, $($args:expr),*) => {
+        sqlx::query_as!(
+            TagListingRow,
+            r#"
+WITH listed AS (
+    SELECT
+        tags.id,
+        tags.title,
+        tags.created_at,
+        tags.updated_at,
+        (
+            SELECT COUNT(*)
+            FROM remarks_tags
+            JOIN remarks ON remarks.id = remarks_tags.remark_id
+            WHERE remarks_tags.tag_id = tags.id
+            AND remarks.updated_at <= $1
+            AND (remarks.deleted_at IS NULL OR remarks.deleted_at > $1)
+        ) + (
+            SELECT COUNT(*)
+            FROM remarks
+            WHERE remarks.updated_at > $1
+            AND remarks.created_at <= $1
+            AND (remarks.deleted_at IS NULL OR remarks.deleted_at > $1)
+            AND EXISTS (
+                SELECT 1
+                FROM json_each((
+                    SELECT remark_revisions.tags
+                    FROM remark_revisions
+                    WHERE remark_revisions.remark_id = remarks.id
+                    AND remark_revisions.created_at <= $1
+                    ORDER BY remark_revisions.number DESC
+                    LIMIT 1
+                )) AS revision_tag
+                WHERE revision_tag.value = tags.title
+            )
+        ) AS usage_count
+    FROM tags
+    WHERE tags.created_at <= $1
+)
+"# + $select,
+            $($args),*
+        )
+    };
+}
+
+pub struct TagTitleRow {
+    pub title: String,
+}
+
+impl GetTag for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn get_tag(&self, id: Uuid) -> ApplicationResult<Tag> {
+        sqlx::query_as!(
+            TagRow,
+            r#"
+SELECT
+    id AS "id!: Uuid",
+    title,
+    created_at AS "created_at!: DateTime<Utc>",
+    updated_at AS "updated_at!: DateTime<Utc>"
+FROM tags
+WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?
+        .try_into()
+    }
+}
+
+impl ListTagChildren for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn list_tag_children(&self, tag: &Tag) -> ApplicationResult<Vec<Tag>> {
+        let title = tag.title().as_str();
+
+        sqlx::query_as!(
+            TagRow,
+            r#"
+SELECT
+    id AS "id!: Uuid",
+    title,
+    created_at AS "created_at!: DateTime<Utc>",
+    updated_at AS "updated_at!: DateTime<Utc>"
+FROM tags
+WHERE substr(title, 1, length($1) + 1) = $1 || '/'
+AND instr(substr(title, length($1) + 2), '/') = 0
+ORDER BY title ASC
+            "#,
+            title
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?
+        .into_iter()
+        .map(TryInto::try_into)
+        .collect()
+    }
+}
+
+impl ListTags for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn list_tags(&self, parameters: TagsPageParameters) -> ApplicationResult<Page<Tag>> {
+        let TagsPageParameters {
+            page_token,
+            page_size,
+            order_by,
+            with_usage_count,
+        } = parameters;
+
+        let page_token = page_token.map(TryInto::<PageToken>::try_into).transpose()?;
+
+        let page_size = resolve_page_size(
+            page_size,
+            page_token.as_ref().and_then(|token| token.page_size),
+        )?;
+
+        let order_by = match &page_token {
+            Some(token) => token.order_by()?,
+            None => order_by,
+        };
+
+        let last_id = page_token
+            .as_ref()
+            .map(|token| token.id)
+            .unwrap_or(Uuid::nil());
+
+        let last_created_at = page_token.as_ref().and_then(|token| token.created_at);
+        let last_title = page_token.as_ref().and_then(|token| token.title.clone());
+        let last_usage_count = page_token.as_ref().and_then(|token| token.usage_count);
+
+        let as_of = page_token
+            .as_ref()
+            .and_then(|token| token.as_of)
+            .unwrap_or_else(Utc::now);
+
+        let total_size = match page_token.as_ref().and_then(|token| token.total_size) {
+            Some(total_size) => total_size,
+            None => sqlx::query_scalar!(
+                r#"SELECT COUNT(*) AS "count!: i64" FROM tags WHERE created_at <= $1"#,
+                as_of
+            )
+            .fetch_one(&self.pool)
+            .await
+            .map_err(from_sqlx_err)?,
+        };
+
+        let limit = *page_size;
+
+        let rows = match order_by {
+            TagsOrderBy::Title => {
+                listed_tags_query!(
+                    r#"
+SELECT
+    id AS "id!: Uuid",
+    title AS "title!",
+    created_at AS "created_at!: DateTime<Utc>",
+    updated_at AS "updated_at!: DateTime<Utc>",
+    usage_count AS "usage_count!: i64"
+FROM listed
+WHERE $2 IS NULL OR title > $2 OR (title = $2 AND id > $3)
+ORDER BY title ASC, id ASC
+LIMIT $4
+                "#,
+                    as_of,
+                    last_title,
+                    last_id,
+                    limit
+                )
+                .fetch_all(&self.pool)
+                .await
+            }
+            TagsOrderBy::UsageCount => {
+                listed_tags_query!(
+                    r#"
+SELECT
+    id AS "id!: Uuid",
+    title AS "title!",
+    created_at AS "created_at!: DateTime<Utc>",
+    updated_at AS "updated_at!: DateTime<Utc>",
+    usage_count AS "usage_count!: i64"
+FROM listed
+WHERE $2 IS NULL OR usage_count < $2 OR (usage_count = $2 AND id > $3)
+ORDER BY usage_count DESC, id ASC
+LIMIT $4
+                "#,
+                    as_of,
+                    last_usage_count,
+                    last_id,
+                    limit
+                )
+                .fetch_all(&self.pool)
+                .await
+            }
+            TagsOrderBy::CreatedAtDesc => {
+                listed_tags_query!(
+                    r#"
+SELECT
+    id AS "id!: Uuid",
+    title AS "title!",
+    created_at AS "created_at!: DateTime<Utc>",
+    updated_at AS "updated_at!: DateTime<Utc>",
+    usage_count AS "usage_count!: i64"
+FROM listed
+WHERE $2 IS NULL OR created_at < $2 OR (created_at = $2 AND id > $3)
+ORDER BY created_at DESC, id ASC
+LIMIT $4
+                "#,
+                    as_of,
+                    last_created_at,
+                    last_id,
+                    limit
+                )
+                .fetch_all(&self.pool)
+                .await
+            }
+        }
+        .map_err(from_sqlx_err)?;
+
+        let next_page_token =
+            PageToken::from_rows(&rows, page_size, order_by, as_of, total_size).map(Into::into);
+        let items = rows
+            .into_iter()
+            .map(|row| row.into_tag(with_usage_count))
+            .collect::<ApplicationResult<Vec<Tag>>>()?;
+
+        Ok(Page {
+            next_page_token,
+            items,
+            total_size: Some(total_size),
+        })
+    }
+}
+
+impl MergeTags for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn merge_tags(&self, source: &Tag, target: &mut Tag) -> ApplicationResult<()> {
+        let source_id = source.id();
+        let source_title = source.title().as_str();
+        let target_id = target.id();
+        let updated_at = Utc::now();
+
+        let mut tx = self.begin_transaction().await?;
+
+        let has_children = sqlx::query_scalar!(
+            r#"
+SELECT EXISTS(
+    SELECT 1 FROM tags WHERE substr(title, 1, length($1) + 1) = $1 || '/'
+) AS "exists!: bool"
+            "#,
+            source_title
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        if has_children {
+            return Err(ApplicationError::invalid_argument(
+                "can't merge a tag that has child tags",
+            ));
+        }
+
+        sqlx::query!(
+            r#"
+INSERT INTO remarks_tags ( remark_id, tag_id )
+SELECT remark_id, $2 FROM remarks_tags WHERE tag_id = $1
+ON CONFLICT DO NOTHING
+            "#,
+            source_id,
+            target_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        sqlx::query!("DELETE FROM remarks_tags WHERE tag_id = $1", source_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(from_sqlx_err)?;
+
+        sqlx::query!(
+            "UPDATE tag_aliases SET tag_id = $2 WHERE tag_id = $1",
+            source_id,
+            target_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        sqlx::query!(
+            r#"
+INSERT INTO tag_aliases ( title, tag_id, created_at )
+VALUES ( $1, $2, $3 )
+ON CONFLICT ( title ) DO UPDATE SET tag_id = excluded.tag_id
+            "#,
+            source_title,
+            target_id,
+            updated_at
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        sqlx::query!("DELETE FROM tags WHERE id = $1", source_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(from_sqlx_err)?;
+
+        let rec = sqlx::query!(
+            "UPDATE tags SET updated_at = $2 WHERE id = $1",
+            target_id,
+            updated_at
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        if rec.rows_affected() == 0 {
+            return Err(ApplicationError::NotFound);
+        }
+
+        target.set_updated_at(updated_at)?;
+
+        remarks::delete_unused_tags(&mut tx).await?;
+
+        commit_transaction(tx).await?;
+
+        Ok(())
+    }
+}
+
+impl SuggestTags for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn suggest_tags(&self, prefix: &str, limit: PageSize) -> ApplicationResult<Vec<Tag>> {
+        let limit = *limit;
+
+        sqlx::query_as!(
+            TagListingRow,
+            r#"
+SELECT
+    tags.id AS "id!: Uuid",
+    tags.title,
+    tags.created_at AS "created_at!: DateTime<Utc>",
+    tags.updated_at AS "updated_at!: DateTime<Utc>",
+    (
+        SELECT COUNT(*)
+        FROM remarks_tags
+        JOIN remarks ON remarks.id = remarks_tags.remark_id AND remarks.deleted_at IS NULL
+        WHERE remarks_tags.tag_id = tags.id
+    ) AS "usage_count!: i64"
+FROM tags
+WHERE substr(tags.title, 1, length($1)) = $1
+ORDER BY 5 DESC, tags.title ASC
+LIMIT $2
+            "#,
+            prefix,
+            limit,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(from_sqlx_err)?
+        .into_iter()
+        .map(|row| row.into_tag(true))
+        .collect()
+    }
+}
+
+impl UpdateTag for Repository {
+    #[tracing::instrument(skip_all)]
+    async fn update_tag(&self, tag: &mut Tag) -> Result<(), ApplicationError> {
+        let id = tag.id();
+        let title = tag.title().as_str();
+        let updated_at = Utc::now();
+
+        let mut tx = self.begin_transaction().await?;
+
+        let previous = sqlx::query!(
+            r#"SELECT title, updated_at AS "updated_at!: DateTime<Utc>" FROM tags WHERE id = $1"#,
+            id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        if previous.updated_at != tag.updated_at() {
+            return Err(ApplicationError::Conflict);
+        }
+
+        let previous_title = previous.title;
+
+        let title_taken = sqlx::query_scalar!(
+            r#"
+SELECT EXISTS(
+    SELECT 1 FROM tags
+    WHERE id <> $3 AND (
+        title = $2
+        OR title IN (
+            SELECT $2 || substr(descendants.title, length($1) + 1)
+            FROM tags AS descendants
+            WHERE substr(descendants.title, 1, length($1) + 1) = $1 || '/'
+        )
+    )
+) AS "exists!: bool"
+            "#,
+            previous_title,
+            title,
+            id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        if title_taken {
+            return Err(ApplicationError::invalid_argument(
+                "tag title is already taken, merge the tags instead",
+            ));
+        }
+
+        sqlx::query!(
+            "UPDATE tags SET title = $2, updated_at = $3 WHERE id = $1",
+            id,
+            title,
+            updated_at
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        sqlx::query!(
+            r#"
+UPDATE tags
+SET title = $2 || substr(title, length($1) + 1), updated_at = $4
+WHERE substr(title, 1, length($1) + 1) = $1 || '/' AND id <> $3
+            "#,
+            previous_title,
+            title,
+            id,
+            updated_at
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(from_sqlx_err)?;
+
+        sqlx::query!("DELETE FROM tag_aliases WHERE title = $1", title)
+            .execute(&mut *tx)
+            .await
+            .map_err(from_sqlx_err)?;
+
+        tag.set_updated_at(updated_at)?;
+
+        remarks::create_ancestor_tags(&mut tx, tag.title()).await?;
+
+        remarks::delete_unused_tags(&mut tx).await?;
+
+        commit_transaction(tx).await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PageToken {
+    id: Uuid,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_at: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    usage_count: Option<i64>,
+
+    #[serde(default)]
+    order_by: Option<String>,
+
+    #[serde(default)]
+    page_size: Option<i64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    as_of: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    total_size: Option<i64>,
+}
+
+impl TagListingRow {
+    fn into_tag(self, with_usage_count: bool) -> ApplicationResult<Tag> {
+        let TagListingRow {
+            id,
+            title,
+            created_at,
+            updated_at,
+            usage_count,
+        } = self;
+
+        let tag = Tag::new(TagAttributes {
+            id,
+            title: TagTitle::new(title)?,
+            created_at,
+            updated_at,
+            usage_count: with_usage_count.then_some(usage_count),
+        });
+
+        Ok(tag)
+    }
+}
+
+impl PageToken {
+    fn from_rows(
+        rows: &[TagListingRow],
+        page_size: PageSize,
+        order_by: TagsOrderBy,
+        as_of: DateTime<Utc>,
+        total_size: i64,
+    ) -> Option<Self> {
+        if rows.len() < *page_size as usize {
+            return None;
+        }
+
+        rows.last().map(|row| PageToken {
+            id: row.id,
+            created_at: (order_by == TagsOrderBy::CreatedAtDesc).then_some(row.created_at),
+            title: (order_by == TagsOrderBy::Title).then(|| row.title.clone()),
+            usage_count: (order_by == TagsOrderBy::UsageCount).then_some(row.usage_count),
+            order_by: Some(order_by.as_str().to_string()),
+            page_size: Some(*page_size),
+            as_of: Some(as_of),
+            total_size: Some(total_size),
+        })
+    }
+
+    fn order_by(&self) -> ApplicationResult<TagsOrderBy> {
+        self.order_by
+            .as_deref()
+            .map(str::parse)
+            .unwrap_or(Ok(TagsOrderBy::default()))
+            .map_err(|_err| ApplicationError::invalid_argument("malformed tags page token"))
+    }
+}
+
+impl FromStr for PageToken {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> eyre::Result<Self> {
+        use base64::Engine;
+
+        let json = URL_SAFE_NO_PAD_ENGINE.decode(s)?;
+        let token = serde_json::from_slice(&json)?;
+
+        Ok(token)
+    }
+}
+
+impl TryFrom<canopus_definitions::PageToken> for PageToken {
+    type Error = ApplicationError;
+
+    fn try_from(value: canopus_definitions::PageToken) -> ApplicationResult<Self> {
+        value
+            .parse()
+            .map_err(|_err| ApplicationError::invalid_argument("malformed tags page token"))
+    }
+}
+
+impl std::fmt::Display for PageToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use base64::Engine;
+
+        let json = serde_json::to_string(&self).map_err(|_err| std::fmt::Error)?;
+
+        let encoded_json = URL_SAFE_NO_PAD_ENGINE.encode(json);
+
+        f.write_str(&encoded_json)
+    }
+}
+
+impl From<PageToken> for canopus_definitions::PageToken {
+    fn from(value: PageToken) -> Self {
+        value.to_string().into()
+    }
+}
+
+impl TryFrom<TagRow> for Tag {
+    type Error = ApplicationError;
+
+    fn try_from(value: TagRow) -> ApplicationResult<Self> {
+        let TagRow {
+            id,
+            title,
+            created_at,
+            updated_at,
+        } = value;
+
+        let tag = Self::new(TagAttributes {
+            id,
+            title: TagTitle::new(title)?,
+            created_at,
+            updated_at,
+            usage_count: None,
+        });
+
+        Ok(tag)
+    }
+}
+
+impl TryFrom<TagTitleRow> for TagTitle {
+    type Error = ApplicationError;
+
+    fn try_from(value: TagTitleRow) -> ApplicationResult<Self> {
+        let TagTitleRow { title } = value;
+
+        TagTitle::new(title)
+    }
+}
@@ -9,8 +9,8 @@ async fn main() -> Result<()> {
     let mut stream = connect().await?;
 
     let mut buf = vec![0; 1024];
-    stream.read(&mut buf).await?;
-    let message = std::str::from_utf8(&mut buf)?;
+    let n = stream.read(&mut buf).await?;
+    let message = std::str::from_utf8(&buf[..n])?;
     println!("Received: {}", message);
 
     loop {
@@ -21,8 +21,8 @@ async fn main() -> Result<()> {
         stream.write_all(input.as_bytes()).await?;
 
         let mut buf = vec![0; 1024];
-        stream.read(&mut buf).await?;
-        let message = std::str::from_utf8(&mut buf)?;
+        let n = stream.read(&mut buf).await?;
+        let message = std::str::from_utf8(&buf[..n])?;
         println!("Received: {}", message);
 
         if input == "exit" {
@@ -34,24 +34,32 @@ switch ($CommandName) {
     }
 
     "list" {
-        $command = $base_command + "List-Remarks"
-        $listing_command = $command + " | ConvertFrom-Csv"
-        $rows = Invoke-Expression $listing_command
+        $command = $base_command + "export-csv --page-size 20"
+        $token_file = New-TemporaryFile
 
-        do {
-            if ($rows.Count -eq 0) {
-                return
-            }
+        try {
+            $listing_command = $command + " 2> `"$token_file`" | ConvertFrom-Csv"
+            $rows = Invoke-Expression $listing_command
 
-            $rows | Format-Table
+            do {
+                if ($rows.Count -eq 0) {
+                    return
+                }
 
-            if (-not(LoadNextPage)) {
-                return
-            }
+                $rows | Format-Table
 
-            $listing_command = $command + " --LoadNextPage | ConvertFrom-Csv"
-            $rows = Invoke-Expression $listing_command
-        } while($true)
+                $page_token = Get-Content $token_file -Raw
+
+                if (-not($page_token) -or -not(LoadNextPage)) {
+                    return
+                }
+
+                $listing_command = $command + " --page-token $($page_token.Trim()) 2> `"$token_file`" | ConvertFrom-Csv"
+                $rows = Invoke-Expression $listing_command
+            } while($true)
+        } finally {
+            Remove-Item $token_file
+        }
     }
 
     "new" {